@@ -8,59 +8,113 @@ export type SellResult = {
     sol_amount: bigint;
 };
 
+// mirrors the u128 checked arithmetic of amm.rs, null where rust gets None
+const U128_MAX = (1n << 128n) - 1n;
+
+const checked = (value: bigint): bigint | null =>
+    value < 0n || value > U128_MAX ? null : value;
+
+// a * b / c rounded down or up, like mul_div_floor and mul_div_ceil in amm/math.rs
+const mulDivFloor = (a: bigint, b: bigint, c: bigint): bigint | null => {
+    const product = checked(a * b);
+    return product === null || c === 0n ? null : product / c;
+};
+
+const mulDivCeil = (a: bigint, b: bigint, c: bigint): bigint | null => {
+    const product = checked(a * b);
+    if (product === null || c === 0n) return null;
+    return product % c === 0n ? product / c : checked(product / c + 1n);
+};
+
+// results are narrowed to u64 the way amm.rs casts them
+const asU64 = (value: bigint): bigint => BigInt.asUintN(64, value);
+
 export class AMM {
     constructor(
         public virtualSolReserves: bigint,
         public virtualTokenReserves: bigint,
         public realSolReserves: bigint,
-        public realTokenReserves: bigint,
-        public initialVirtualTokenReserves: bigint
+        public realTokenReserves: bigint
     ) {}
 
-    getBuyPrice(tokens: bigint): bigint {
-        const productOfReserves = this.virtualSolReserves * this.virtualTokenReserves;
-        const newVirtualTokenReserves = this.virtualTokenReserves - tokens;
-        const newVirtualSolReserves = (productOfReserves / newVirtualTokenReserves) + 1n;
-        const amountNeeded = newVirtualSolReserves - this.virtualSolReserves;
+    getBuyPrice(tokens: bigint): bigint | null {
+        if (tokens === 0n || tokens > this.virtualTokenReserves) {
+            return null;
+        }
+
+        const newVirtualTokenReserves = checked(this.virtualTokenReserves - tokens);
+        if (newVirtualTokenReserves === null) return null;
 
-        return amountNeeded;
+        // rounds up in the curve's favour
+        const newVirtualSolReserves = mulDivCeil(this.virtualSolReserves, this.virtualTokenReserves, newVirtualTokenReserves);
+        if (newVirtualSolReserves === null) return null;
+
+        return checked(newVirtualSolReserves - this.virtualSolReserves);
     }
 
-    applyBuy(token_amount: bigint): BuyResult {
+    applyBuy(token_amount: bigint): BuyResult | null {
         const final_token_amount = token_amount > this.realTokenReserves ? this.realTokenReserves : token_amount;
+
         const sol_amount = this.getBuyPrice(final_token_amount);
+        if (sol_amount === null) return null;
+
+        const virtualTokenReserves = checked(this.virtualTokenReserves - final_token_amount);
+        if (virtualTokenReserves === null) return null;
+        this.virtualTokenReserves = virtualTokenReserves;
 
-        this.virtualTokenReserves = this.virtualTokenReserves - final_token_amount;
-        this.realTokenReserves = this.realTokenReserves - final_token_amount;
+        const realTokenReserves = checked(this.realTokenReserves - final_token_amount);
+        if (realTokenReserves === null) return null;
+        this.realTokenReserves = realTokenReserves;
 
-        this.virtualSolReserves = this.virtualSolReserves + sol_amount;
-        this.realSolReserves = this.realSolReserves + sol_amount;
+        const virtualSolReserves = checked(this.virtualSolReserves + sol_amount);
+        if (virtualSolReserves === null) return null;
+        this.virtualSolReserves = virtualSolReserves;
+
+        const realSolReserves = checked(this.realSolReserves + sol_amount);
+        if (realSolReserves === null) return null;
+        this.realSolReserves = realSolReserves;
 
         return {
-            token_amount: final_token_amount,
-            sol_amount: sol_amount
+            token_amount: asU64(final_token_amount),
+            sol_amount: asU64(sol_amount)
         }
     }
 
-    applySell(token_amount: bigint): SellResult {
-        this.virtualTokenReserves = this.virtualTokenReserves + token_amount;
-        this.realTokenReserves = this.realTokenReserves + token_amount;
+    applySell(token_amount: bigint): SellResult | null {
+        const virtualTokenReserves = checked(this.virtualTokenReserves + token_amount);
+        if (virtualTokenReserves === null) return null;
+        this.virtualTokenReserves = virtualTokenReserves;
+
+        const realTokenReserves = checked(this.realTokenReserves + token_amount);
+        if (realTokenReserves === null) return null;
+        this.realTokenReserves = realTokenReserves;
 
         const sell_price = this.getSellPrice(token_amount);
+        if (sell_price === null) return null;
 
-        this.virtualSolReserves = this.virtualSolReserves - sell_price;
-        this.realSolReserves = this.realSolReserves - sell_price;
+        const virtualSolReserves = checked(this.virtualSolReserves - sell_price);
+        if (virtualSolReserves === null) return null;
+        this.virtualSolReserves = virtualSolReserves;
+
+        const realSolReserves = checked(this.realSolReserves - sell_price);
+        if (realSolReserves === null) return null;
+        this.realSolReserves = realSolReserves;
 
         return {
-            token_amount: token_amount,
-            sol_amount: sell_price
+            token_amount: asU64(token_amount),
+            sol_amount: asU64(sell_price)
         }
     }
 
-    getSellPrice(tokens: bigint): bigint {
-        const scaling_factor = this.initialVirtualTokenReserves;
-        const token_sell_proportion = (tokens * scaling_factor) / this.virtualTokenReserves;
-        const sol_received = (this.virtualSolReserves * token_sell_proportion) / scaling_factor;
+    getSellPrice(tokens: bigint): bigint | null {
+        if (tokens === 0n || tokens > this.virtualTokenReserves) {
+            return null;
+        }
+
+        // rounds down in the curve's favour
+        const sol_received = mulDivFloor(this.virtualSolReserves, tokens, this.virtualTokenReserves);
+        if (sol_received === null) return null;
+
         return sol_received < this.realSolReserves ? sol_received : this.realSolReserves;
     }
-}
\ No newline at end of file
+}
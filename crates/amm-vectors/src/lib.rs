@@ -0,0 +1,220 @@
+//! Golden vectors for `curve_launchpad::amm::AMM`.
+//!
+//! `client/amm.ts` reimplements the curve math, so both implementations are
+//! checked against one corpus, `tests/vectors/amm.json`, generated here from
+//! the Rust `AMM`. Amounts are decimal strings since they don't fit in a
+//! JavaScript number. Run `cargo run -p amm-vectors` after changing the math.
+
+use curve_launchpad::amm::AMM;
+use serde::{Deserialize, Serialize};
+
+pub const CORPUS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../tests/vectors/amm.json");
+
+const MAINNET_VIRTUAL_SOL_RESERVES: u128 = 30_000_000_000;
+const MAINNET_VIRTUAL_TOKEN_RESERVES: u128 = 1_073_000_000_000_000;
+const MAINNET_REAL_TOKEN_RESERVES: u128 = 793_100_000_000_000;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Vector {
+    pub name: String,
+    pub state: State,
+    pub action: Action,
+    pub expected: Expected,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct State {
+    #[serde(with = "decimal")]
+    pub virtual_sol_reserves: u128,
+    #[serde(with = "decimal")]
+    pub virtual_token_reserves: u128,
+    #[serde(with = "decimal")]
+    pub real_sol_reserves: u128,
+    #[serde(with = "decimal")]
+    pub real_token_reserves: u128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Action {
+    GetBuyPrice {
+        #[serde(with = "decimal")]
+        amount: u128,
+    },
+    GetSellPrice {
+        #[serde(with = "decimal")]
+        amount: u128,
+    },
+    ApplyBuy {
+        #[serde(with = "decimal")]
+        amount: u128,
+    },
+    ApplySell {
+        #[serde(with = "decimal")]
+        amount: u128,
+    },
+}
+
+/// What the action returned, `None` where the Rust `AMM` returns `None`, and
+/// the curve afterwards.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Expected {
+    pub result: Option<Outcome>,
+    pub state: State,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum Outcome {
+    Price(#[serde(with = "decimal")] u128),
+    Trade {
+        #[serde(with = "decimal")]
+        token_amount: u128,
+        #[serde(with = "decimal")]
+        sol_amount: u128,
+    },
+}
+
+impl State {
+    pub fn to_amm(&self) -> AMM {
+        AMM::new(
+            self.virtual_sol_reserves,
+            self.virtual_token_reserves,
+            self.real_sol_reserves,
+            self.real_token_reserves,
+        )
+    }
+
+    pub fn from_amm(amm: &AMM) -> Self {
+        State {
+            virtual_sol_reserves: amm.virtual_sol_reserves,
+            virtual_token_reserves: amm.virtual_token_reserves,
+            real_sol_reserves: amm.real_sol_reserves,
+            real_token_reserves: amm.real_token_reserves,
+        }
+    }
+}
+
+/// Runs `action` on `state` with the Rust `AMM`.
+pub fn run(state: &State, action: Action) -> Expected {
+    let mut amm = state.to_amm();
+
+    let result = match action {
+        Action::GetBuyPrice { amount } => amm.get_buy_price(amount).map(Outcome::Price),
+        Action::GetSellPrice { amount } => amm.get_sell_price(amount).map(Outcome::Price),
+        Action::ApplyBuy { amount } => amm.apply_buy(amount).map(|result| Outcome::Trade {
+            token_amount: result.token_amount as u128,
+            sol_amount: result.sol_amount as u128,
+        }),
+        Action::ApplySell { amount } => amm.apply_sell(amount).map(|result| Outcome::Trade {
+            token_amount: result.token_amount as u128,
+            sol_amount: result.sol_amount as u128,
+        }),
+    };
+
+    Expected {
+        result,
+        state: State::from_amm(&amm),
+    }
+}
+
+/// Every state in the corpus crossed with every action and amount.
+pub fn generate() -> Vec<Vector> {
+    let mut vectors = Vec::new();
+
+    for (state_name, state) in states() {
+        let amounts = [
+            ("zero", 0),
+            ("one", 1),
+            ("small", 100),
+            ("million", 1_000_000),
+            ("trillion", 1_000_000_000_000),
+            ("real_token_reserves", state.real_token_reserves),
+            ("virtual_token_reserves", state.virtual_token_reserves),
+            ("past_virtual_token_reserves", state.virtual_token_reserves + 1),
+            ("u64_max", u64::MAX as u128),
+        ];
+
+        for (amount_name, amount) in amounts {
+            let actions = [
+                ("get_buy_price", Action::GetBuyPrice { amount }),
+                ("get_sell_price", Action::GetSellPrice { amount }),
+                ("apply_buy", Action::ApplyBuy { amount }),
+                ("apply_sell", Action::ApplySell { amount }),
+            ];
+
+            for (action_name, action) in actions {
+                vectors.push(Vector {
+                    name: format!("{state_name}/{action_name}/{amount_name}"),
+                    state: state.clone(),
+                    action,
+                    expected: run(&state, action),
+                });
+            }
+        }
+    }
+
+    vectors
+}
+
+fn states() -> Vec<(&'static str, State)> {
+    let launch = State {
+        virtual_sol_reserves: MAINNET_VIRTUAL_SOL_RESERVES,
+        virtual_token_reserves: MAINNET_VIRTUAL_TOKEN_RESERVES,
+        real_sol_reserves: 0,
+        real_token_reserves: MAINNET_REAL_TOKEN_RESERVES,
+    };
+
+    let after_buying = |tokens: u128| {
+        let mut amm = launch.to_amm();
+        amm.apply_buy(tokens).unwrap();
+        State::from_amm(&amm)
+    };
+
+    vec![
+        (
+            "small",
+            State {
+                virtual_sol_reserves: 1_000,
+                virtual_token_reserves: 1_000,
+                real_sol_reserves: 500,
+                real_token_reserves: 500,
+            },
+        ),
+        (
+            "small_no_sol",
+            State {
+                virtual_sol_reserves: 600,
+                virtual_token_reserves: 600,
+                real_sol_reserves: 0,
+                real_token_reserves: 500,
+            },
+        ),
+        ("mainnet_launch", launch.clone()),
+        ("mainnet_half_sold", after_buying(MAINNET_REAL_TOKEN_RESERVES / 2)),
+        ("mainnet_nearly_complete", after_buying(MAINNET_REAL_TOKEN_RESERVES - 1_000_000)),
+        ("mainnet_complete", after_buying(MAINNET_REAL_TOKEN_RESERVES)),
+        //products of reserves that overflow u128
+        (
+            "overflowing",
+            State {
+                virtual_sol_reserves: u128::MAX / 2,
+                virtual_token_reserves: u64::MAX as u128,
+                real_sol_reserves: u64::MAX as u128,
+                real_token_reserves: u64::MAX as u128,
+            },
+        ),
+    ]
+}
+
+mod decimal {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &u128, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(D::Error::custom)
+    }
+}
@@ -0,0 +1,15 @@
+//! Regenerates the AMM golden vectors, to `tests/vectors/amm.json` unless
+//! another path is given.
+
+use std::{env, fs};
+
+fn main() {
+    let path = env::args().nth(1).unwrap_or_else(|| amm_vectors::CORPUS_PATH.to_string());
+
+    let vectors = amm_vectors::generate();
+    let mut json = serde_json::to_string_pretty(&vectors).expect("vectors serialize");
+    json.push('\n');
+
+    fs::write(&path, json).unwrap_or_else(|err| panic!("writing {path}: {err}"));
+    println!("wrote {} vectors to {path}", vectors.len());
+}
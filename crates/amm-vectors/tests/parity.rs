@@ -0,0 +1,26 @@
+//! The Rust `AMM` against the checked in corpus, which `client/amm.ts` is
+//! tested against as well.
+
+use std::fs;
+
+use amm_vectors::{generate, run, Vector, CORPUS_PATH};
+
+fn corpus() -> Vec<Vector> {
+    let json = fs::read_to_string(CORPUS_PATH).expect("tests/vectors/amm.json exists");
+    serde_json::from_str(&json).expect("corpus parses")
+}
+
+#[test]
+fn amm_matches_corpus() {
+    for vector in corpus() {
+        assert_eq!(run(&vector.state, vector.action), vector.expected, "{}", vector.name);
+    }
+}
+
+#[test]
+fn corpus_is_up_to_date() {
+    assert!(
+        corpus() == generate(),
+        "tests/vectors/amm.json is stale, regenerate it with `cargo run -p amm-vectors`"
+    );
+}
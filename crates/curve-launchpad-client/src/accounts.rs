@@ -0,0 +1,74 @@
+//! Decoders for the program's account data, as returned by `getAccountInfo`.
+//!
+//! Accounts that haven't been through `migrate_global`/`migrate_curve` yet are
+//! upgraded in memory, so callers always see the current layout.
+
+use anchor_lang::{AccountDeserialize, Result};
+use curve_launchpad::state::{upgrade_bonding_curve, upgrade_global, BondingCurve, CreatorStats, CurveOracle, Global, MintVault};
+
+pub fn decode_global(data: &[u8]) -> Result<Global> {
+    match upgrade_global(data)? {
+        Some(global) => Ok(global),
+        None => Global::try_deserialize(&mut &data[..]),
+    }
+}
+
+pub fn decode_bonding_curve(data: &[u8]) -> Result<BondingCurve> {
+    match upgrade_bonding_curve(data)? {
+        Some(bonding_curve) => Ok(bonding_curve),
+        None => BondingCurve::try_deserialize(&mut &data[..]),
+    }
+}
+
+pub fn decode_curve_oracle(data: &[u8]) -> Result<CurveOracle> {
+    CurveOracle::try_deserialize(&mut &data[..])
+}
+
+pub fn decode_creator_stats(data: &[u8]) -> Result<CreatorStats> {
+    CreatorStats::try_deserialize(&mut &data[..])
+}
+
+pub fn decode_mint_vault(data: &[u8]) -> Result<MintVault> {
+    MintVault::try_deserialize(&mut &data[..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{prelude::Pubkey, AccountSerialize, Space};
+    use curve_launchpad::state::CurveStatus;
+
+    const GLOBAL_V0: &[u8] = include_bytes!("../../../programs/curve-launchpad/tests/fixtures/global_v0.bin");
+    const BONDING_CURVE_V0: &[u8] =
+        include_bytes!("../../../programs/curve-launchpad/tests/fixtures/bonding_curve_v0.bin");
+
+    #[test]
+    fn test_decode_legacy_accounts() {
+        let global = decode_global(GLOBAL_V0).unwrap();
+        assert_eq!(global.version, Global::CURRENT_VERSION);
+        assert_eq!(global.fee_basis_points, 50);
+
+        let bonding_curve = decode_bonding_curve(BONDING_CURVE_V0).unwrap();
+        assert_eq!(bonding_curve.version, BondingCurve::CURRENT_VERSION);
+        assert_eq!(bonding_curve.status, CurveStatus::Trading);
+    }
+
+    #[test]
+    fn test_decode_current_accounts() {
+        let mut bonding_curve = decode_bonding_curve(BONDING_CURVE_V0).unwrap();
+        bonding_curve.creator = Pubkey::new_unique();
+
+        let mut data = vec![0u8; 8 + BondingCurve::INIT_SPACE];
+        bonding_curve.try_serialize(&mut &mut data[..]).unwrap();
+
+        let decoded = decode_bonding_curve(&data).unwrap();
+        assert_eq!(decoded.creator, bonding_curve.creator);
+        assert_eq!(decoded.real_sol_reserves, bonding_curve.real_sol_reserves);
+    }
+
+    #[test]
+    fn test_decode_wrong_account() {
+        assert!(decode_global(BONDING_CURVE_V0).is_err());
+        assert!(decode_bonding_curve(GLOBAL_V0).is_err());
+    }
+}
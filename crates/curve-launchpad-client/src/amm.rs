@@ -0,0 +1,138 @@
+//! Addresses of the dynamic AMM and vault program accounts `create_pool`
+//! passes through, derived the way those programs derive them.
+
+use anchor_lang::{
+    prelude::{pubkey, Pubkey},
+    solana_program::{system_program, sysvar},
+};
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address},
+    metadata::mpl_token_metadata,
+    token::{self, spl_token},
+};
+use curve_launchpad::{accounts, dynamic_amm};
+
+use crate::pda;
+
+pub const VAULT_PROGRAM_ID: Pubkey = pubkey!("24Uqj9JCLxUeoC3hGfh5W3s9FM9uCHDS2SG3LYwBpyTi");
+pub const VAULT_BASE_KEY: Pubkey = pubkey!("HWzXGcGHy4tcpYfaRDCyLNzXqBTv3E6BttpCH2vJxArv");
+
+/// The pool's quote token, graduated curves are paired with SOL.
+pub const NATIVE_MINT: Pubkey = spl_token::native_mint::ID;
+
+/// A pool's address is the same whichever way round its mints are given.
+pub fn pool(token_a_mint: &Pubkey, token_b_mint: &Pubkey, config: &Pubkey) -> Pubkey {
+    let (first, second) = (token_a_mint.max(token_b_mint), token_a_mint.min(token_b_mint));
+
+    find(&[first.as_ref(), second.as_ref(), config.as_ref()])
+}
+
+pub fn lp_mint(pool: &Pubkey) -> Pubkey {
+    find(&[b"lp_mint", pool.as_ref()])
+}
+
+pub fn protocol_fee(mint: &Pubkey, pool: &Pubkey) -> Pubkey {
+    find(&[b"fee", mint.as_ref(), pool.as_ref()])
+}
+
+pub fn lock_escrow(pool: &Pubkey, owner: &Pubkey) -> Pubkey {
+    find(&[b"lock_escrow", pool.as_ref(), owner.as_ref()])
+}
+
+/// The vault program's shared vault for `mint`.
+pub fn vault(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"vault", mint.as_ref(), VAULT_BASE_KEY.as_ref()], &VAULT_PROGRAM_ID).0
+}
+
+/// The token account holding `vault`'s deposits.
+pub fn vault_token_vault(vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"token_vault", vault.as_ref()], &VAULT_PROGRAM_ID).0
+}
+
+pub fn vault_lp_mint(vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"lp_mint", vault.as_ref()], &VAULT_PROGRAM_ID).0
+}
+
+/// The pool's position in `vault`.
+pub fn vault_lp(vault: &Pubkey, pool: &Pubkey) -> Pubkey {
+    find(&[vault.as_ref(), pool.as_ref()])
+}
+
+/// Accounts for creating the SOL / `mint` pool with `config`, seeded from
+/// `payer`'s WSOL and token accounts and locked in its lock escrow.
+pub fn create_pool_accounts(payer: &Pubkey, config: &Pubkey, mint: &Pubkey) -> accounts::CreatePool {
+    let pool = pool(&NATIVE_MINT, mint, config);
+    let lp_mint = lp_mint(&pool);
+    let a_vault = vault(&NATIVE_MINT);
+    let b_vault = vault(mint);
+    let lock_escrow = lock_escrow(&pool, payer);
+
+    accounts::CreatePool {
+        pool,
+        config: *config,
+        lp_mint,
+        token_a_mint: NATIVE_MINT,
+        token_b_mint: *mint,
+        a_vault,
+        b_vault,
+        a_token_vault: vault_token_vault(&a_vault),
+        b_token_vault: vault_token_vault(&b_vault),
+        a_vault_lp_mint: vault_lp_mint(&a_vault),
+        b_vault_lp_mint: vault_lp_mint(&b_vault),
+        a_vault_lp: vault_lp(&a_vault, &pool),
+        b_vault_lp: vault_lp(&b_vault, &pool),
+        payer_token_a: get_associated_token_address(payer, &NATIVE_MINT),
+        payer_token_b: get_associated_token_address(payer, mint),
+        payer_pool_lp: get_associated_token_address(payer, &lp_mint),
+        protocol_token_a_fee: protocol_fee(&NATIVE_MINT, &pool),
+        protocol_token_b_fee: protocol_fee(mint, &pool),
+        payer: *payer,
+        rent: sysvar::rent::ID,
+        mint_metadata: pda::metadata(&lp_mint),
+        metadata_program: mpl_token_metadata::ID,
+        vault_program: VAULT_PROGRAM_ID,
+        token_program: token::ID,
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+        dynamic_amm_program: dynamic_amm::ID,
+        global: pda::global(),
+        bonding_curve: pda::bonding_curve(mint),
+        lock_escrow,
+        lock_escrow_token_account: get_associated_token_address(&lock_escrow, &lp_mint),
+    }
+}
+
+fn find(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &dynamic_amm::ID).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_ignores_mint_order() {
+        let mint = Pubkey::new_unique();
+        let config = Pubkey::new_unique();
+
+        assert_eq!(pool(&NATIVE_MINT, &mint, &config), pool(&mint, &NATIVE_MINT, &config));
+        assert_ne!(pool(&NATIVE_MINT, &mint, &config), pool(&NATIVE_MINT, &mint, &Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_create_pool_accounts() {
+        let payer = Pubkey::new_unique();
+        let config = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+
+        let accounts = create_pool_accounts(&payer, &config, &mint);
+
+        assert_eq!(accounts.pool, pool(&NATIVE_MINT, &mint, &config));
+        assert_eq!(accounts.token_b_mint, mint);
+        assert_eq!(accounts.b_vault, vault(&mint));
+        assert_eq!(accounts.b_token_vault, vault_token_vault(&vault(&mint)));
+        assert_eq!(accounts.payer_token_a, get_associated_token_address(&payer, &NATIVE_MINT));
+        assert_eq!(accounts.bonding_curve, pda::bonding_curve(&mint));
+        assert_eq!(accounts.lock_escrow, lock_escrow(&accounts.pool, &payer));
+    }
+}
@@ -0,0 +1,594 @@
+//! Instruction builders for every instruction in the program.
+//!
+//! Arguments that don't fit in a couple of parameters are taken as the
+//! program's own generated instruction structs, so they can't drift.
+
+use anchor_lang::{
+    prelude::{pubkey, Pubkey},
+    solana_program::{instruction::Instruction, system_program, sysvar},
+    InstructionData, ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address},
+    metadata::mpl_token_metadata,
+    token,
+};
+use curve_launchpad::{accounts, instruction};
+
+use crate::pda;
+
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey = pubkey!("ComputeBudget111111111111111111111111111111");
+
+/// Compute units to give `create`. Its Metaplex CPIs take about 115k with
+/// `verify_creator`, the rest covers the program's own PDA lookups,
+/// account setup and event, which the default 200k doesn't always leave room for.
+pub const CREATE_COMPUTE_UNIT_LIMIT: u32 = 300_000;
+
+/// `args` names the launchpad collection NFT `initialize` mints.
+pub fn initialize(authority: &Pubkey, args: instruction::Initialize) -> Instruction {
+    build(
+        accounts::Initialize {
+            authority: *authority,
+            global: pda::global(),
+            mint_authority: pda::mint_authority(),
+            collection_mint: pda::collection_mint(),
+            collection_token_account: pda::collection_token_account(),
+            collection_metadata: pda::metadata(&pda::collection_mint()),
+            collection_master_edition: pda::master_edition(&pda::collection_mint()),
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+            rent: sysvar::rent::ID,
+        },
+        args,
+    )
+}
+
+/// Creates the collection of a deployment initialized before there was one.
+pub fn init_collection(authority: &Pubkey, args: instruction::InitCollection) -> Instruction {
+    build(
+        accounts::InitCollection {
+            authority: *authority,
+            global: pda::global(),
+            mint_authority: pda::mint_authority(),
+            collection_mint: pda::collection_mint(),
+            collection_token_account: pda::collection_token_account(),
+            collection_metadata: pda::metadata(&pda::collection_mint()),
+            collection_master_edition: pda::master_edition(&pda::collection_mint()),
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+            rent: sysvar::rent::ID,
+        },
+        args,
+    )
+}
+
+pub fn set_params(authority: &Pubkey, args: instruction::SetParams) -> Instruction {
+    build(
+        accounts::SetParams {
+            global: pda::global(),
+            user: *authority,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority(),
+            program: curve_launchpad::ID,
+        },
+        args,
+    )
+}
+
+pub fn set_create_controls(authority: &Pubkey, args: instruction::SetCreateControls) -> Instruction {
+    build(
+        accounts::SetParams {
+            global: pda::global(),
+            user: *authority,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority(),
+            program: curve_launchpad::ID,
+        },
+        args,
+    )
+}
+
+/// `mint_suffix` is the base58 ending `create` requires of mints, empty for any.
+pub fn set_mint_suffix(authority: &Pubkey, mint_suffix: &str) -> Instruction {
+    build(
+        accounts::SetParams {
+            global: pda::global(),
+            user: *authority,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority(),
+            program: curve_launchpad::ID,
+        },
+        instruction::SetMintSuffix {
+            mint_suffix: mint_suffix.to_string(),
+        },
+    )
+}
+
+/// Lets `creator` create curves while the allowlist is on, or stops it.
+pub fn set_creator_allowed(authority: &Pubkey, creator: &Pubkey, allowed: bool) -> Instruction {
+    build(
+        accounts::SetCreatorAllowed {
+            global: pda::global(),
+            user: *authority,
+            creator: *creator,
+            creator_stats: pda::creator_stats(creator),
+            system_program: system_program::ID,
+            event_authority: pda::event_authority(),
+            program: curve_launchpad::ID,
+        },
+        instruction::SetCreatorAllowed { allowed },
+    )
+}
+
+/// `fee_recipient` must be `Global::fee_recipient`, it receives the creation fee.
+/// The fresh `mint` keypair signs alongside the creator.
+pub fn create(
+    mint: &Pubkey,
+    creator: &Pubkey,
+    fee_recipient: &Pubkey,
+    args: instruction::Create,
+) -> Instruction {
+    let mut ix = create_accounts(mint, creator, fee_recipient, None, args);
+    //anchor only marks `Signer` accounts, the mint is checked by hand
+    if let Some(meta) = ix.accounts.iter_mut().find(|meta| meta.pubkey == *mint) {
+        meta.is_signer = true;
+    }
+    ix
+}
+
+/// Launches `mint`, which has to be queued in the mint vault, instead of a fresh keypair.
+pub fn create_from_vault(
+    mint: &Pubkey,
+    creator: &Pubkey,
+    fee_recipient: &Pubkey,
+    args: instruction::Create,
+) -> Instruction {
+    create_accounts(mint, creator, fee_recipient, Some(pda::mint_vault()), args)
+}
+
+/// Sets the transaction's compute unit limit, goes before the instructions
+/// it's for, e.g. `CREATE_COMPUTE_UNIT_LIMIT` ahead of `create`.
+pub fn set_compute_unit_limit(units: u32) -> Instruction {
+    //`ComputeBudgetInstruction::SetComputeUnitLimit`, borsh variant 2
+    let mut data = vec![2];
+    data.extend_from_slice(&units.to_le_bytes());
+
+    Instruction {
+        program_id: COMPUTE_BUDGET_PROGRAM_ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+fn create_accounts(
+    mint: &Pubkey,
+    creator: &Pubkey,
+    fee_recipient: &Pubkey,
+    mint_vault: Option<Pubkey>,
+    args: instruction::Create,
+) -> Instruction {
+    build(
+        accounts::Create {
+            mint: *mint,
+            creator: *creator,
+            mint_authority: pda::mint_authority(),
+            bonding_curve: pda::bonding_curve(mint),
+            curve_oracle: pda::curve_oracle(mint),
+            bonding_curve_token_account: pda::bonding_curve_token_account(mint),
+            global: pda::global(),
+            fee_recipient: *fee_recipient,
+            creator_stats: pda::creator_stats(creator),
+            metadata: pda::metadata(mint),
+            collection_mint: pda::collection_mint(),
+            collection_metadata: pda::metadata(&pda::collection_mint()),
+            collection_master_edition: pda::master_edition(&pda::collection_mint()),
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+            rent: sysvar::rent::ID,
+            mint_vault,
+            event_authority: pda::event_authority(),
+            program: curve_launchpad::ID,
+        },
+        args,
+    )
+}
+
+/// The authority queues a pre-generated `mint` keypair, which signs, for `create_from_vault`.
+pub fn deposit_vault_mint(authority: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        accounts::DepositVaultMint {
+            authority: *authority,
+            global: pda::global(),
+            mint: *mint,
+            mint_authority: pda::mint_authority(),
+            mint_vault: pda::mint_vault(),
+            system_program: system_program::ID,
+            token_program: token::ID,
+            rent: sysvar::rent::ID,
+            event_authority: pda::event_authority(),
+            program: curve_launchpad::ID,
+        },
+        instruction::DepositVaultMint {},
+    )
+}
+
+pub fn buy(
+    user: &Pubkey,
+    mint: &Pubkey,
+    fee_recipient: &Pubkey,
+    token_amount: u64,
+    max_sol_cost: u64,
+) -> Instruction {
+    build(
+        accounts::Buy {
+            user: *user,
+            global: pda::global(),
+            fee_recipient: *fee_recipient,
+            mint: *mint,
+            bonding_curve: pda::bonding_curve(mint),
+            curve_oracle: pda::curve_oracle(mint),
+            bonding_curve_token_account: pda::bonding_curve_token_account(mint),
+            user_token_account: get_associated_token_address(user, mint),
+            system_program: system_program::ID,
+            token_program: token::ID,
+            event_authority: pda::event_authority(),
+            program: curve_launchpad::ID,
+        },
+        instruction::Buy {
+            token_amount,
+            max_sol_cost,
+        },
+    )
+}
+
+pub fn sell(
+    user: &Pubkey,
+    mint: &Pubkey,
+    fee_recipient: &Pubkey,
+    token_amount: u64,
+    min_sol_output: u64,
+) -> Instruction {
+    build(
+        accounts::Sell {
+            user: *user,
+            global: pda::global(),
+            fee_recipient: *fee_recipient,
+            mint: *mint,
+            bonding_curve: pda::bonding_curve(mint),
+            curve_oracle: pda::curve_oracle(mint),
+            bonding_curve_token_account: pda::bonding_curve_token_account(mint),
+            user_token_account: get_associated_token_address(user, mint),
+            system_program: system_program::ID,
+            token_program: token::ID,
+            event_authority: pda::event_authority(),
+            program: curve_launchpad::ID,
+        },
+        instruction::Sell {
+            token_amount,
+            min_sol_output,
+        },
+    )
+}
+
+/// Read-only, meant to be simulated. The program returns a borsh encoded
+/// `curve_launchpad::instructions::Quote` as return data.
+pub fn quote_buy(mint: &Pubkey, token_amount: u64) -> Instruction {
+    build(quote_accounts(mint), instruction::QuoteBuy { token_amount })
+}
+
+/// Read-only, see [`quote_buy`].
+pub fn quote_sell(mint: &Pubkey, token_amount: u64) -> Instruction {
+    build(quote_accounts(mint), instruction::QuoteSell { token_amount })
+}
+
+/// Creates the oracle of a curve created before curves had one.
+pub fn init_curve_oracle(payer: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        accounts::InitCurveOracle {
+            payer: *payer,
+            mint: *mint,
+            bonding_curve: pda::bonding_curve(mint),
+            curve_oracle: pda::curve_oracle(mint),
+            system_program: system_program::ID,
+        },
+        instruction::InitCurveOracle {},
+    )
+}
+
+/// Read-only, meant to be simulated like [`quote_buy`]. Returns a
+/// `curve_launchpad::instructions::Twap` over at least the last `window` seconds.
+pub fn get_twap(mint: &Pubkey, window: i64) -> Instruction {
+    build(
+        accounts::GetTwap {
+            mint: *mint,
+            curve_oracle: pda::curve_oracle(mint),
+        },
+        instruction::GetTwap { window },
+    )
+}
+
+pub fn withdraw(withdraw_authority: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        accounts::Withdraw {
+            user: *withdraw_authority,
+            global: pda::global(),
+            mint: *mint,
+            last_withdraw: pda::last_withdraw(),
+            bonding_curve: pda::bonding_curve(mint),
+            bonding_curve_token_account: pda::bonding_curve_token_account(mint),
+            user_token_account: get_associated_token_address(withdraw_authority, mint),
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            mint_authority: pda::mint_authority(),
+            metadata: pda::metadata(mint),
+            token_metadata_program: mpl_token_metadata::ID,
+            event_authority: pda::event_authority(),
+            program: curve_launchpad::ID,
+        },
+        instruction::Withdraw {},
+    )
+}
+
+pub fn redeem(user: &Pubkey, mint: &Pubkey, token_amount: u64) -> Instruction {
+    build(
+        accounts::Redeem {
+            user: *user,
+            global: pda::global(),
+            mint: *mint,
+            bonding_curve: pda::bonding_curve(mint),
+            bonding_curve_token_account: pda::bonding_curve_token_account(mint),
+            user_token_account: get_associated_token_address(user, mint),
+            system_program: system_program::ID,
+            token_program: token::ID,
+            event_authority: pda::event_authority(),
+            program: curve_launchpad::ID,
+        },
+        instruction::Redeem { token_amount },
+    )
+}
+
+/// Only the curve's creator can sign, and only until the curve graduates.
+pub fn update_metadata(creator: &Pubkey, mint: &Pubkey, args: instruction::UpdateMetadata) -> Instruction {
+    build(
+        accounts::UpdateMetadata {
+            creator: *creator,
+            mint: *mint,
+            bonding_curve: pda::bonding_curve(mint),
+            mint_authority: pda::mint_authority(),
+            metadata: pda::metadata(mint),
+            token_metadata_program: mpl_token_metadata::ID,
+            event_authority: pda::event_authority(),
+            program: curve_launchpad::ID,
+        },
+        args,
+    )
+}
+
+pub fn set_curve_frozen(authority: &Pubkey, mint: &Pubkey, frozen: bool) -> Instruction {
+    build(
+        accounts::SetCurveFrozen {
+            global: pda::global(),
+            user: *authority,
+            mint: *mint,
+            bonding_curve: pda::bonding_curve(mint),
+            event_authority: pda::event_authority(),
+            program: curve_launchpad::ID,
+        },
+        instruction::SetCurveFrozen { frozen },
+    )
+}
+
+/// `creator` must be the creator recorded on the curve, it receives the rent.
+pub fn close_curve(user: &Pubkey, creator: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        accounts::CloseCurve {
+            user: *user,
+            creator: *creator,
+            global: pda::global(),
+            mint: *mint,
+            bonding_curve: pda::bonding_curve(mint),
+            curve_oracle: pda::curve_oracle(mint),
+            bonding_curve_token_account: pda::bonding_curve_token_account(mint),
+            graduated_curve: pda::graduated_curve(mint),
+            system_program: system_program::ID,
+            token_program: token::ID,
+            event_authority: pda::event_authority(),
+            program: curve_launchpad::ID,
+        },
+        instruction::CloseCurve {},
+    )
+}
+
+pub fn migrate_global(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateGlobal {
+            user: *authority,
+            global: pda::global(),
+            system_program: system_program::ID,
+        },
+        instruction::MigrateGlobal {},
+    )
+}
+
+/// `creator` is recorded on curves from before curves had one.
+pub fn migrate_curve(authority: &Pubkey, mint: &Pubkey, creator: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateCurve {
+            user: *authority,
+            global: pda::global(),
+            mint: *mint,
+            bonding_curve: pda::bonding_curve(mint),
+            system_program: system_program::ID,
+        },
+        instruction::MigrateCurve { creator: *creator },
+    )
+}
+
+/// The AMM's own accounts depend on the pool being created, so they're
+/// taken as they are.
+pub fn create_pool(accounts: accounts::CreatePool) -> Instruction {
+    build(accounts, instruction::CreatePool {})
+}
+
+fn quote_accounts(mint: &Pubkey) -> accounts::QuoteCurve {
+    accounts::QuoteCurve {
+        global: pda::global(),
+        mint: *mint,
+        bonding_curve: pda::bonding_curve(mint),
+        bonding_curve_token_account: pda::bonding_curve_token_account(mint),
+    }
+}
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: curve_launchpad::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{AnchorDeserialize, Discriminator};
+
+    #[test]
+    fn test_buy() {
+        let user = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let fee_recipient = Pubkey::new_unique();
+
+        let ix = buy(&user, &mint, &fee_recipient, 1_000, 2_000);
+
+        assert_eq!(ix.program_id, curve_launchpad::ID);
+        assert_eq!(&ix.data[..8], &instruction::Buy::DISCRIMINATOR);
+        assert_eq!(&ix.data[8..16], &1_000u64.to_le_bytes());
+        assert_eq!(&ix.data[16..], &2_000u64.to_le_bytes());
+
+        assert_eq!(ix.accounts[0].pubkey, user);
+        assert!(ix.accounts[0].is_signer && ix.accounts[0].is_writable);
+        assert_eq!(ix.accounts[2].pubkey, fee_recipient);
+        assert!(ix.accounts[2].is_writable);
+        assert_eq!(ix.accounts[4].pubkey, pda::bonding_curve(&mint));
+        assert_eq!(ix.accounts[5].pubkey, pda::curve_oracle(&mint));
+        assert!(ix.accounts[5].is_writable);
+        assert_eq!(ix.accounts[7].pubkey, get_associated_token_address(&user, &mint));
+        assert_eq!(ix.accounts.last().unwrap().pubkey, curve_launchpad::ID);
+    }
+
+    #[test]
+    fn test_set_compute_unit_limit() {
+        let ix = set_compute_unit_limit(CREATE_COMPUTE_UNIT_LIMIT);
+
+        assert_eq!(ix.program_id, COMPUTE_BUDGET_PROGRAM_ID);
+        assert!(ix.accounts.is_empty());
+        assert_eq!(ix.data, [2, 0xe0, 0x93, 0x04, 0x00]);
+    }
+
+    #[test]
+    fn test_create() {
+        let mint = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+        let fee_recipient = Pubkey::new_unique();
+
+        let ix = create(
+            &mint,
+            &creator,
+            &fee_recipient,
+            instruction::Create {
+                name: "test".to_string(),
+                symbol: "tst".to_string(),
+                uri: "https://www.test.com".to_string(),
+                graduation_deadline: None,
+                graduation_target: None,
+                start_time: None,
+                verify_creator: false,
+                decimals: None,
+                token_supply: None,
+            },
+        );
+
+        assert_eq!(&ix.data[..8], &instruction::Create::DISCRIMINATOR);
+        //the mint keypair signs alongside the creator
+        let signers: Vec<_> = ix
+            .accounts
+            .iter()
+            .filter(|meta| meta.is_signer)
+            .map(|meta| meta.pubkey)
+            .collect();
+        assert_eq!(signers, vec![mint, creator]);
+        assert_eq!(ix.accounts[7].pubkey, fee_recipient);
+        assert!(ix.accounts[7].is_writable);
+        assert_eq!(ix.accounts[8].pubkey, pda::creator_stats(&creator));
+        assert_eq!(ix.accounts[9].pubkey, pda::metadata(&mint));
+        assert_eq!(ix.accounts[10].pubkey, pda::collection_mint());
+        //the mint authority verifies the token into the collection
+        assert!(ix.accounts[2].is_writable);
+        //no vault, anchor's placeholder for a missing optional account
+        assert_eq!(ix.accounts[18].pubkey, curve_launchpad::ID);
+
+        let args = instruction::Create::try_from_slice(&ix.data[8..]).unwrap();
+        let ix = create_from_vault(&mint, &creator, &fee_recipient, args);
+        assert!(ix.accounts.iter().all(|meta| meta.pubkey != mint || !meta.is_signer));
+        assert_eq!(ix.accounts[18].pubkey, pda::mint_vault());
+        assert!(ix.accounts[18].is_writable);
+    }
+
+    #[test]
+    fn test_signers() {
+        let key = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+
+        let signers = |ix: Instruction| {
+            ix.accounts
+                .into_iter()
+                .filter(|meta| meta.is_signer)
+                .map(|meta| meta.pubkey)
+                .collect::<Vec<_>>()
+        };
+
+        let initialize_args = instruction::Initialize {
+            collection_name: "Curve Launchpad".to_string(),
+            collection_symbol: "LAUNCH".to_string(),
+            collection_uri: "https://www.test.com/collection.json".to_string(),
+        };
+        let collection_args = instruction::InitCollection {
+            name: "Curve Launchpad".to_string(),
+            symbol: "LAUNCH".to_string(),
+            uri: "https://www.test.com/collection.json".to_string(),
+        };
+        assert_eq!(signers(initialize(&key, initialize_args)), vec![key]);
+        assert_eq!(signers(init_collection(&key, collection_args)), vec![key]);
+        assert_eq!(signers(sell(&key, &mint, &Pubkey::new_unique(), 1, 0)), vec![key]);
+        assert_eq!(signers(withdraw(&key, &mint)), vec![key]);
+        assert_eq!(signers(redeem(&key, &mint, 1)), vec![key]);
+        assert_eq!(signers(set_curve_frozen(&key, &mint, true)), vec![key]);
+        assert_eq!(
+            signers(update_metadata(
+                &key,
+                &mint,
+                instruction::UpdateMetadata {
+                    name: "test".to_string(),
+                    symbol: "tst".to_string(),
+                    uri: "https://www.test.com".to_string(),
+                },
+            )),
+            vec![key]
+        );
+        assert_eq!(signers(close_curve(&key, &Pubkey::new_unique(), &mint)), vec![key]);
+        assert_eq!(signers(migrate_global(&key)), vec![key]);
+        assert_eq!(signers(migrate_curve(&key, &mint, &Pubkey::new_unique())), vec![key]);
+        assert!(signers(quote_buy(&mint, 1)).is_empty());
+        assert!(signers(quote_sell(&mint, 1)).is_empty());
+        assert_eq!(signers(init_curve_oracle(&key, &mint)), vec![key]);
+        assert!(signers(get_twap(&mint, 60)).is_empty());
+        assert_eq!(signers(set_creator_allowed(&key, &Pubkey::new_unique(), true)), vec![key]);
+    }
+}
@@ -0,0 +1,12 @@
+//! Off-chain helpers for the curve launchpad program.
+//!
+//! Everything here is pure: PDAs, instructions, account decoding and quotes
+//! are computed locally, so the crate has no RPC dependency and builds for WASM.
+
+pub mod accounts;
+pub mod amm;
+pub mod instructions;
+pub mod pda;
+pub mod quote;
+
+pub use curve_launchpad::{self, ID as PROGRAM_ID};
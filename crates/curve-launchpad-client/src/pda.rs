@@ -0,0 +1,120 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::{associated_token::get_associated_token_address, metadata::mpl_token_metadata};
+use curve_launchpad::{
+    instructions::COLLECTION_MINT_SEED,
+    state::{BondingCurve, CreatorStats, CurveOracle, Global, GraduatedCurve, LastWithdraw, MintVault},
+};
+
+pub const MINT_AUTHORITY_SEED: &[u8] = b"mint-authority";
+pub const METADATA_SEED: &[u8] = b"metadata";
+pub const EDITION_SEED: &[u8] = b"edition";
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+pub fn global() -> Pubkey {
+    find(&[Global::SEED_PREFIX])
+}
+
+pub fn bonding_curve(mint: &Pubkey) -> Pubkey {
+    find(&[BondingCurve::SEED_PREFIX, mint.as_ref()])
+}
+
+/// The bonding curve's associated token account, which holds the curve's tokens.
+pub fn bonding_curve_token_account(mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(&bonding_curve(mint), mint)
+}
+
+pub fn curve_oracle(mint: &Pubkey) -> Pubkey {
+    find(&[CurveOracle::SEED_PREFIX, mint.as_ref()])
+}
+
+pub fn creator_stats(creator: &Pubkey) -> Pubkey {
+    find(&[CreatorStats::SEED_PREFIX, creator.as_ref()])
+}
+
+pub fn mint_vault() -> Pubkey {
+    find(&[MintVault::SEED_PREFIX])
+}
+
+pub fn mint_authority() -> Pubkey {
+    find(&[MINT_AUTHORITY_SEED])
+}
+
+pub fn last_withdraw() -> Pubkey {
+    find(&[LastWithdraw::SEED_PREFIX])
+}
+
+pub fn graduated_curve(mint: &Pubkey) -> Pubkey {
+    find(&[GraduatedCurve::SEED_PREFIX, mint.as_ref()])
+}
+
+/// The Metaplex metadata account of a launched mint.
+pub fn metadata(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[METADATA_SEED, mpl_token_metadata::ID.as_ref(), mint.as_ref()],
+        &mpl_token_metadata::ID,
+    )
+    .0
+}
+
+/// The Metaplex master edition account of a mint, only the collection has one.
+pub fn master_edition(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[METADATA_SEED, mpl_token_metadata::ID.as_ref(), mint.as_ref(), EDITION_SEED],
+        &mpl_token_metadata::ID,
+    )
+    .0
+}
+
+/// Mint of the launchpad collection NFT every launched token is verified into.
+pub fn collection_mint() -> Pubkey {
+    find(&[COLLECTION_MINT_SEED])
+}
+
+/// The mint authority's associated token account, which holds the collection NFT.
+pub fn collection_token_account() -> Pubkey {
+    get_associated_token_address(&mint_authority(), &collection_mint())
+}
+
+/// Signer of the self-CPI that `emit_cpi!` events are logged through.
+pub fn event_authority() -> Pubkey {
+    find(&[EVENT_AUTHORITY_SEED])
+}
+
+fn find(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &curve_launchpad::ID).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeds_match_program() {
+        let mint = Pubkey::new_unique();
+
+        assert_eq!(
+            bonding_curve(&mint),
+            Pubkey::find_program_address(&[b"bonding-curve", mint.as_ref()], &curve_launchpad::ID).0
+        );
+        assert_eq!(global(), Pubkey::find_program_address(&[b"global"], &curve_launchpad::ID).0);
+        assert_eq!(
+            last_withdraw(),
+            Pubkey::find_program_address(&[b"last-withdraw"], &curve_launchpad::ID).0
+        );
+        assert_eq!(
+            curve_oracle(&mint),
+            Pubkey::find_program_address(&[b"curve-oracle", mint.as_ref()], &curve_launchpad::ID).0
+        );
+        assert_ne!(bonding_curve(&mint), bonding_curve(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_bonding_curve_token_account() {
+        let mint = Pubkey::new_unique();
+
+        assert_eq!(
+            bonding_curve_token_account(&mint),
+            get_associated_token_address(&bonding_curve(&mint), &mint)
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! Buy and sell quotes computed with the program's own curve math.
+
+use curve_launchpad::{
+    amm::AMM,
+    instructions::calculate_fee,
+    state::{BondingCurve, Global},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuyQuote {
+    /// Tokens that will be received, less than requested if the buy graduates the curve.
+    pub token_amount: u64,
+    /// SOL paid into the curve.
+    pub sol_amount: u64,
+    /// SOL paid to the fee recipient on top of `sol_amount`.
+    pub fee: u64,
+}
+
+impl BuyQuote {
+    /// The lowest `max_sol_cost` that lets the buy go through.
+    pub fn total_cost(&self) -> u64 {
+        self.sol_amount + self.fee
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SellQuote {
+    pub token_amount: u64,
+    /// SOL taken out of the curve, before the fee.
+    pub sol_amount: u64,
+    /// Part of `sol_amount` paid to the fee recipient.
+    pub fee: u64,
+}
+
+impl SellQuote {
+    /// The highest `min_sol_output` that lets the sell go through.
+    pub fn net_output(&self) -> u64 {
+        self.sol_amount - self.fee
+    }
+}
+
+/// The curve's AMM, set up the way `buy` and `sell` set it up.
+pub fn amm(bonding_curve: &BondingCurve) -> AMM {
+    AMM::new(
+        bonding_curve.virtual_sol_reserves as u128,
+        bonding_curve.virtual_token_reserves as u128,
+        bonding_curve.real_sol_reserves as u128,
+        bonding_curve.real_token_reserves as u128,
+    )
+}
+
+/// Quotes a buy of `token_amount`, `None` if the curve can't fill it.
+///
+/// Only the curve math is checked, callers should look at `bonding_curve.status` first.
+pub fn quote_buy(global: &Global, bonding_curve: &BondingCurve, token_amount: u64) -> Option<BuyQuote> {
+    if token_amount == 0 {
+        return None;
+    }
+
+    let mut amm = amm(bonding_curve);
+
+    let fill_amount = match bonding_curve.tokens_to_graduate(&amm) {
+        Some(tokens) if tokens < token_amount as u128 => tokens as u64,
+        _ => token_amount,
+    };
+    if fill_amount > bonding_curve.real_token_reserves {
+        return None;
+    }
+
+    let result = amm.apply_buy(fill_amount as u128)?;
+
+    Some(BuyQuote {
+        token_amount: result.token_amount,
+        sol_amount: result.sol_amount,
+        fee: calculate_fee(result.sol_amount, global.fee_basis_points),
+    })
+}
+
+/// Quotes a sell of `token_amount`, `None` if the curve can't take it.
+///
+/// Only the curve math is checked, callers should look at `bonding_curve.status` first.
+pub fn quote_sell(global: &Global, bonding_curve: &BondingCurve, token_amount: u64) -> Option<SellQuote> {
+    if token_amount == 0 {
+        return None;
+    }
+
+    let result = amm(bonding_curve).apply_sell(token_amount as u128)?;
+
+    Some(SellQuote {
+        token_amount: result.token_amount,
+        sol_amount: result.sol_amount,
+        fee: calculate_fee(result.sol_amount, global.fee_basis_points),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accounts::{decode_bonding_curve, decode_global};
+    use curve_launchpad::state::GraduationTarget;
+
+    fn fixtures() -> (Global, BondingCurve) {
+        (
+            decode_global(include_bytes!("../../../programs/curve-launchpad/tests/fixtures/global_v0.bin")).unwrap(),
+            decode_bonding_curve(include_bytes!(
+                "../../../programs/curve-launchpad/tests/fixtures/bonding_curve_v0.bin"
+            ))
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_quote_buy_matches_amm() {
+        let (global, mut bonding_curve) = fixtures();
+        bonding_curve.graduation_target = None;
+
+        let quote = quote_buy(&global, &bonding_curve, 10_000_000_000_000).unwrap();
+        let expected = amm(&bonding_curve).get_buy_price(10_000_000_000_000).unwrap() as u64;
+
+        assert_eq!(quote.token_amount, 10_000_000_000_000);
+        assert_eq!(quote.sol_amount, expected);
+        assert_eq!(quote.fee, calculate_fee(expected, 50));
+        assert_eq!(quote.total_cost(), expected + quote.fee);
+
+        assert_eq!(quote_buy(&global, &bonding_curve, 0), None);
+        assert_eq!(quote_buy(&global, &bonding_curve, u64::MAX), None);
+    }
+
+    #[test]
+    fn test_quote_buy_stops_at_graduation_target() {
+        let (global, mut bonding_curve) = fixtures();
+        bonding_curve.graduation_target = Some(GraduationTarget::SolRaised(2_000_000_000));
+
+        let quote = quote_buy(&global, &bonding_curve, bonding_curve.real_token_reserves).unwrap();
+
+        assert!(quote.token_amount < bonding_curve.real_token_reserves);
+        assert!(bonding_curve.real_sol_reserves + quote.sol_amount >= 2_000_000_000);
+    }
+
+    #[test]
+    fn test_quote_sell() {
+        let (global, bonding_curve) = fixtures();
+
+        let quote = quote_sell(&global, &bonding_curve, 10_000_000).unwrap();
+        let expected = {
+            let mut amm = amm(&bonding_curve);
+            amm.apply_sell(10_000_000).unwrap().sol_amount
+        };
+
+        assert_eq!(quote.sol_amount, expected);
+        assert_eq!(quote.net_output(), expected - calculate_fee(expected, 50));
+        assert_eq!(quote_sell(&global, &bonding_curve, 0), None);
+    }
+}
@@ -0,0 +1,484 @@
+//! [`Amm`] over a launchpad `BondingCurve`, quoted with the program's own curve math.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use curve_launchpad_client::{
+    accounts::{decode_bonding_curve, decode_global},
+    curve_launchpad::{
+        self,
+        state::{BondingCurve, CurveStatus, Global},
+    },
+    instructions, pda,
+    quote::{self, BuyQuote},
+};
+use serde_json::Value;
+
+use crate::{
+    AccountMap, Amm, AmmContext, ClockRef, KeyedAccount, Quote, QuoteParams, Swap, SwapAndAccountMetas, SwapMode,
+    SwapParams, NATIVE_MINT,
+};
+
+/// One launchpad curve as a market. Its key is the bonding curve account, and
+/// as the curve doesn't record its mint the keyed account's params must carry
+/// it as `{ "mint": "<address>" }`.
+#[derive(Clone)]
+pub struct CurveLaunchpadAmm {
+    key: Pubkey,
+    mint: Pubkey,
+    bonding_curve: BondingCurve,
+    //fees and the fee recipient live on global, which only arrives with the first update
+    global: Option<Global>,
+    clock_ref: ClockRef,
+}
+
+impl CurveLaunchpadAmm {
+    pub fn mint(&self) -> Pubkey {
+        self.mint
+    }
+
+    fn global(&self) -> Result<&Global> {
+        self.global
+            .as_ref()
+            .ok_or_else(|| anyhow!("global hasn't been loaded, update the market before quoting"))
+    }
+
+    fn is_buy(&self, input_mint: &Pubkey, output_mint: &Pubkey) -> Result<bool> {
+        if *input_mint == NATIVE_MINT && *output_mint == self.mint {
+            Ok(true)
+        } else if *input_mint == self.mint && *output_mint == NATIVE_MINT {
+            Ok(false)
+        } else {
+            bail!("curve of {} doesn't trade {input_mint} for {output_mint}", self.mint)
+        }
+    }
+
+    //total cost only grows with the token amount, so the largest buy `lamports` pays for is found by bisection
+    fn buy_with(&self, global: &Global, lamports: u64) -> Option<BuyQuote> {
+        let (mut low, mut high) = (0, self.bonding_curve.real_token_reserves);
+        let mut best = None;
+
+        while low < high {
+            let mid = low + (high - low).div_ceil(2);
+
+            match quote::quote_buy(global, &self.bonding_curve, mid) {
+                Some(quote) if quote.total_cost() <= lamports => {
+                    best = Some(quote);
+                    low = mid;
+                }
+                _ => high = mid - 1,
+            }
+        }
+
+        best
+    }
+}
+
+impl Amm for CurveLaunchpadAmm {
+    fn from_keyed_account(keyed_account: &KeyedAccount, amm_context: &AmmContext) -> Result<Self> {
+        let key = keyed_account.key;
+
+        let mint: Pubkey = keyed_account
+            .params
+            .as_ref()
+            .and_then(|params| params.get("mint"))
+            .and_then(Value::as_str)
+            .with_context(|| format!("market {key} has no mint in its params"))?
+            .parse()?;
+
+        ensure!(key == pda::bonding_curve(&mint), "{key} isn't the bonding curve of {mint}");
+        ensure!(
+            keyed_account.account.owner == curve_launchpad::ID,
+            "{key} isn't owned by the launchpad"
+        );
+
+        let bonding_curve = decode_bonding_curve(&keyed_account.account.data)
+            .map_err(|err| anyhow!("failed to decode bonding curve {key}: {err}"))?;
+
+        Ok(CurveLaunchpadAmm {
+            key,
+            mint,
+            bonding_curve,
+            global: None,
+            clock_ref: amm_context.clock_ref.clone(),
+        })
+    }
+
+    fn label(&self) -> String {
+        "Curve Launchpad".to_string()
+    }
+
+    fn program_id(&self) -> Pubkey {
+        curve_launchpad::ID
+    }
+
+    fn key(&self) -> Pubkey {
+        self.key
+    }
+
+    fn get_reserve_mints(&self) -> Vec<Pubkey> {
+        vec![NATIVE_MINT, self.mint]
+    }
+
+    fn get_accounts_to_update(&self) -> Vec<Pubkey> {
+        vec![self.key, pda::global()]
+    }
+
+    fn update(&mut self, account_map: &AccountMap) -> Result<()> {
+        let account = account_map
+            .get(&self.key)
+            .with_context(|| format!("missing bonding curve {}", self.key))?;
+        self.bonding_curve = decode_bonding_curve(&account.data)
+            .map_err(|err| anyhow!("failed to decode bonding curve {}: {err}", self.key))?;
+
+        let account = account_map
+            .get(&pda::global())
+            .context("missing the launchpad's global account")?;
+        self.global = Some(decode_global(&account.data).map_err(|err| anyhow!("failed to decode global: {err}"))?);
+
+        Ok(())
+    }
+
+    fn quote(&self, quote_params: &QuoteParams) -> Result<Quote> {
+        let global = self.global()?;
+        let is_buy = self.is_buy(&quote_params.input_mint, &quote_params.output_mint)?;
+        let amount = quote_params.amount;
+
+        ensure!(self.is_active(), "curve of {} isn't trading", self.mint);
+        ensure!(
+            quote_params.swap_mode == SwapMode::ExactIn,
+            "launchpad curves only quote exact in"
+        );
+
+        if is_buy {
+            let quote = self
+                .buy_with(global, amount)
+                .with_context(|| format!("{amount} lamports don't buy any tokens"))?;
+
+            Ok(Quote {
+                in_amount: quote.total_cost(),
+                out_amount: quote.token_amount,
+                fee_amount: quote.fee,
+                fee_mint: NATIVE_MINT,
+            })
+        } else {
+            //a scheduled curve opens with a buy, it can't be sold into before that
+            ensure!(
+                self.bonding_curve.status == CurveStatus::Trading,
+                "curve of {} hasn't opened yet",
+                self.mint
+            );
+
+            let quote = quote::quote_sell(global, &self.bonding_curve, amount)
+                .with_context(|| format!("curve can't take a sell of {amount}"))?;
+
+            Ok(Quote {
+                in_amount: quote.token_amount,
+                out_amount: quote.net_output(),
+                fee_amount: quote.fee,
+                fee_mint: NATIVE_MINT,
+            })
+        }
+    }
+
+    fn get_swap_and_account_metas(&self, swap_params: &SwapParams) -> Result<SwapAndAccountMetas> {
+        let global = self.global()?;
+        let is_buy = self.is_buy(&swap_params.source_mint, &swap_params.destination_mint)?;
+
+        let user = swap_params.token_transfer_authority;
+        let user_token_account = get_associated_token_address(&user, &self.mint);
+
+        let (swap, instruction) = if is_buy {
+            ensure!(
+                swap_params.destination_token_account == user_token_account,
+                "the curve only pays out to the user's associated token account"
+            );
+
+            let swap = Swap::Buy {
+                token_amount: swap_params.out_amount,
+                max_sol_cost: swap_params.in_amount,
+            };
+            let instruction = instructions::buy(
+                &user,
+                &self.mint,
+                &global.fee_recipient,
+                swap_params.out_amount,
+                swap_params.in_amount,
+            );
+            (swap, instruction)
+        } else {
+            ensure!(
+                swap_params.source_token_account == user_token_account,
+                "the curve only takes tokens from the user's associated token account"
+            );
+
+            let swap = Swap::Sell {
+                token_amount: swap_params.in_amount,
+                min_sol_output: swap_params.out_amount,
+            };
+            let instruction = instructions::sell(
+                &user,
+                &self.mint,
+                &global.fee_recipient,
+                swap_params.in_amount,
+                swap_params.out_amount,
+            );
+            (swap, instruction)
+        };
+
+        Ok(SwapAndAccountMetas {
+            swap,
+            account_metas: instruction.accounts,
+        })
+    }
+
+    fn is_active(&self) -> bool {
+        let now = self.clock_ref.unix_timestamp();
+
+        let open = match self.bonding_curve.status {
+            CurveStatus::Trading => true,
+            CurveStatus::Scheduled => now >= self.bonding_curve.start_time,
+            _ => false,
+        };
+
+        open && !self.bonding_curve.missed_deadline(now)
+    }
+
+    fn clone_amm(&self) -> Box<dyn Amm + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{solana_program::instruction::Instruction, AccountSerialize, Space};
+    use serde_json::json;
+    use solana_sdk::account::Account;
+
+    const GLOBAL_V0: &[u8] = include_bytes!("../../../programs/curve-launchpad/tests/fixtures/global_v0.bin");
+    const BONDING_CURVE_V0: &[u8] =
+        include_bytes!("../../../programs/curve-launchpad/tests/fixtures/bonding_curve_v0.bin");
+
+    fn account(data: &[u8]) -> Account {
+        Account {
+            lamports: 1_000_000_000,
+            data: data.to_vec(),
+            owner: curve_launchpad::ID,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    fn keyed_account(mint: &Pubkey, data: &[u8]) -> KeyedAccount {
+        KeyedAccount {
+            key: pda::bonding_curve(mint),
+            account: account(data),
+            params: Some(json!({ "mint": mint.to_string() })),
+        }
+    }
+
+    //a market loaded from the snapshots the way a router would, `edit` changes the curve first
+    fn market(edit: impl FnOnce(&mut BondingCurve)) -> CurveLaunchpadAmm {
+        let mint = Pubkey::new_unique();
+
+        let mut bonding_curve = decode_bonding_curve(BONDING_CURVE_V0).unwrap();
+        bonding_curve.graduation_target = None;
+        edit(&mut bonding_curve);
+
+        let mut data = vec![0u8; 8 + BondingCurve::INIT_SPACE];
+        bonding_curve.try_serialize(&mut &mut data[..]).unwrap();
+
+        let mut amm = CurveLaunchpadAmm::from_keyed_account(&keyed_account(&mint, &data), &AmmContext::default()).unwrap();
+
+        let account_map: AccountMap = amm
+            .get_accounts_to_update()
+            .into_iter()
+            .zip([account(&data), account(GLOBAL_V0)])
+            .collect();
+        amm.update(&account_map).unwrap();
+
+        amm
+    }
+
+    fn buy_params(amm: &CurveLaunchpadAmm, amount: u64) -> QuoteParams {
+        QuoteParams {
+            amount,
+            input_mint: NATIVE_MINT,
+            output_mint: amm.mint(),
+            swap_mode: SwapMode::ExactIn,
+        }
+    }
+
+    fn sell_params(amm: &CurveLaunchpadAmm, amount: u64) -> QuoteParams {
+        QuoteParams {
+            amount,
+            input_mint: amm.mint(),
+            output_mint: NATIVE_MINT,
+            swap_mode: SwapMode::ExactIn,
+        }
+    }
+
+    #[test]
+    fn test_from_keyed_account() {
+        let mint = Pubkey::new_unique();
+
+        let amm = CurveLaunchpadAmm::from_keyed_account(&keyed_account(&mint, BONDING_CURVE_V0), &AmmContext::default())
+            .unwrap();
+        assert_eq!(amm.key(), pda::bonding_curve(&mint));
+        assert_eq!(amm.get_reserve_mints(), vec![NATIVE_MINT, mint]);
+        assert_eq!(amm.get_accounts_to_update(), vec![pda::bonding_curve(&mint), pda::global()]);
+
+        //quoting needs global, which only comes with an update
+        assert!(amm.quote(&buy_params(&amm, 1_000_000_000)).is_err());
+
+        let no_mint = KeyedAccount {
+            params: None,
+            ..keyed_account(&mint, BONDING_CURVE_V0)
+        };
+        assert!(CurveLaunchpadAmm::from_keyed_account(&no_mint, &AmmContext::default()).is_err());
+
+        let wrong_mint = KeyedAccount {
+            params: Some(json!({ "mint": Pubkey::new_unique().to_string() })),
+            ..keyed_account(&mint, BONDING_CURVE_V0)
+        };
+        assert!(CurveLaunchpadAmm::from_keyed_account(&wrong_mint, &AmmContext::default()).is_err());
+
+        let not_a_curve = keyed_account(&mint, GLOBAL_V0);
+        assert!(CurveLaunchpadAmm::from_keyed_account(&not_a_curve, &AmmContext::default()).is_err());
+    }
+
+    #[test]
+    fn test_quote_buy_spends_at_most_the_input() {
+        let amm = market(|_| {});
+        let global = decode_global(GLOBAL_V0).unwrap();
+
+        let lamports = 1_000_000_000;
+        let quote = amm.quote(&buy_params(&amm, lamports)).unwrap();
+
+        assert!(quote.in_amount <= lamports);
+        assert_eq!(quote.fee_mint, NATIVE_MINT);
+
+        //it's the curve's own quote for those tokens, and one more token would cost too much
+        let expected = quote::quote_buy(&global, &amm.bonding_curve, quote.out_amount).unwrap();
+        assert_eq!(quote.in_amount, expected.total_cost());
+        assert_eq!(quote.fee_amount, expected.fee);
+
+        let one_more = quote::quote_buy(&global, &amm.bonding_curve, quote.out_amount + 1).unwrap();
+        assert!(one_more.total_cost() > lamports);
+
+        assert!(amm.quote(&buy_params(&amm, 0)).is_err());
+    }
+
+    #[test]
+    fn test_quote_buy_stops_at_the_curve_reserves() {
+        let amm = market(|_| {});
+
+        let quote = amm.quote(&buy_params(&amm, u64::MAX)).unwrap();
+        assert_eq!(quote.out_amount, amm.bonding_curve.real_token_reserves);
+    }
+
+    #[test]
+    fn test_quote_sell() {
+        let amm = market(|_| {});
+        let global = decode_global(GLOBAL_V0).unwrap();
+
+        let quote = amm.quote(&sell_params(&amm, 10_000_000)).unwrap();
+        let expected = quote::quote_sell(&global, &amm.bonding_curve, 10_000_000).unwrap();
+
+        assert_eq!(quote.in_amount, 10_000_000);
+        assert_eq!(quote.out_amount, expected.net_output());
+        assert_eq!(quote.fee_amount, expected.fee);
+    }
+
+    #[test]
+    fn test_quote_rejects_what_the_curve_wont_trade() {
+        let amm = market(|_| {});
+
+        let other_mint = QuoteParams {
+            output_mint: Pubkey::new_unique(),
+            ..buy_params(&amm, 1_000)
+        };
+        assert!(amm.quote(&other_mint).is_err());
+
+        let exact_out = QuoteParams {
+            swap_mode: SwapMode::ExactOut,
+            ..buy_params(&amm, 1_000)
+        };
+        assert!(amm.quote(&exact_out).is_err());
+
+        let complete = market(|curve| curve.status = CurveStatus::Complete);
+        assert!(!complete.is_active());
+        assert!(complete.quote(&buy_params(&complete, 1_000_000_000)).is_err());
+
+        let missed_deadline = market(|curve| curve.graduation_deadline = Some(0));
+        assert!(!missed_deadline.is_active());
+
+        //a scheduled curve past its start time opens with a buy, so it can be bought but not sold
+        let scheduled = market(|curve| {
+            curve.status = CurveStatus::Scheduled;
+            curve.start_time = 0;
+        });
+        assert!(scheduled.is_active());
+        assert!(scheduled.quote(&buy_params(&scheduled, 1_000_000_000)).is_ok());
+        assert!(scheduled.quote(&sell_params(&scheduled, 10_000_000)).is_err());
+
+        let not_started = market(|curve| {
+            curve.status = CurveStatus::Scheduled;
+            curve.start_time = 1;
+        });
+        assert!(!not_started.is_active());
+    }
+
+    #[test]
+    fn test_get_swap_and_account_metas() {
+        let amm = market(|_| {});
+        let global = decode_global(GLOBAL_V0).unwrap();
+        let user = Pubkey::new_unique();
+        let user_token_account = get_associated_token_address(&user, &amm.mint());
+
+        let buy = SwapParams {
+            swap_mode: SwapMode::ExactIn,
+            in_amount: 1_000_000_000,
+            out_amount: 30_000_000_000,
+            source_mint: NATIVE_MINT,
+            destination_mint: amm.mint(),
+            source_token_account: user,
+            destination_token_account: user_token_account,
+            token_transfer_authority: user,
+        };
+
+        let swap = amm.get_swap_and_account_metas(&buy).unwrap();
+        assert_eq!(
+            swap.swap,
+            Swap::Buy {
+                token_amount: 30_000_000_000,
+                max_sol_cost: 1_000_000_000
+            }
+        );
+
+        let expected: Instruction =
+            instructions::buy(&user, &amm.mint(), &global.fee_recipient, 30_000_000_000, 1_000_000_000);
+        assert_eq!(swap.instruction(), expected);
+
+        let sell = SwapParams {
+            in_amount: 30_000_000_000,
+            out_amount: 900_000_000,
+            source_mint: amm.mint(),
+            destination_mint: NATIVE_MINT,
+            source_token_account: user_token_account,
+            destination_token_account: user,
+            ..buy
+        };
+
+        let swap = amm.get_swap_and_account_metas(&sell).unwrap();
+        let expected = instructions::sell(&user, &amm.mint(), &global.fee_recipient, 30_000_000_000, 900_000_000);
+        assert_eq!(swap.instruction(), expected);
+
+        let other_token_account = SwapParams {
+            destination_token_account: Pubkey::new_unique(),
+            ..buy
+        };
+        assert!(amm.get_swap_and_account_metas(&other_token_account).is_err());
+    }
+}
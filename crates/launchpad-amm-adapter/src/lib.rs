@@ -0,0 +1,191 @@
+//! Aggregator adapter for launchpad curves.
+//!
+//! [`Amm`] follows the shape of the interface aggregators like Jupiter use to
+//! integrate a DEX: build a market from its account, refresh it from an
+//! account map, quote, then produce the swap's account metas. Routers
+//! written against it can include launchpad curves through
+//! [`CurveLaunchpadAmm`] without any curve specific code.
+//!
+//! The curve trades native SOL against its token. SOL is paid and received
+//! in lamports by the swap's `token_transfer_authority` rather than through a
+//! wrapped SOL account, `NATIVE_MINT` only names that side of the market.
+
+mod curve;
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicI64, Ordering},
+        Arc,
+    },
+};
+
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::instruction::{AccountMeta, Instruction},
+    InstructionData,
+};
+use anyhow::Result;
+use curve_launchpad_client::curve_launchpad::{self, instruction};
+use serde_json::Value;
+use solana_sdk::{account::Account, clock::Clock};
+
+pub use anchor_spl::token::spl_token::native_mint::ID as NATIVE_MINT;
+pub use curve::CurveLaunchpadAmm;
+
+pub type AccountMap = HashMap<Pubkey, Account>;
+
+/// A market's account as the router found it, `params` carries what the account alone doesn't say.
+#[derive(Clone, Debug)]
+pub struct KeyedAccount {
+    pub key: Pubkey,
+    pub account: Account,
+    pub params: Option<Value>,
+}
+
+/// The cluster clock, shared with every market so a router updates it once.
+#[derive(Clone, Debug, Default)]
+pub struct ClockRef {
+    pub unix_timestamp: Arc<AtomicI64>,
+}
+
+impl ClockRef {
+    pub fn update(&self, clock: Clock) {
+        self.unix_timestamp.store(clock.unix_timestamp, Ordering::Relaxed);
+    }
+
+    pub fn unix_timestamp(&self) -> i64 {
+        self.unix_timestamp.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct AmmContext {
+    pub clock_ref: ClockRef,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SwapMode {
+    /// `amount` is what goes in.
+    #[default]
+    ExactIn,
+    /// `amount` is what comes out.
+    ExactOut,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct QuoteParams {
+    pub amount: u64,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub swap_mode: SwapMode,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Quote {
+    pub in_amount: u64,
+    pub out_amount: u64,
+    pub fee_amount: u64,
+    pub fee_mint: Pubkey,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SwapParams {
+    pub swap_mode: SwapMode,
+    /// Tokens sold, or the most lamports a buy may cost.
+    pub in_amount: u64,
+    /// Tokens bought, or the least lamports a sell may pay.
+    pub out_amount: u64,
+    pub source_mint: Pubkey,
+    pub destination_mint: Pubkey,
+    pub source_token_account: Pubkey,
+    pub destination_token_account: Pubkey,
+    /// The user, who signs the swap.
+    pub token_transfer_authority: Pubkey,
+}
+
+/// The launchpad instruction a swap turns into, with its arguments.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Swap {
+    Buy { token_amount: u64, max_sol_cost: u64 },
+    Sell { token_amount: u64, min_sol_output: u64 },
+}
+
+#[derive(Clone, Debug)]
+pub struct SwapAndAccountMetas {
+    pub swap: Swap,
+    pub account_metas: Vec<AccountMeta>,
+}
+
+impl SwapAndAccountMetas {
+    pub fn instruction(self) -> Instruction {
+        let data = match self.swap {
+            Swap::Buy {
+                token_amount,
+                max_sol_cost,
+            } => instruction::Buy {
+                token_amount,
+                max_sol_cost,
+            }
+            .data(),
+            Swap::Sell {
+                token_amount,
+                min_sol_output,
+            } => instruction::Sell {
+                token_amount,
+                min_sol_output,
+            }
+            .data(),
+        };
+
+        Instruction {
+            program_id: curve_launchpad::ID,
+            accounts: self.account_metas,
+            data,
+        }
+    }
+}
+
+pub trait Amm {
+    fn from_keyed_account(keyed_account: &KeyedAccount, amm_context: &AmmContext) -> Result<Self>
+    where
+        Self: Sized;
+
+    /// A human readable name for the venue.
+    fn label(&self) -> String;
+
+    fn program_id(&self) -> Pubkey;
+
+    /// The market's address.
+    fn key(&self) -> Pubkey;
+
+    /// The mints the market trades.
+    fn get_reserve_mints(&self) -> Vec<Pubkey>;
+
+    /// The accounts `update` needs.
+    fn get_accounts_to_update(&self) -> Vec<Pubkey>;
+
+    /// Refreshes the market from freshly fetched accounts.
+    fn update(&mut self, account_map: &AccountMap) -> Result<()>;
+
+    fn quote(&self, quote_params: &QuoteParams) -> Result<Quote>;
+
+    fn get_swap_and_account_metas(&self, swap_params: &SwapParams) -> Result<SwapAndAccountMetas>;
+
+    fn supports_exact_out(&self) -> bool {
+        false
+    }
+
+    /// Whether the market can be traded on right now.
+    fn is_active(&self) -> bool {
+        true
+    }
+
+    fn clone_amm(&self) -> Box<dyn Amm + Send + Sync>;
+}
+
+impl Clone for Box<dyn Amm + Send + Sync> {
+    fn clone(&self) -> Self {
+        self.clone_amm()
+    }
+}
@@ -0,0 +1,182 @@
+//! Routes trades through `launchpad-amm-adapter` the way an aggregator would,
+//! from accounts fetched off the chain, and checks the swaps it builds do what
+//! it quoted.
+
+//the launchpad's own test environment
+#[path = "../../../programs/curve-launchpad/tests/common/mod.rs"]
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use common::*;
+use curve_launchpad::state::GraduationTarget;
+use curve_launchpad_client::pda;
+use launchpad_amm_adapter::{
+    AccountMap, Amm, AmmContext, CurveLaunchpadAmm, KeyedAccount, QuoteParams, SwapMode, SwapParams, NATIVE_MINT,
+};
+use serde_json::json;
+use solana_sdk::signer::Signer;
+
+async fn fetch(env: &mut TestEnv, addresses: Vec<Pubkey>) -> AccountMap {
+    let mut account_map = AccountMap::new();
+
+    for address in addresses {
+        let account = env.context.banks_client.get_account(address).await.unwrap().unwrap();
+        account_map.insert(address, account);
+    }
+
+    account_map
+}
+
+async fn load_market(env: &mut TestEnv, mint: &Pubkey) -> CurveLaunchpadAmm {
+    let key = pda::bonding_curve(mint);
+    let account = fetch(env, vec![key]).await.remove(&key).unwrap();
+
+    let keyed_account = KeyedAccount {
+        key,
+        account,
+        params: Some(json!({ "mint": mint.to_string() })),
+    };
+
+    let amm_context = AmmContext::default();
+    amm_context.clock_ref.update(env.context.banks_client.get_sysvar().await.unwrap());
+
+    let mut amm = CurveLaunchpadAmm::from_keyed_account(&keyed_account, &amm_context).unwrap();
+    let account_map = fetch(env, amm.get_accounts_to_update()).await;
+    amm.update(&account_map).unwrap();
+
+    amm
+}
+
+fn swap_params(user: &Pubkey, mint: &Pubkey, is_buy: bool, in_amount: u64, out_amount: u64) -> SwapParams {
+    let user_token_account = get_associated_token_address(user, mint);
+
+    let (source_mint, destination_mint, source_token_account, destination_token_account) = if is_buy {
+        (NATIVE_MINT, *mint, *user, user_token_account)
+    } else {
+        (*mint, NATIVE_MINT, user_token_account, *user)
+    };
+
+    SwapParams {
+        swap_mode: SwapMode::ExactIn,
+        in_amount,
+        out_amount,
+        source_mint,
+        destination_mint,
+        source_token_account,
+        destination_token_account,
+        token_transfer_authority: *user,
+    }
+}
+
+#[tokio::test]
+async fn routes_a_buy_and_a_sell() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let user = env.funded_user(10 * LAMPORTS_PER_SOL).await;
+    env.create_token_account(&user.pubkey(), &mint).await;
+
+    let amm = load_market(&mut env, &mint).await;
+    assert!(amm.is_active());
+    assert_eq!(amm.get_reserve_mints(), vec![NATIVE_MINT, mint]);
+
+    let quote = amm
+        .quote(&QuoteParams {
+            amount: LAMPORTS_PER_SOL,
+            input_mint: NATIVE_MINT,
+            output_mint: mint,
+            swap_mode: SwapMode::ExactIn,
+        })
+        .unwrap();
+    assert!(quote.in_amount <= LAMPORTS_PER_SOL);
+
+    let swap = amm
+        .get_swap_and_account_metas(&swap_params(&user.pubkey(), &mint, true, quote.in_amount, quote.out_amount))
+        .unwrap();
+
+    let pre_buy_balance = env.balance(&user.pubkey()).await;
+    env.send(&[swap.instruction()], &[&user]).await.unwrap();
+
+    assert_eq!(env.token_balance(&user.pubkey(), &mint).await, quote.out_amount);
+    //the user also pays the 5000 lamport signature fee
+    assert_eq!(pre_buy_balance - env.balance(&user.pubkey()).await, quote.in_amount + 5_000);
+
+    //sell half back through a market refreshed after the buy
+    let amm = load_market(&mut env, &mint).await;
+    let token_amount = quote.out_amount / 2;
+
+    let quote = amm
+        .quote(&QuoteParams {
+            amount: token_amount,
+            input_mint: mint,
+            output_mint: NATIVE_MINT,
+            swap_mode: SwapMode::ExactIn,
+        })
+        .unwrap();
+
+    let swap = amm
+        .get_swap_and_account_metas(&swap_params(&user.pubkey(), &mint, false, token_amount, quote.out_amount))
+        .unwrap();
+
+    let pre_sell_balance = env.balance(&user.pubkey()).await;
+    env.send(&[swap.instruction()], &[&user]).await.unwrap();
+
+    assert_eq!(env.token_balance(&user.pubkey(), &mint).await, token_amount + token_amount % 2);
+    assert_eq!(env.balance(&user.pubkey()).await + 5_000 - pre_sell_balance, quote.out_amount);
+}
+
+#[tokio::test]
+async fn stale_quote_is_rejected_on_chain() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let user = env.funded_user(10 * LAMPORTS_PER_SOL).await;
+    let creator = env.creator.insecure_clone();
+    env.create_token_account(&user.pubkey(), &mint).await;
+
+    let amm = load_market(&mut env, &mint).await;
+    let quote = amm
+        .quote(&QuoteParams {
+            amount: LAMPORTS_PER_SOL,
+            input_mint: NATIVE_MINT,
+            output_mint: mint,
+            swap_mode: SwapMode::ExactIn,
+        })
+        .unwrap();
+
+    //someone else buys first and moves the price
+    env.buy(&creator, &mint, DEFAULT_TOKEN_BALANCE / 10, u64::MAX).await.unwrap();
+
+    let swap = amm
+        .get_swap_and_account_metas(&swap_params(&user.pubkey(), &mint, true, quote.in_amount, quote.out_amount))
+        .unwrap();
+
+    let result = env.send(&[swap.instruction()], &[&user]).await;
+    assert_error(result, curve_launchpad::instructions::CurveLaunchpadError::MaxSOLCostExceeded);
+}
+
+#[tokio::test]
+async fn graduated_curve_is_inactive() {
+    let mut env = TestEnv::new().await;
+    let mint = env
+        .create_curve_with_target(GraduationTarget::SolRaised(2 * LAMPORTS_PER_SOL))
+        .await;
+    let creator = env.creator.insecure_clone();
+
+    let amm = load_market(&mut env, &mint).await;
+
+    //the quote stops at the graduation target however much SOL comes in
+    let quote = amm
+        .quote(&QuoteParams {
+            amount: 100 * LAMPORTS_PER_SOL,
+            input_mint: NATIVE_MINT,
+            output_mint: mint,
+            swap_mode: SwapMode::ExactIn,
+        })
+        .unwrap();
+    assert!(quote.in_amount < 3 * LAMPORTS_PER_SOL);
+
+    env.buy(&creator, &mint, quote.out_amount, u64::MAX).await.unwrap();
+
+    let amm = load_market(&mut env, &mint).await;
+    assert!(!amm.is_active());
+}
@@ -0,0 +1,341 @@
+use anchor_lang::prelude::Pubkey;
+use clap::{Args, Parser, Subcommand};
+
+#[derive(Debug, Parser)]
+#[command(name = "launchpad-cli", version, about = "Operate the curve launchpad program")]
+pub struct Cli {
+    /// RPC endpoint of the cluster
+    #[arg(long, short = 'u', global = true, env = "LAUNCHPAD_RPC_URL", default_value = "http://127.0.0.1:8899")]
+    pub url: String,
+
+    /// Keypair file that signs and pays for transactions
+    #[arg(long, short = 'k', global = true, env = "LAUNCHPAD_KEYPAIR", default_value = "~/.config/solana/id.json")]
+    pub keypair: String,
+
+    /// Simulate transactions instead of sending them
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Create the global state and the launchpad collection, the keypair becomes its authority
+    Init(CollectionArgs),
+    /// Update the global parameters, unset options keep their current value
+    SetParams(SetParamsArgs),
+    /// Update the creation fee and creator limits, unset options keep their current value
+    SetCreateControls(SetCreateControlsArgs),
+    /// Allow a wallet to create curves while the creator allowlist is on
+    AllowCreator {
+        creator: Pubkey,
+        /// Take the wallet off the allowlist instead
+        #[arg(long)]
+        revoke: bool,
+    },
+    /// Require launched mints' addresses to end in a base58 suffix, empty for any mint
+    SetMintSuffix {
+        suffix: String,
+    },
+    /// Queue pre-generated mint keypairs in the mint vault for `create --from-vault`
+    DepositVaultMint {
+        /// Keypair files of the mints
+        #[arg(required = true)]
+        mint_keypairs: Vec<String>,
+    },
+    /// Launch a new token on a bonding curve
+    Create(CreateArgs),
+    /// Buy tokens from a curve
+    Buy(TradeArgs),
+    /// Sell tokens back to a curve
+    Sell(TradeArgs),
+    /// Withdraw a completed curve's liquidity as the withdraw authority
+    Withdraw {
+        mint: Pubkey,
+    },
+    /// Upgrade accounts still in a legacy layout
+    #[command(subcommand)]
+    Migrate(MigrateCommand),
+    /// Print the global state
+    ShowGlobal,
+    /// Print a mint's bonding curve
+    ShowCurve {
+        mint: Pubkey,
+    },
+    /// Quote a buy or sell without sending anything
+    Quote(QuoteArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct CollectionArgs {
+    /// Name of the collection NFT every launched token is verified into
+    #[arg(long, default_value = "Curve Launchpad")]
+    pub collection_name: String,
+    #[arg(long, default_value = "LAUNCH")]
+    pub collection_symbol: String,
+    /// URI of the collection NFT's metadata JSON
+    #[arg(long)]
+    pub collection_uri: String,
+}
+
+#[derive(Debug, Args)]
+pub struct SetParamsArgs {
+    #[arg(long)]
+    pub fee_recipient: Option<Pubkey>,
+    #[arg(long)]
+    pub withdraw_authority: Option<Pubkey>,
+    #[arg(long)]
+    pub initial_virtual_token_reserves: Option<u64>,
+    #[arg(long)]
+    pub initial_virtual_sol_reserves: Option<u64>,
+    #[arg(long)]
+    pub initial_real_token_reserves: Option<u64>,
+    #[arg(long)]
+    pub initial_token_supply: Option<u64>,
+    #[arg(long)]
+    pub fee_basis_points: Option<u64>,
+}
+
+#[derive(Debug, Args)]
+pub struct SetCreateControlsArgs {
+    /// Lamports paid to the fee recipient for every curve created
+    #[arg(long)]
+    pub creation_fee: Option<u64>,
+    /// Curves a creator can create per window, 0 for no limit
+    #[arg(long)]
+    pub rate_limit: Option<u32>,
+    /// Length of the rate limit window in seconds
+    #[arg(long)]
+    pub rate_limit_window: Option<i64>,
+    /// Only let allowed creators create curves
+    #[arg(long)]
+    pub allowlist: Option<bool>,
+}
+
+#[derive(Debug, Args)]
+pub struct CreateArgs {
+    #[arg(long)]
+    pub name: String,
+    #[arg(long)]
+    pub symbol: String,
+    #[arg(long)]
+    pub uri: String,
+    /// Keypair file for the new mint, a fresh one is generated if unset
+    #[arg(long)]
+    pub mint_keypair: Option<String>,
+    /// Launch a mint from the mint vault that matches the current mint suffix
+    #[arg(long, conflicts_with = "mint_keypair")]
+    pub from_vault: bool,
+    /// Unix timestamp after which an incomplete curve is refunded
+    #[arg(long)]
+    pub graduation_deadline: Option<i64>,
+    /// Graduate once this many lamports have been raised
+    #[arg(long, conflicts_with = "target_market_cap")]
+    pub target_sol: Option<u64>,
+    /// Graduate once the market cap reaches this many lamports
+    #[arg(long)]
+    pub target_market_cap: Option<u64>,
+    /// Unix timestamp trading opens at
+    #[arg(long)]
+    pub start_time: Option<i64>,
+    /// List the keypair as the token's verified creator in its metadata
+    #[arg(long)]
+    pub verify_creator: bool,
+    /// Mint decimals, at most 9, the global template's if unset
+    #[arg(long)]
+    pub decimals: Option<u8>,
+    /// Total supply in base units, the template's number of whole tokens if unset
+    #[arg(long)]
+    pub token_supply: Option<u64>,
+}
+
+#[derive(Debug, Args)]
+pub struct TradeArgs {
+    pub mint: Pubkey,
+    /// Token amount in base units
+    #[arg(long)]
+    pub amount: u64,
+    /// SOL limit in lamports, max cost for buys and min output for sells;
+    /// derived from a fresh quote and --slippage-bps if unset
+    #[arg(long)]
+    pub sol_limit: Option<u64>,
+    #[arg(long, default_value_t = 100)]
+    pub slippage_bps: u64,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum MigrateCommand {
+    /// Upgrade the global state
+    Global,
+    /// Upgrade a mint's bonding curve as the authority
+    Curve {
+        mint: Pubkey,
+        /// Creator to record on curves from before curves had one, who can then close the curve
+        #[arg(long)]
+        creator: Pubkey,
+    },
+    /// Create the price oracle of a curve created before curves had one
+    Oracle { mint: Pubkey },
+    /// Create the launchpad collection of a deployment initialized before there was one
+    Collection(CollectionArgs),
+    /// Seed and lock the SOL / mint AMM pool of a withdrawn curve as the withdraw authority
+    Pool {
+        mint: Pubkey,
+        /// AMM config the pool is created with
+        #[arg(long)]
+        config: Pubkey,
+        /// Lamports to wrap into the keypair's WSOL account first, the pool is seeded with its whole balance
+        #[arg(long)]
+        wrap_sol: Option<u64>,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct QuoteArgs {
+    pub mint: Pubkey,
+    /// Token amount to buy, in base units
+    #[arg(long, conflicts_with = "sell", required_unless_present = "sell")]
+    pub buy: Option<u64>,
+    /// Token amount to sell, in base units
+    #[arg(long)]
+    pub sell: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse_buy() {
+        let mint = Pubkey::new_unique();
+        let cli = Cli::try_parse_from([
+            "launchpad-cli",
+            "--dry-run",
+            "buy",
+            &mint.to_string(),
+            "--amount",
+            "1000",
+        ])
+        .unwrap();
+
+        assert!(cli.dry_run);
+        assert_eq!(cli.url, "http://127.0.0.1:8899");
+        match cli.command {
+            Command::Buy(args) => {
+                assert_eq!(args.mint, mint);
+                assert_eq!(args.amount, 1000);
+                assert_eq!(args.sol_limit, None);
+                assert_eq!(args.slippage_bps, 100);
+            }
+            command => panic!("unexpected {command:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_quote_needs_one_side() {
+        let mint = Pubkey::new_unique().to_string();
+
+        assert!(Cli::try_parse_from(["launchpad-cli", "quote", &mint]).is_err());
+        assert!(Cli::try_parse_from(["launchpad-cli", "quote", &mint, "--buy", "1", "--sell", "1"]).is_err());
+        assert!(Cli::try_parse_from(["launchpad-cli", "quote", &mint, "--sell", "1"]).is_ok());
+    }
+
+    #[test]
+    fn test_parse_create_single_target() {
+        let base = ["launchpad-cli", "create", "--name", "test", "--symbol", "tst", "--uri", "https://www.test.com"];
+
+        assert!(Cli::try_parse_from(base.iter().chain(&["--target-sol", "1"])).is_ok());
+        assert!(
+            Cli::try_parse_from(base.iter().chain(&["--target-sol", "1", "--target-market-cap", "1"])).is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_migrate() {
+        let mint = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+        let cli = Cli::try_parse_from([
+            "launchpad-cli",
+            "migrate",
+            "curve",
+            &mint.to_string(),
+            "--creator",
+            &creator.to_string(),
+        ])
+        .unwrap();
+
+        assert!(matches!(
+            cli.command,
+            Command::Migrate(MigrateCommand::Curve { mint: m, creator: c }) if m == mint && c == creator
+        ));
+        assert!(Cli::try_parse_from(["launchpad-cli", "migrate", "curve", &mint.to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_parse_init() {
+        let cli = Cli::try_parse_from(["launchpad-cli", "init", "--collection-uri", "https://www.test.com/c.json"]).unwrap();
+
+        match cli.command {
+            Command::Init(args) => {
+                assert_eq!(args.collection_name, "Curve Launchpad");
+                assert_eq!(args.collection_symbol, "LAUNCH");
+                assert_eq!(args.collection_uri, "https://www.test.com/c.json");
+            }
+            command => panic!("unexpected {command:?}"),
+        }
+
+        //the collection needs real metadata
+        assert!(Cli::try_parse_from(["launchpad-cli", "init"]).is_err());
+        assert!(Cli::try_parse_from(["launchpad-cli", "migrate", "collection"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_migrate_pool() {
+        let mint = Pubkey::new_unique();
+        let config = Pubkey::new_unique();
+        let cli = Cli::try_parse_from([
+            "launchpad-cli",
+            "migrate",
+            "pool",
+            &mint.to_string(),
+            "--config",
+            &config.to_string(),
+            "--wrap-sol",
+            "85000000000",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Command::Migrate(MigrateCommand::Pool { mint: m, config: c, wrap_sol }) => {
+                assert_eq!(m, mint);
+                assert_eq!(c, config);
+                assert_eq!(wrap_sol, Some(85_000_000_000));
+            }
+            command => panic!("unexpected {command:?}"),
+        }
+
+        //the config can't be guessed
+        assert!(Cli::try_parse_from(["launchpad-cli", "migrate", "pool", &mint.to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_parse_set_create_controls() {
+        let cli = Cli::try_parse_from(["launchpad-cli", "set-create-controls", "--allowlist", "true"]).unwrap();
+
+        match cli.command {
+            Command::SetCreateControls(args) => {
+                assert_eq!(args.allowlist, Some(true));
+                assert_eq!(args.creation_fee, None);
+            }
+            command => panic!("unexpected {command:?}"),
+        }
+    }
+}
@@ -0,0 +1,368 @@
+mod cli;
+mod output;
+mod rpc;
+
+use anchor_lang::{prelude::Pubkey, solana_program::instruction::Instruction};
+use anchor_spl::{
+    associated_token::{
+        get_associated_token_address, spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+    },
+    token::spl_token,
+};
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use cli::{Cli, CollectionArgs, Command, CreateArgs, MigrateCommand, QuoteArgs, SetCreateControlsArgs, SetParamsArgs, TradeArgs};
+use curve_launchpad_client::{
+    curve_launchpad::{instruction, state::GraduationTarget},
+    amm, instructions, quote,
+};
+use rpc::Client;
+use serde_json::{json, Value};
+use solana_sdk::{signature::Keypair, signer::Signer, system_instruction};
+
+fn main() {
+    let cli = Cli::parse();
+
+    match run(cli) {
+        Ok(value) => println!("{value:#}"),
+        Err(err) => {
+            println!("{:#}", json!({ "success": false, "error": format!("{err:#}") }));
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<Value> {
+    let client = Client::new(&cli.url, &cli.keypair, cli.dry_run)?;
+    let payer = client.payer.pubkey();
+
+    match cli.command {
+        Command::Init(args) => {
+            let args = instruction::Initialize {
+                collection_name: args.collection_name,
+                collection_symbol: args.collection_symbol,
+                collection_uri: args.collection_uri,
+            };
+            client.send(&[instructions::initialize(&payer, args)], &[])
+        }
+        Command::SetParams(args) => set_params(&client, args),
+        Command::SetCreateControls(args) => set_create_controls(&client, args),
+        Command::AllowCreator { creator, revoke } => {
+            client.send(&[instructions::set_creator_allowed(&payer, &creator, !revoke)], &[])
+        }
+        Command::SetMintSuffix { suffix } => client.send(&[instructions::set_mint_suffix(&payer, &suffix)], &[]),
+        Command::DepositVaultMint { mint_keypairs } => deposit_vault_mint(&client, &mint_keypairs),
+        Command::Create(args) => create(&client, args),
+        Command::Buy(args) => buy(&client, args),
+        Command::Sell(args) => sell(&client, args),
+        Command::Withdraw { mint } => client.send(&[instructions::withdraw(&payer, &mint)], &[]),
+        Command::Migrate(MigrateCommand::Global) => client.send(&[instructions::migrate_global(&payer)], &[]),
+        Command::Migrate(MigrateCommand::Curve { mint, creator }) => {
+            client.send(&[instructions::migrate_curve(&payer, &mint, &creator)], &[])
+        }
+        Command::Migrate(MigrateCommand::Collection(args)) => {
+            client.send(&[instructions::init_collection(&payer, collection_args(args))], &[])
+        }
+        Command::Migrate(MigrateCommand::Oracle { mint }) => {
+            client.send(&[instructions::init_curve_oracle(&payer, &mint)], &[])
+        }
+        Command::Migrate(MigrateCommand::Pool { mint, config, wrap_sol }) => create_pool(&client, &mint, &config, wrap_sol),
+        Command::ShowGlobal => {
+            let (address, global) = client.global()?;
+            Ok(output::global(&address, &global))
+        }
+        Command::ShowCurve { mint } => {
+            let (address, bonding_curve) = client.bonding_curve(&mint)?;
+            Ok(output::bonding_curve(&address, &mint, &bonding_curve))
+        }
+        Command::Quote(args) => quote(&client, args),
+    }
+}
+
+fn set_params(client: &Client, args: SetParamsArgs) -> Result<Value> {
+    let (_, global) = client.global()?;
+
+    let ix = instructions::set_params(
+        &client.payer.pubkey(),
+        instruction::SetParams {
+            fee_recipient: args.fee_recipient.unwrap_or(global.fee_recipient),
+            withdraw_authority: args.withdraw_authority.unwrap_or(global.withdraw_authority),
+            initial_virtual_token_reserves: args
+                .initial_virtual_token_reserves
+                .unwrap_or(global.initial_virtual_token_reserves),
+            initial_virtual_sol_reserves: args
+                .initial_virtual_sol_reserves
+                .unwrap_or(global.initial_virtual_sol_reserves),
+            initial_real_token_reserves: args
+                .initial_real_token_reserves
+                .unwrap_or(global.initial_real_token_reserves),
+            inital_token_supply: args.initial_token_supply.unwrap_or(global.initial_token_supply),
+            fee_basis_points: args.fee_basis_points.unwrap_or(global.fee_basis_points),
+        },
+    );
+
+    client.send(&[ix], &[])
+}
+
+fn set_create_controls(client: &Client, args: SetCreateControlsArgs) -> Result<Value> {
+    let (_, global) = client.global()?;
+
+    let ix = instructions::set_create_controls(
+        &client.payer.pubkey(),
+        instruction::SetCreateControls {
+            creation_fee_lamports: args.creation_fee.unwrap_or(global.creation_fee_lamports),
+            creator_rate_limit: args.rate_limit.unwrap_or(global.creator_rate_limit),
+            creator_rate_limit_window: args.rate_limit_window.unwrap_or(global.creator_rate_limit_window),
+            creator_allowlist: args.allowlist.unwrap_or(global.creator_allowlist),
+        },
+    );
+
+    client.send(&[ix], &[])
+}
+
+fn deposit_vault_mint(client: &Client, mint_keypairs: &[String]) -> Result<Value> {
+    let mints = mint_keypairs
+        .iter()
+        .map(|path| rpc::read_keypair(path))
+        .collect::<Result<Vec<_>>>()?;
+
+    let ixs: Vec<_> = mints
+        .iter()
+        .map(|mint| instructions::deposit_vault_mint(&client.payer.pubkey(), &mint.pubkey()))
+        .collect();
+    let signers: Vec<_> = mints.iter().collect();
+
+    let mut result = client.send(&ixs, &signers)?;
+    result["mints"] = json!(mints.iter().map(|mint| mint.pubkey().to_string()).collect::<Vec<_>>());
+
+    Ok(result)
+}
+
+fn create(client: &Client, args: CreateArgs) -> Result<Value> {
+    let (_, global) = client.global()?;
+
+    let mint = match (&args.mint_keypair, args.from_vault) {
+        (_, true) => None,
+        (Some(path), _) => Some(rpc::read_keypair(path)?),
+        (None, _) => Some(Keypair::new()),
+    };
+    let mint_address = match &mint {
+        Some(mint) => mint.pubkey(),
+        None => *client
+            .mint_vault()?
+            .mints
+            .iter()
+            .find(|mint| global.is_vanity_mint(mint))
+            .ok_or_else(|| anyhow!("mint vault has no mint ending in {:?}", global.mint_suffix()))?,
+    };
+
+    let create_args = create_args(args);
+
+    let mut result = match &mint {
+        Some(mint) => {
+            let ix = instructions::create(&mint_address, &client.payer.pubkey(), &global.fee_recipient, create_args);
+            client.send(&[create_budget(), ix], &[mint])?
+        }
+        None => {
+            let ix = instructions::create_from_vault(&mint_address, &client.payer.pubkey(), &global.fee_recipient, create_args);
+            client.send(&[create_budget(), ix], &[])?
+        }
+    };
+    result["mint"] = json!(mint_address.to_string());
+
+    Ok(result)
+}
+
+//create's metaplex CPIs leave little of the default compute budget
+fn create_budget() -> Instruction {
+    instructions::set_compute_unit_limit(instructions::CREATE_COMPUTE_UNIT_LIMIT)
+}
+
+fn collection_args(args: CollectionArgs) -> instruction::InitCollection {
+    instruction::InitCollection {
+        name: args.collection_name,
+        symbol: args.collection_symbol,
+        uri: args.collection_uri,
+    }
+}
+
+fn create_args(args: CreateArgs) -> instruction::Create {
+    let graduation_target = match (args.target_sol, args.target_market_cap) {
+        (Some(lamports), _) => Some(GraduationTarget::SolRaised(lamports)),
+        (_, Some(lamports)) => Some(GraduationTarget::MarketCap(lamports)),
+        _ => None,
+    };
+
+    instruction::Create {
+        name: args.name,
+        symbol: args.symbol,
+        uri: args.uri,
+        graduation_deadline: args.graduation_deadline,
+        graduation_target,
+        start_time: args.start_time,
+        verify_creator: args.verify_creator,
+        decimals: args.decimals,
+        token_supply: args.token_supply,
+    }
+}
+
+fn create_pool(client: &Client, mint: &Pubkey, config: &Pubkey, wrap_sol: Option<u64>) -> Result<Value> {
+    let ixs = create_pool_instructions(&client.payer.pubkey(), mint, config, wrap_sol)?;
+
+    let mut result = client.send(&ixs, &[])?;
+    result["pool"] = json!(amm::pool(&amm::NATIVE_MINT, mint, config).to_string());
+
+    Ok(result)
+}
+
+//the pool is seeded with the payer's whole WSOL balance, so any SOL to add is wrapped in the same transaction
+fn create_pool_instructions(
+    payer: &Pubkey,
+    mint: &Pubkey,
+    config: &Pubkey,
+    wrap_sol: Option<u64>,
+) -> Result<Vec<Instruction>> {
+    let mut ixs = Vec::new();
+
+    if let Some(lamports) = wrap_sol {
+        let wsol_account = get_associated_token_address(payer, &amm::NATIVE_MINT);
+
+        ixs.push(create_associated_token_account_idempotent(payer, payer, &amm::NATIVE_MINT, &spl_token::ID));
+        ixs.push(system_instruction::transfer(payer, &wsol_account, lamports));
+        ixs.push(spl_token::instruction::sync_native(&spl_token::ID, &wsol_account)?);
+    }
+
+    ixs.push(instructions::create_pool(amm::create_pool_accounts(payer, config, mint)));
+
+    Ok(ixs)
+}
+
+fn buy(client: &Client, args: TradeArgs) -> Result<Value> {
+    let (_, global) = client.global()?;
+    let payer = client.payer.pubkey();
+
+    let max_sol_cost = match args.sol_limit {
+        Some(lamports) => lamports,
+        None => {
+            let (_, bonding_curve) = client.bonding_curve(&args.mint)?;
+            let quote = quote::quote_buy(&global, &bonding_curve, args.amount)
+                .ok_or_else(|| anyhow!("curve can't fill a buy of {}", args.amount))?;
+            with_slippage(quote.total_cost(), args.slippage_bps, true)
+        }
+    };
+
+    let instructions = [
+        create_associated_token_account_idempotent(&payer, &payer, &args.mint, &anchor_spl::token::ID),
+        instructions::buy(&payer, &args.mint, &global.fee_recipient, args.amount, max_sol_cost),
+    ];
+
+    client.send(&instructions, &[])
+}
+
+fn sell(client: &Client, args: TradeArgs) -> Result<Value> {
+    let (_, global) = client.global()?;
+
+    let min_sol_output = match args.sol_limit {
+        Some(lamports) => lamports,
+        None => {
+            let (_, bonding_curve) = client.bonding_curve(&args.mint)?;
+            let quote = quote::quote_sell(&global, &bonding_curve, args.amount)
+                .ok_or_else(|| anyhow!("curve can't take a sell of {}", args.amount))?;
+            with_slippage(quote.net_output(), args.slippage_bps, false)
+        }
+    };
+
+    let ix = instructions::sell(
+        &client.payer.pubkey(),
+        &args.mint,
+        &global.fee_recipient,
+        args.amount,
+        min_sol_output,
+    );
+
+    client.send(&[ix], &[])
+}
+
+fn quote(client: &Client, args: QuoteArgs) -> Result<Value> {
+    let (_, global) = client.global()?;
+    let (_, bonding_curve) = client.bonding_curve(&args.mint)?;
+
+    match (args.buy, args.sell) {
+        (Some(amount), _) => quote::quote_buy(&global, &bonding_curve, amount)
+            .map(|quote| output::buy_quote(&args.mint, &quote))
+            .ok_or_else(|| anyhow!("curve can't fill a buy of {amount}")),
+        (_, Some(amount)) => quote::quote_sell(&global, &bonding_curve, amount)
+            .map(|quote| output::sell_quote(&args.mint, &quote))
+            .ok_or_else(|| anyhow!("curve can't take a sell of {amount}")),
+        _ => Err(anyhow!("one of --buy or --sell is required")),
+    }
+}
+
+//widens a quoted SOL amount by the slippage tolerance, up for costs and down for outputs
+fn with_slippage(lamports: u64, slippage_bps: u64, round_up: bool) -> u64 {
+    let delta = (lamports as u128 * slippage_bps as u128 / 10_000) as u64;
+
+    if round_up {
+        lamports.saturating_add(delta)
+    } else {
+        lamports.saturating_sub(delta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_slippage() {
+        assert_eq!(with_slippage(10_000, 100, true), 10_100);
+        assert_eq!(with_slippage(10_000, 100, false), 9_900);
+        assert_eq!(with_slippage(10_000, 0, true), 10_000);
+        assert_eq!(with_slippage(u64::MAX, 100, true), u64::MAX);
+        assert_eq!(with_slippage(10, 20_000, false), 0);
+    }
+
+    #[test]
+    fn test_create_args() {
+        let args = CreateArgs {
+            name: "test".to_string(),
+            symbol: "TST".to_string(),
+            uri: "https://www.test.com".to_string(),
+            mint_keypair: None,
+            from_vault: false,
+            graduation_deadline: Some(100),
+            target_sol: None,
+            target_market_cap: Some(5),
+            start_time: None,
+            verify_creator: true,
+            decimals: Some(9),
+            token_supply: None,
+        };
+
+        let create = create_args(args);
+        assert_eq!(create.graduation_target, Some(GraduationTarget::MarketCap(5)));
+        assert_eq!(create.graduation_deadline, Some(100));
+        assert!(create.verify_creator);
+        assert_eq!(create.decimals, Some(9));
+    }
+
+    #[test]
+    fn test_create_pool_instructions() {
+        let payer = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let config = Pubkey::new_unique();
+
+        let ixs = create_pool_instructions(&payer, &mint, &config, None).unwrap();
+        assert_eq!(ixs.len(), 1);
+        assert_eq!(ixs[0].program_id, curve_launchpad_client::PROGRAM_ID);
+        assert_eq!(ixs[0].accounts[0].pubkey, amm::pool(&amm::NATIVE_MINT, &mint, &config));
+
+        //wrapping funds the WSOL account the pool is seeded from
+        let ixs = create_pool_instructions(&payer, &mint, &config, Some(1_000)).unwrap();
+        let wsol_account = get_associated_token_address(&payer, &amm::NATIVE_MINT);
+        assert_eq!(ixs.len(), 4);
+        assert_eq!(ixs[1], system_instruction::transfer(&payer, &wsol_account, 1_000));
+        assert_eq!(ixs[2].program_id, spl_token::ID);
+        assert!(ixs[3].accounts.iter().any(|meta| meta.pubkey == wsol_account));
+    }
+}
@@ -0,0 +1,106 @@
+//! JSON shapes printed by the CLI, one object per command.
+
+use anchor_lang::prelude::Pubkey;
+use curve_launchpad_client::{
+    curve_launchpad::state::{BondingCurve, Global},
+    quote::{BuyQuote, SellQuote},
+};
+use serde_json::{json, Value};
+
+pub fn global(address: &Pubkey, global: &Global) -> Value {
+    json!({
+        "address": address.to_string(),
+        "version": global.version,
+        "authority": global.authority.to_string(),
+        "initialized": global.initialized,
+        "fee_recipient": global.fee_recipient.to_string(),
+        "withdraw_authority": global.withdraw_authority.to_string(),
+        "initial_virtual_token_reserves": global.initial_virtual_token_reserves,
+        "initial_virtual_sol_reserves": global.initial_virtual_sol_reserves,
+        "initial_real_token_reserves": global.initial_real_token_reserves,
+        "initial_real_sol_reserves": global.initial_real_sol_reserves,
+        "initial_token_supply": global.initial_token_supply,
+        "fee_basis_points": global.fee_basis_points,
+        "creation_fee_lamports": global.creation_fee_lamports,
+        "creator_rate_limit": global.creator_rate_limit,
+        "creator_rate_limit_window": global.creator_rate_limit_window,
+        "creator_allowlist": global.creator_allowlist,
+        "mint_suffix": global.mint_suffix(),
+    })
+}
+
+pub fn bonding_curve(address: &Pubkey, mint: &Pubkey, bonding_curve: &BondingCurve) -> Value {
+    json!({
+        "address": address.to_string(),
+        "mint": mint.to_string(),
+        "version": bonding_curve.version,
+        "creator": bonding_curve.creator.to_string(),
+        "status": format!("{:?}", bonding_curve.status),
+        "virtual_sol_reserves": bonding_curve.virtual_sol_reserves,
+        "virtual_token_reserves": bonding_curve.virtual_token_reserves,
+        "real_sol_reserves": bonding_curve.real_sol_reserves,
+        "real_token_reserves": bonding_curve.real_token_reserves,
+        "token_total_supply": bonding_curve.token_total_supply,
+        "start_time": bonding_curve.start_time,
+        "graduation_deadline": bonding_curve.graduation_deadline,
+        "graduation_target": bonding_curve.graduation_target.map(|target| format!("{target:?}")),
+    })
+}
+
+pub fn buy_quote(mint: &Pubkey, quote: &BuyQuote) -> Value {
+    json!({
+        "mint": mint.to_string(),
+        "side": "buy",
+        "token_amount": quote.token_amount,
+        "sol_amount": quote.sol_amount,
+        "fee": quote.fee,
+        "total_cost": quote.total_cost(),
+    })
+}
+
+pub fn sell_quote(mint: &Pubkey, quote: &SellQuote) -> Value {
+    json!({
+        "mint": mint.to_string(),
+        "side": "sell",
+        "token_amount": quote.token_amount,
+        "sol_amount": quote.sol_amount,
+        "fee": quote.fee,
+        "net_output": quote.net_output(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curve_launchpad_client::{accounts::decode_bonding_curve, curve_launchpad::state::GraduationTarget};
+
+    #[test]
+    fn test_bonding_curve() {
+        let mut curve = decode_bonding_curve(include_bytes!(
+            "../../../programs/curve-launchpad/tests/fixtures/bonding_curve_v0.bin"
+        ))
+        .unwrap();
+        curve.graduation_deadline = Some(1_719_592_000);
+        curve.graduation_target = Some(GraduationTarget::MarketCap(1));
+        let mint = Pubkey::new_unique();
+
+        let value = bonding_curve(&Pubkey::new_unique(), &mint, &curve);
+
+        assert_eq!(value["mint"], mint.to_string());
+        assert_eq!(value["status"], "Trading");
+        assert_eq!(value["real_sol_reserves"], 1_000_000_000u64);
+        assert_eq!(value["graduation_deadline"], 1_719_592_000i64);
+        assert_eq!(value["graduation_target"], "MarketCap(1)");
+    }
+
+    #[test]
+    fn test_quotes() {
+        let mint = Pubkey::new_unique();
+
+        let buy = buy_quote(&mint, &BuyQuote { token_amount: 10, sol_amount: 200, fee: 1 });
+        assert_eq!(buy["total_cost"], 201);
+
+        let sell = sell_quote(&mint, &SellQuote { token_amount: 10, sol_amount: 200, fee: 1 });
+        assert_eq!(sell["net_output"], 199);
+    }
+}
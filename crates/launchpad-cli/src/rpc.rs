@@ -0,0 +1,102 @@
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, Context, Result};
+use curve_launchpad_client::{
+    accounts::{decode_bonding_curve, decode_global, decode_mint_vault},
+    curve_launchpad::state::{BondingCurve, Global, MintVault},
+    pda,
+};
+use serde_json::{json, Value};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    signature::{read_keypair_file, Keypair},
+    signer::Signer,
+    transaction::Transaction,
+};
+
+pub struct Client {
+    pub rpc: RpcClient,
+    pub payer: Keypair,
+    pub dry_run: bool,
+}
+
+impl Client {
+    pub fn new(url: &str, keypair: &str, dry_run: bool) -> Result<Self> {
+        Ok(Client {
+            rpc: RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed()),
+            payer: read_keypair(keypair)?,
+            dry_run,
+        })
+    }
+
+    pub fn global(&self) -> Result<(Pubkey, Global)> {
+        let address = pda::global();
+        let data = self.account_data(&address).context("global state not initialized")?;
+
+        Ok((address, decode_global(&data)?))
+    }
+
+    pub fn bonding_curve(&self, mint: &Pubkey) -> Result<(Pubkey, BondingCurve)> {
+        let address = pda::bonding_curve(mint);
+        let data = self
+            .account_data(&address)
+            .with_context(|| format!("no bonding curve for mint {mint}"))?;
+
+        Ok((address, decode_bonding_curve(&data)?))
+    }
+
+    pub fn mint_vault(&self) -> Result<MintVault> {
+        let data = self.account_data(&pda::mint_vault()).context("no mints deposited")?;
+
+        Ok(decode_mint_vault(&data)?)
+    }
+
+    /// Sends the instructions signed by the payer and `signers`, or simulates them with `--dry-run`.
+    pub fn send(&self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<Value> {
+        let blockhash = self.rpc.get_latest_blockhash()?;
+
+        let mut all_signers: Vec<&dyn Signer> = vec![&self.payer];
+        all_signers.extend(signers.iter().map(|signer| *signer as &dyn Signer));
+
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+
+        if self.dry_run {
+            let simulation = self.rpc.simulate_transaction(&transaction)?.value;
+
+            return Ok(json!({
+                "dry_run": true,
+                "success": simulation.err.is_none(),
+                "error": simulation.err.map(|err| err.to_string()),
+                "units_consumed": simulation.units_consumed,
+                "logs": simulation.logs.unwrap_or_default(),
+            }));
+        }
+
+        let signature = self.rpc.send_and_confirm_transaction(&transaction)?;
+
+        Ok(json!({
+            "dry_run": false,
+            "success": true,
+            "signature": signature.to_string(),
+        }))
+    }
+
+    fn account_data(&self, address: &Pubkey) -> Result<Vec<u8>> {
+        Ok(self.rpc.get_account_data(address)?)
+    }
+}
+
+pub fn read_keypair(path: &str) -> Result<Keypair> {
+    let path = match (path.strip_prefix("~/"), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => format!("{home}/{rest}"),
+        _ => path.to_string(),
+    };
+
+    read_keypair_file(&path).map_err(|err| anyhow!("failed to read keypair {path}: {err}"))
+}
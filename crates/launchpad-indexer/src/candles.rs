@@ -0,0 +1,415 @@
+//! OHLCV candles built from `TradeEvent`s.
+//!
+//! Prices are the curve's spot price after each trade,
+//! `virtual_sol_reserves / virtual_token_reserves`, in lamports per base unit
+//! of the token. Volumes are raw amounts.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
+    str::FromStr,
+};
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{bail, Result};
+use curve_launchpad_client::curve_launchpad::instructions::TradeEvent;
+use rusqlite::Connection;
+use serde::Serialize;
+use solana_sdk::signature::Signature;
+
+use crate::{events::LaunchpadEvent, sink::Sink, transaction::IndexedEvent};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Interval {
+    OneSecond,
+    OneMinute,
+    FiveMinutes,
+    OneHour,
+}
+
+impl Interval {
+    pub const ALL: [Interval; 4] = [
+        Interval::OneSecond,
+        Interval::OneMinute,
+        Interval::FiveMinutes,
+        Interval::OneHour,
+    ];
+
+    pub fn seconds(self) -> i64 {
+        match self {
+            Interval::OneSecond => 1,
+            Interval::OneMinute => 60,
+            Interval::FiveMinutes => 300,
+            Interval::OneHour => 3_600,
+        }
+    }
+
+    /// Open time of the candle `timestamp` falls in.
+    pub fn start_of(self, timestamp: i64) -> i64 {
+        timestamp - timestamp.rem_euclid(self.seconds())
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Interval::OneSecond => "1s",
+            Interval::OneMinute => "1m",
+            Interval::FiveMinutes => "5m",
+            Interval::OneHour => "1h",
+        })
+    }
+}
+
+impl FromStr for Interval {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match Interval::ALL
+            .into_iter()
+            .find(|interval| interval.to_string() == s)
+        {
+            Some(interval) => Ok(interval),
+            None => bail!("unknown interval {s}, expected one of 1s, 1m, 5m or 1h"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Candle {
+    pub open_time: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub sol_volume: u64,
+    pub token_volume: u64,
+    /// Zero for gap-filled candles.
+    pub trades: u32,
+}
+
+impl Candle {
+    //a candle with no trades, carrying the previous close
+    fn flat(open_time: i64, price: f64) -> Self {
+        Candle {
+            open_time,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            sol_volume: 0,
+            token_volume: 0,
+            trades: 0,
+        }
+    }
+}
+
+/// The curve's price after `trade`.
+pub fn price(trade: &TradeEvent) -> f64 {
+    trade.virtual_sol_reserves as f64 / trade.virtual_token_reserves as f64
+}
+
+//orders trades within a candle: timestamp, slot, position among the transaction's events
+type Position = (i64, u64, u32);
+
+struct Bucket {
+    candle: Candle,
+    first: Position,
+    last: Position,
+}
+
+/// Candles at one interval for every mint traded.
+///
+/// Trades can arrive in any order, so history can be backfilled, from a
+/// [`SqliteSink`](crate::SqliteSink) database with [`backfill`](Self::backfill)
+/// or by writing older events, while live events are written as a [`Sink`].
+/// Each trade is counted once however often it's seen.
+pub struct CandleAggregator {
+    interval: Interval,
+    series: HashMap<Pubkey, BTreeMap<i64, Bucket>>,
+    seen: HashSet<(Signature, u32)>,
+}
+
+impl CandleAggregator {
+    pub fn new(interval: Interval) -> Self {
+        CandleAggregator {
+            interval,
+            series: HashMap::new(),
+            seen: HashSet::new(),
+        }
+    }
+
+    pub fn interval(&self) -> Interval {
+        self.interval
+    }
+
+    pub fn mints(&self) -> impl Iterator<Item = &Pubkey> {
+        self.series.keys()
+    }
+
+    /// Adds the event if it's a trade not seen before, returning whether it was.
+    pub fn push(&mut self, indexed: &IndexedEvent) -> bool {
+        match &indexed.event {
+            LaunchpadEvent::Trade(trade) => {
+                self.add(indexed.signature, indexed.index, indexed.slot, trade)
+            }
+            _ => false,
+        }
+    }
+
+    /// Adds every trade stored by a [`SqliteSink`](crate::SqliteSink), returning how many were new.
+    pub fn backfill(&mut self, connection: &Connection) -> Result<usize> {
+        let mut statement = connection.prepare(
+            "SELECT signature, event_index, slot, mint, user, is_buy, sol_amount, token_amount, timestamp,
+             virtual_sol_reserves, virtual_token_reserves, real_sol_reserves, real_token_reserves
+             FROM trades ORDER BY timestamp, slot, event_index",
+        )?;
+        let mut rows = statement.query([])?;
+        let mut added = 0;
+
+        while let Some(row) = rows.next()? {
+            let signature: String = row.get(0)?;
+            let mint: String = row.get(3)?;
+            let user: String = row.get(4)?;
+
+            let trade = TradeEvent {
+                mint: mint.parse()?,
+                sol_amount: row.get(6)?,
+                token_amount: row.get(7)?,
+                is_buy: row.get(5)?,
+                user: user.parse()?,
+                timestamp: row.get(8)?,
+                virtual_sol_reserves: row.get(9)?,
+                virtual_token_reserves: row.get(10)?,
+                real_sol_reserves: row.get(11)?,
+                real_token_reserves: row.get(12)?,
+            };
+
+            if self.add(signature.parse()?, row.get(1)?, row.get(2)?, &trade) {
+                added += 1;
+            }
+        }
+
+        Ok(added)
+    }
+
+    fn add(&mut self, signature: Signature, index: u32, slot: u64, trade: &TradeEvent) -> bool {
+        if !self.seen.insert((signature, index)) {
+            return false;
+        }
+
+        let position = (trade.timestamp, slot, index);
+        let price = price(trade);
+        let open_time = self.interval.start_of(trade.timestamp);
+
+        let bucket = self
+            .series
+            .entry(trade.mint)
+            .or_default()
+            .entry(open_time)
+            .or_insert_with(|| Bucket {
+                candle: Candle::flat(open_time, price),
+                first: position,
+                last: position,
+            });
+
+        let candle = &mut bucket.candle;
+        candle.high = candle.high.max(price);
+        candle.low = candle.low.min(price);
+        candle.sol_volume = candle.sol_volume.saturating_add(trade.sol_amount);
+        candle.token_volume = candle.token_volume.saturating_add(trade.token_amount);
+        candle.trades += 1;
+
+        //a backfilled trade can come before the candle's first or after its last
+        if position < bucket.first {
+            bucket.first = position;
+            candle.open = price;
+        }
+        if position >= bucket.last {
+            bucket.last = position;
+            candle.close = price;
+        }
+
+        true
+    }
+
+    /// The mint's candles opening from `from` until `to`, `from` rounded down
+    /// to the interval. Intervals without trades after the mint's first are
+    /// filled with flat candles at the previous close.
+    pub fn candles(&self, mint: &Pubkey, from: i64, to: i64) -> Vec<Candle> {
+        let Some(series) = self.series.get(mint) else {
+            return Vec::new();
+        };
+
+        let start = self.interval.start_of(from);
+        let mut close = series
+            .range(..start)
+            .next_back()
+            .map(|(_, bucket)| bucket.candle.close);
+        let mut candles = Vec::new();
+
+        for open_time in (start..to).step_by(self.interval.seconds() as usize) {
+            match series.get(&open_time) {
+                Some(bucket) => {
+                    candles.push(bucket.candle);
+                    close = Some(bucket.candle.close);
+                }
+                None => {
+                    if let Some(close) = close {
+                        candles.push(Candle::flat(open_time, close));
+                    }
+                }
+            }
+        }
+
+        candles
+    }
+}
+
+impl Sink for CandleAggregator {
+    fn write(&mut self, events: &[IndexedEvent]) -> Result<()> {
+        for indexed in events {
+            self.push(indexed);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sink::SqliteSink;
+
+    fn trade(
+        mint: Pubkey,
+        timestamp: i64,
+        virtual_sol_reserves: u64,
+        sol_amount: u64,
+    ) -> IndexedEvent {
+        IndexedEvent {
+            signature: Signature::new_unique(),
+            slot: timestamp as u64,
+            block_time: Some(timestamp),
+            index: 0,
+            event: LaunchpadEvent::Trade(TradeEvent {
+                mint,
+                sol_amount,
+                token_amount: sol_amount * 10,
+                is_buy: true,
+                user: Pubkey::new_unique(),
+                timestamp,
+                virtual_sol_reserves,
+                virtual_token_reserves: 1_000,
+                real_sol_reserves: 0,
+                real_token_reserves: 0,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_interval() {
+        for interval in Interval::ALL {
+            assert_eq!(interval.to_string().parse::<Interval>().unwrap(), interval);
+        }
+        assert!("2m".parse::<Interval>().is_err());
+
+        assert_eq!(Interval::FiveMinutes.start_of(1_000), 900);
+        assert_eq!(Interval::OneMinute.start_of(-1), -60);
+        assert_eq!(Interval::OneHour.start_of(7_200), 7_200);
+    }
+
+    #[test]
+    fn test_candles() {
+        let mint = Pubkey::new_unique();
+        let mut aggregator = CandleAggregator::new(Interval::OneMinute);
+
+        let trades = [
+            trade(mint, 60, 2_000, 10),
+            trade(mint, 70, 5_000, 20),
+            trade(mint, 80, 1_000, 30),
+            trade(mint, 119, 3_000, 40),
+            //nothing from 120 to 180
+            trade(mint, 200, 4_000, 50),
+        ];
+        aggregator.write(&trades).unwrap();
+        //other mints are kept apart
+        aggregator
+            .write(&[trade(Pubkey::new_unique(), 90, 9_000, 1)])
+            .unwrap();
+
+        assert_eq!(
+            aggregator.candles(&mint, 0, 300),
+            [
+                Candle {
+                    open_time: 60,
+                    open: 2.0,
+                    high: 5.0,
+                    low: 1.0,
+                    close: 3.0,
+                    sol_volume: 100,
+                    token_volume: 1_000,
+                    trades: 4,
+                },
+                Candle::flat(120, 3.0),
+                Candle {
+                    open_time: 180,
+                    open: 4.0,
+                    high: 4.0,
+                    low: 4.0,
+                    close: 4.0,
+                    sol_volume: 50,
+                    token_volume: 500,
+                    trades: 1,
+                },
+                Candle::flat(240, 4.0),
+            ]
+        );
+
+        //a range starting in a gap carries the close from before it
+        assert_eq!(
+            aggregator.candles(&mint, 150, 180),
+            [Candle::flat(120, 3.0)]
+        );
+        assert!(aggregator.candles(&Pubkey::new_unique(), 0, 300).is_empty());
+        assert_eq!(aggregator.mints().count(), 2);
+    }
+
+    #[test]
+    fn test_backfill() {
+        let mint = Pubkey::new_unique();
+        let trades = [
+            trade(mint, 0, 2_000, 10),
+            trade(mint, 1, 5_000, 20),
+            trade(mint, 2, 1_000, 30),
+            trade(mint, 3, 3_000, 40),
+        ];
+
+        //the aggregator started with the latest trades, older ones are backfilled around them
+        let mut aggregator = CandleAggregator::new(Interval::FiveMinutes);
+        aggregator.write(&trades[2..]).unwrap();
+        assert_eq!(aggregator.candles(&mint, 0, 300)[0].open, 1.0);
+
+        let mut sink = SqliteSink::in_memory().unwrap();
+        sink.write(&trades).unwrap();
+        assert_eq!(aggregator.backfill(sink.connection()).unwrap(), 2);
+        assert_eq!(aggregator.backfill(sink.connection()).unwrap(), 0);
+
+        let mut backfilled = CandleAggregator::new(Interval::FiveMinutes);
+        assert_eq!(backfilled.backfill(sink.connection()).unwrap(), 4);
+
+        let candle = Candle {
+            open_time: 0,
+            open: 2.0,
+            high: 5.0,
+            low: 1.0,
+            close: 3.0,
+            sol_volume: 100,
+            token_volume: 1_000,
+            trades: 4,
+        };
+        assert_eq!(aggregator.candles(&mint, 0, 300), [candle]);
+        assert_eq!(backfilled.candles(&mint, 0, 300), [candle]);
+    }
+}
@@ -0,0 +1,315 @@
+//! Decoding of the events the program emits with `emit_cpi!`.
+//!
+//! `emit_cpi!` has the program call itself, signed by its event authority,
+//! with `EVENT_IX_TAG_LE` followed by the event's 8 byte discriminator and its
+//! borsh encoding as the instruction data.
+
+use anchor_lang::{event::EVENT_IX_TAG_LE, prelude::Pubkey, AnchorDeserialize, Discriminator};
+use anyhow::{anyhow, Result};
+use curve_launchpad_client::curve_launchpad::instructions::{
+    CloseCurveEvent, CompleteEvent, CreateEvent, CreatorAllowedEvent, GraduatedEvent, MetadataUpdatedEvent,
+    RedeemEvent, SetCreateControlsEvent, SetMintSuffixEvent, SetParamsEvent, StatusChangedEvent, TradeEvent,
+    VaultMintDepositedEvent,
+};
+use serde_json::{json, Value};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LaunchpadEvent {
+    Create(CreateEvent),
+    Trade(TradeEvent),
+    Complete(CompleteEvent),
+    StatusChanged(StatusChangedEvent),
+    Graduated(GraduatedEvent),
+    Redeem(RedeemEvent),
+    CloseCurve(CloseCurveEvent),
+    SetParams(SetParamsEvent),
+    SetCreateControls(SetCreateControlsEvent),
+    CreatorAllowed(CreatorAllowedEvent),
+    MetadataUpdated(MetadataUpdatedEvent),
+    SetMintSuffix(SetMintSuffixEvent),
+    VaultMintDeposited(VaultMintDepositedEvent),
+    /// An event this build doesn't know, kept raw so a newer program doesn't stop indexing.
+    Unknown { discriminator: [u8; 8], data: Vec<u8> },
+}
+
+impl LaunchpadEvent {
+    /// Decodes the data of an event self-CPI. `Ok(None)` if the data isn't an
+    /// event, an error if it's a known event that doesn't decode.
+    pub fn decode(data: &[u8]) -> Result<Option<Self>> {
+        let Some(data) = data.strip_prefix(&EVENT_IX_TAG_LE[..]) else {
+            return Ok(None);
+        };
+        if data.len() < 8 {
+            return Err(anyhow!("event data too short for a discriminator"));
+        }
+        let (discriminator, payload) = data.split_at(8);
+
+        let event = match discriminator {
+            d if d == CreateEvent::DISCRIMINATOR => LaunchpadEvent::Create(parse(payload)?),
+            d if d == TradeEvent::DISCRIMINATOR => LaunchpadEvent::Trade(parse(payload)?),
+            d if d == CompleteEvent::DISCRIMINATOR => LaunchpadEvent::Complete(parse(payload)?),
+            d if d == StatusChangedEvent::DISCRIMINATOR => {
+                LaunchpadEvent::StatusChanged(parse(payload)?)
+            }
+            d if d == GraduatedEvent::DISCRIMINATOR => LaunchpadEvent::Graduated(parse(payload)?),
+            d if d == RedeemEvent::DISCRIMINATOR => LaunchpadEvent::Redeem(parse(payload)?),
+            d if d == CloseCurveEvent::DISCRIMINATOR => LaunchpadEvent::CloseCurve(parse(payload)?),
+            d if d == SetParamsEvent::DISCRIMINATOR => LaunchpadEvent::SetParams(parse(payload)?),
+            d if d == SetCreateControlsEvent::DISCRIMINATOR => {
+                LaunchpadEvent::SetCreateControls(parse(payload)?)
+            }
+            d if d == CreatorAllowedEvent::DISCRIMINATOR => {
+                LaunchpadEvent::CreatorAllowed(parse(payload)?)
+            }
+            d if d == MetadataUpdatedEvent::DISCRIMINATOR => {
+                LaunchpadEvent::MetadataUpdated(parse(payload)?)
+            }
+            d if d == SetMintSuffixEvent::DISCRIMINATOR => LaunchpadEvent::SetMintSuffix(parse(payload)?),
+            d if d == VaultMintDepositedEvent::DISCRIMINATOR => {
+                LaunchpadEvent::VaultMintDeposited(parse(payload)?)
+            }
+            d => LaunchpadEvent::Unknown {
+                discriminator: d.try_into()?,
+                data: payload.to_vec(),
+            },
+        };
+
+        Ok(Some(event))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LaunchpadEvent::Create(_) => "CreateEvent",
+            LaunchpadEvent::Trade(_) => "TradeEvent",
+            LaunchpadEvent::Complete(_) => "CompleteEvent",
+            LaunchpadEvent::StatusChanged(_) => "StatusChangedEvent",
+            LaunchpadEvent::Graduated(_) => "GraduatedEvent",
+            LaunchpadEvent::Redeem(_) => "RedeemEvent",
+            LaunchpadEvent::CloseCurve(_) => "CloseCurveEvent",
+            LaunchpadEvent::SetParams(_) => "SetParamsEvent",
+            LaunchpadEvent::SetCreateControls(_) => "SetCreateControlsEvent",
+            LaunchpadEvent::CreatorAllowed(_) => "CreatorAllowedEvent",
+            LaunchpadEvent::MetadataUpdated(_) => "MetadataUpdatedEvent",
+            LaunchpadEvent::SetMintSuffix(_) => "SetMintSuffixEvent",
+            LaunchpadEvent::VaultMintDeposited(_) => "VaultMintDepositedEvent",
+            LaunchpadEvent::Unknown { .. } => "Unknown",
+        }
+    }
+
+    pub fn discriminator(&self) -> [u8; 8] {
+        match self {
+            LaunchpadEvent::Create(_) => CreateEvent::DISCRIMINATOR,
+            LaunchpadEvent::Trade(_) => TradeEvent::DISCRIMINATOR,
+            LaunchpadEvent::Complete(_) => CompleteEvent::DISCRIMINATOR,
+            LaunchpadEvent::StatusChanged(_) => StatusChangedEvent::DISCRIMINATOR,
+            LaunchpadEvent::Graduated(_) => GraduatedEvent::DISCRIMINATOR,
+            LaunchpadEvent::Redeem(_) => RedeemEvent::DISCRIMINATOR,
+            LaunchpadEvent::CloseCurve(_) => CloseCurveEvent::DISCRIMINATOR,
+            LaunchpadEvent::SetParams(_) => SetParamsEvent::DISCRIMINATOR,
+            LaunchpadEvent::SetCreateControls(_) => SetCreateControlsEvent::DISCRIMINATOR,
+            LaunchpadEvent::CreatorAllowed(_) => CreatorAllowedEvent::DISCRIMINATOR,
+            LaunchpadEvent::MetadataUpdated(_) => MetadataUpdatedEvent::DISCRIMINATOR,
+            LaunchpadEvent::SetMintSuffix(_) => SetMintSuffixEvent::DISCRIMINATOR,
+            LaunchpadEvent::VaultMintDeposited(_) => VaultMintDepositedEvent::DISCRIMINATOR,
+            LaunchpadEvent::Unknown { discriminator, .. } => *discriminator,
+        }
+    }
+
+    /// The mint the event is about, `None` for global events.
+    pub fn mint(&self) -> Option<Pubkey> {
+        match self {
+            LaunchpadEvent::Create(event) => Some(event.mint),
+            LaunchpadEvent::Trade(event) => Some(event.mint),
+            LaunchpadEvent::Complete(event) => Some(event.mint),
+            LaunchpadEvent::StatusChanged(event) => Some(event.mint),
+            LaunchpadEvent::Graduated(event) => Some(event.mint),
+            LaunchpadEvent::Redeem(event) => Some(event.mint),
+            LaunchpadEvent::CloseCurve(event) => Some(event.mint),
+            LaunchpadEvent::MetadataUpdated(event) => Some(event.mint),
+            LaunchpadEvent::VaultMintDeposited(event) => Some(event.mint),
+            LaunchpadEvent::SetParams(_)
+            | LaunchpadEvent::SetCreateControls(_)
+            | LaunchpadEvent::CreatorAllowed(_)
+            | LaunchpadEvent::SetMintSuffix(_)
+            | LaunchpadEvent::Unknown { .. } => None,
+        }
+    }
+
+    /// The event's fields as JSON, keys and amounts as strings so nothing loses precision.
+    pub fn to_json(&self) -> Value {
+        match self {
+            LaunchpadEvent::Create(event) => json!({
+                "name": event.name,
+                "symbol": event.symbol,
+                "uri": event.uri,
+                "mint": event.mint.to_string(),
+                "bondingCurve": event.bonding_curve.to_string(),
+                "creator": event.creator.to_string(),
+            }),
+            LaunchpadEvent::Trade(event) => json!({
+                "mint": event.mint.to_string(),
+                "solAmount": event.sol_amount.to_string(),
+                "tokenAmount": event.token_amount.to_string(),
+                "isBuy": event.is_buy,
+                "user": event.user.to_string(),
+                "timestamp": event.timestamp,
+                "virtualSolReserves": event.virtual_sol_reserves.to_string(),
+                "virtualTokenReserves": event.virtual_token_reserves.to_string(),
+                "realSolReserves": event.real_sol_reserves.to_string(),
+                "realTokenReserves": event.real_token_reserves.to_string(),
+            }),
+            LaunchpadEvent::Complete(event) => json!({
+                "user": event.user.to_string(),
+                "mint": event.mint.to_string(),
+                "bondingCurve": event.bonding_curve.to_string(),
+                "timestamp": event.timestamp,
+            }),
+            LaunchpadEvent::StatusChanged(event) => json!({
+                "mint": event.mint.to_string(),
+                "bondingCurve": event.bonding_curve.to_string(),
+                "previousStatus": format!("{:?}", event.previous_status),
+                "status": format!("{:?}", event.status),
+                "timestamp": event.timestamp,
+            }),
+            LaunchpadEvent::Graduated(event) => json!({
+                "mint": event.mint.to_string(),
+                "bondingCurve": event.bonding_curve.to_string(),
+                "mintAuthorityRevoked": event.mint_authority_revoked,
+                "freezeAuthorityRevoked": event.freeze_authority_revoked,
+                "metadataMadeImmutable": event.metadata_made_immutable,
+                "timestamp": event.timestamp,
+            }),
+            LaunchpadEvent::Redeem(event) => json!({
+                "mint": event.mint.to_string(),
+                "solAmount": event.sol_amount.to_string(),
+                "tokenAmount": event.token_amount.to_string(),
+                "user": event.user.to_string(),
+                "timestamp": event.timestamp,
+                "realSolReserves": event.real_sol_reserves.to_string(),
+                "realTokenReserves": event.real_token_reserves.to_string(),
+            }),
+            LaunchpadEvent::CloseCurve(event) => json!({
+                "mint": event.mint.to_string(),
+                "bondingCurve": event.bonding_curve.to_string(),
+                "creator": event.creator.to_string(),
+                "lamports": event.lamports.to_string(),
+                "timestamp": event.timestamp,
+            }),
+            LaunchpadEvent::SetParams(event) => json!({
+                "feeRecipient": event.fee_recipient.to_string(),
+                "withdrawAuthority": event.withdraw_authority.to_string(),
+                "initialVirtualTokenReserves": event.initial_virtual_token_reserves.to_string(),
+                "initialVirtualSolReserves": event.initial_virtual_sol_reserves.to_string(),
+                "initialRealTokenReserves": event.initial_real_token_reserves.to_string(),
+                "initialTokenSupply": event.initial_token_supply.to_string(),
+                "feeBasisPoints": event.fee_basis_points.to_string(),
+            }),
+            LaunchpadEvent::SetCreateControls(event) => json!({
+                "creationFeeLamports": event.creation_fee_lamports.to_string(),
+                "creatorRateLimit": event.creator_rate_limit,
+                "creatorRateLimitWindow": event.creator_rate_limit_window,
+                "creatorAllowlist": event.creator_allowlist,
+            }),
+            LaunchpadEvent::CreatorAllowed(event) => json!({
+                "creator": event.creator.to_string(),
+                "allowed": event.allowed,
+            }),
+            LaunchpadEvent::MetadataUpdated(event) => json!({
+                "mint": event.mint.to_string(),
+                "name": event.name,
+                "symbol": event.symbol,
+                "uri": event.uri,
+                "timestamp": event.timestamp,
+            }),
+            LaunchpadEvent::SetMintSuffix(event) => json!({
+                "mintSuffix": event.mint_suffix,
+            }),
+            LaunchpadEvent::VaultMintDeposited(event) => json!({
+                "mint": event.mint.to_string(),
+                "vaultSize": event.vault_size,
+            }),
+            LaunchpadEvent::Unknown { data, .. } => json!({
+                "data": bs58::encode(data).into_string(),
+            }),
+        }
+    }
+}
+
+fn parse<E: AnchorDeserialize>(mut payload: &[u8]) -> Result<E> {
+    E::deserialize(&mut payload).map_err(|err| anyhow!("failed to decode event: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Event;
+    use curve_launchpad_client::curve_launchpad::state::CurveStatus;
+
+    fn emitted(event: &impl Event) -> Vec<u8> {
+        [&EVENT_IX_TAG_LE[..], &event.data()].concat()
+    }
+
+    fn trade_event() -> TradeEvent {
+        TradeEvent {
+            mint: Pubkey::new_unique(),
+            sol_amount: 1_000,
+            token_amount: 2_000,
+            is_buy: true,
+            user: Pubkey::new_unique(),
+            timestamp: 1_700_000_000,
+            virtual_sol_reserves: 30_000_001_000,
+            virtual_token_reserves: 1_072_999_999_998_000,
+            real_sol_reserves: 1_000,
+            real_token_reserves: 793_099_999_998_000,
+        }
+    }
+
+    #[test]
+    fn test_decode() {
+        let trade = trade_event();
+        let event = LaunchpadEvent::decode(&emitted(&trade)).unwrap().unwrap();
+
+        assert_eq!(event.name(), "TradeEvent");
+        assert_eq!(event.discriminator(), TradeEvent::DISCRIMINATOR);
+        assert_eq!(event.mint(), Some(trade.mint));
+        assert_eq!(event, LaunchpadEvent::Trade(trade));
+
+        let status_changed = StatusChangedEvent {
+            mint: Pubkey::new_unique(),
+            bonding_curve: Pubkey::new_unique(),
+            previous_status: CurveStatus::Trading,
+            status: CurveStatus::Complete,
+            timestamp: 1_700_000_000,
+        };
+        let event = LaunchpadEvent::decode(&emitted(&status_changed))
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.to_json()["status"], "Complete");
+    }
+
+    #[test]
+    fn test_decode_rejects() {
+        //instructions other than events are skipped
+        assert!(LaunchpadEvent::decode(&[1, 2, 3]).unwrap().is_none());
+        assert!(LaunchpadEvent::decode(&[]).unwrap().is_none());
+
+        //tagged as an event but broken
+        let data = emitted(&trade_event());
+        assert!(LaunchpadEvent::decode(&data[..data.len() - 1]).is_err());
+        assert!(LaunchpadEvent::decode(&data[..12]).is_err());
+
+    }
+
+    #[test]
+    fn test_decode_unknown() {
+        //events added after this build are kept raw rather than failing the batch
+        let mut data = emitted(&trade_event());
+        data[8] ^= 0xff;
+
+        let event = LaunchpadEvent::decode(&data).unwrap().unwrap();
+        assert_eq!(event.name(), "Unknown");
+        assert_eq!(event.discriminator()[..], data[8..16]);
+        assert_eq!(event.mint(), None);
+        assert_eq!(event.to_json()["data"], bs58::encode(&data[16..]).into_string());
+    }
+}
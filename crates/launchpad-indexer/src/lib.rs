@@ -0,0 +1,45 @@
+//! Indexes the launchpad's events.
+//!
+//! The program emits `CreateEvent`, `TradeEvent` and the rest with
+//! `emit_cpi!`, so they live in each transaction's inner instructions rather
+//! than its logs. A [`Source`] supplies transactions, from files or over RPC,
+//! [`RawTransaction::events`] finds and decodes the events in them, and a
+//! [`Sink`] stores them, [`SqliteSink`] in SQLite. [`CandleAggregator`] is
+//! a sink too, building OHLCV candles from the trades, and so is
+//! [`PositionTracker`], following each wallet's holdings and PnL.
+//!
+//! ```no_run
+//! use launchpad_indexer::{FileSource, SqliteSink};
+//!
+//! let mut source = FileSource::new("transactions");
+//! let mut sink = SqliteSink::open("events.db").unwrap();
+//! launchpad_indexer::run(&mut source, &mut sink).unwrap();
+//! ```
+
+pub mod candles;
+pub mod events;
+pub mod positions;
+pub mod sink;
+pub mod source;
+pub mod transaction;
+
+use anyhow::Result;
+
+pub use candles::{Candle, CandleAggregator, Interval};
+pub use events::LaunchpadEvent;
+pub use positions::{Position, PositionTracker};
+pub use sink::{Sink, SqliteSink};
+pub use source::{FileSource, RpcSource, Source};
+pub use transaction::{IndexedEvent, RawInstruction, RawTransaction};
+
+/// Polls `source` once and writes the events it finds to `sink`, returning how many there were.
+pub fn run(source: &mut impl Source, sink: &mut impl Sink) -> Result<usize> {
+    let mut events = Vec::new();
+
+    for transaction in source.poll()? {
+        events.extend(transaction.events()?);
+    }
+
+    sink.write(&events)?;
+    Ok(events.len())
+}
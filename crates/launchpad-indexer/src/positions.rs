@@ -0,0 +1,308 @@
+//! Per wallet positions built from `TradeEvent`s.
+//!
+//! Cost is tracked at average cost: a buy adds its SOL to the position's cost
+//! basis, a sell removes the sold tokens' share of it and realizes the
+//! difference to what they sold for. Trades carry the SOL that went to or from
+//! the curve, so fees are worked out from the fee rate, which follows
+//! `SetParamsEvent`s.
+
+use std::collections::{HashMap, HashSet};
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use curve_launchpad_client::curve_launchpad::instructions::{calculate_fee, TradeEvent};
+use serde::{Deserialize, Serialize};
+use solana_sdk::signature::Signature;
+
+use crate::{events::LaunchpadEvent, sink::Sink, transaction::IndexedEvent};
+
+/// A wallet's position in one mint. Amounts are in lamports and raw token units.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Position {
+    pub tokens: u64,
+    /// SOL paid for the tokens held, fees excluded.
+    pub cost_basis: u64,
+    /// SOL received for tokens sold less their cost, fees excluded.
+    pub realized_pnl: i64,
+    pub fees_paid: u64,
+    pub trades: u32,
+}
+
+impl Position {
+    /// Average lamports paid per token unit held.
+    pub fn average_cost(&self) -> Option<f64> {
+        (self.tokens > 0).then(|| self.cost_basis as f64 / self.tokens as f64)
+    }
+
+    fn apply(&mut self, trade: &TradeEvent, fee: u64) {
+        if trade.is_buy {
+            self.tokens = self.tokens.saturating_add(trade.token_amount);
+            self.cost_basis = self.cost_basis.saturating_add(trade.sol_amount);
+        } else {
+            //tokens that came from outside the curve, by transfer, have no cost
+            let sold = trade.token_amount.min(self.tokens);
+            let cost = (self.cost_basis as u128 * sold as u128)
+                .checked_div(self.tokens as u128)
+                .unwrap_or(0) as u64;
+
+            self.tokens = self.tokens.saturating_sub(trade.token_amount);
+            self.cost_basis -= cost;
+            self.realized_pnl = self
+                .realized_pnl
+                .saturating_add(trade.sol_amount as i64 - cost as i64);
+        }
+
+        self.fees_paid = self.fees_paid.saturating_add(fee);
+        self.trades += 1;
+    }
+}
+
+/// Positions for every wallet and mint traded.
+///
+/// Average cost depends on the order of trades, so events must be written in
+/// the order they happened, as the sources return them. Events from slots
+/// already applied are skipped, so a source can be replayed from an earlier
+/// point, and [`snapshot`](Self::snapshot) and [`restore`](Self::restore) let
+/// tracking resume after a restart.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PositionTracker {
+    positions: HashMap<(Pubkey, Pubkey), Position>,
+    fee_basis_points: u64,
+    //events applied from the latest slot, earlier slots are done
+    slot: u64,
+    applied: HashSet<(Signature, u32)>,
+}
+
+impl PositionTracker {
+    /// `fee_basis_points` is the fee rate when tracking starts, `Global::fee_basis_points`.
+    pub fn new(fee_basis_points: u64) -> Self {
+        PositionTracker {
+            fee_basis_points,
+            ..PositionTracker::default()
+        }
+    }
+
+    pub fn position(&self, user: &Pubkey, mint: &Pubkey) -> Option<&Position> {
+        self.positions.get(&(*user, *mint))
+    }
+
+    /// Every position, keyed by user and mint.
+    pub fn positions(&self) -> impl Iterator<Item = (&(Pubkey, Pubkey), &Position)> {
+        self.positions.iter()
+    }
+
+    /// Wallets holding `mint`, with how much.
+    pub fn holders(&self, mint: &Pubkey) -> Vec<(Pubkey, u64)> {
+        let mut holders: Vec<_> = self
+            .positions
+            .iter()
+            .filter(|((_, position_mint), position)| position_mint == mint && position.tokens > 0)
+            .map(|((user, _), position)| (*user, position.tokens))
+            .collect();
+        holders.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        holders
+    }
+
+    pub fn fee_basis_points(&self) -> u64 {
+        self.fee_basis_points
+    }
+
+    /// Applies the event unless it was already, returning whether it changed anything.
+    pub fn push(&mut self, indexed: &IndexedEvent) -> bool {
+        if indexed.slot < self.slot {
+            return false;
+        }
+        if indexed.slot > self.slot {
+            self.slot = indexed.slot;
+            self.applied.clear();
+        }
+        if !self.applied.insert((indexed.signature, indexed.index)) {
+            return false;
+        }
+
+        match &indexed.event {
+            LaunchpadEvent::Trade(trade) => {
+                let fee = calculate_fee(trade.sol_amount, self.fee_basis_points);
+                self.positions
+                    .entry((trade.user, trade.mint))
+                    .or_default()
+                    .apply(trade, fee);
+                true
+            }
+            LaunchpadEvent::SetParams(params) => {
+                self.fee_basis_points = params.fee_basis_points;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn snapshot(&self) -> Result<Vec<u8>> {
+        Ok(bincode::serialize(self)?)
+    }
+
+    pub fn restore(snapshot: &[u8]) -> Result<Self> {
+        Ok(bincode::deserialize(snapshot)?)
+    }
+}
+
+impl Sink for PositionTracker {
+    fn write(&mut self, events: &[IndexedEvent]) -> Result<()> {
+        for indexed in events {
+            self.push(indexed);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curve_launchpad_client::curve_launchpad::instructions::SetParamsEvent;
+
+    fn indexed(slot: u64, event: LaunchpadEvent) -> IndexedEvent {
+        IndexedEvent {
+            signature: Signature::new_unique(),
+            slot,
+            block_time: None,
+            index: 0,
+            event,
+        }
+    }
+
+    fn trade(
+        slot: u64,
+        user: Pubkey,
+        mint: Pubkey,
+        is_buy: bool,
+        sol_amount: u64,
+        token_amount: u64,
+    ) -> IndexedEvent {
+        indexed(
+            slot,
+            LaunchpadEvent::Trade(TradeEvent {
+                mint,
+                sol_amount,
+                token_amount,
+                is_buy,
+                user,
+                timestamp: slot as i64,
+                virtual_sol_reserves: 0,
+                virtual_token_reserves: 0,
+                real_sol_reserves: 0,
+                real_token_reserves: 0,
+            }),
+        )
+    }
+
+    fn set_fee(slot: u64, fee_basis_points: u64) -> IndexedEvent {
+        indexed(
+            slot,
+            LaunchpadEvent::SetParams(SetParamsEvent {
+                fee_recipient: Pubkey::new_unique(),
+                withdraw_authority: Pubkey::new_unique(),
+                initial_virtual_token_reserves: 0,
+                initial_virtual_sol_reserves: 0,
+                initial_real_token_reserves: 0,
+                initial_token_supply: 0,
+                fee_basis_points,
+            }),
+        )
+    }
+
+    #[test]
+    fn test_positions() {
+        let user = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let mut tracker = PositionTracker::new(100);
+
+        tracker
+            .write(&[
+                trade(1, user, mint, true, 1_000, 100),
+                trade(2, user, mint, true, 3_000, 100),
+                trade(2, other, mint, true, 500, 50),
+            ])
+            .unwrap();
+
+        let position = tracker.position(&user, &mint).unwrap();
+        assert_eq!(position.tokens, 200);
+        assert_eq!(position.cost_basis, 4_000);
+        assert_eq!(position.average_cost(), Some(20.0));
+        assert_eq!(position.fees_paid, 40);
+        assert_eq!(tracker.holders(&mint), [(user, 200), (other, 50)]);
+
+        //half sold for more than it cost, at a new fee rate
+        tracker
+            .write(&[set_fee(3, 50), trade(4, user, mint, false, 2_400, 100)])
+            .unwrap();
+
+        let position = tracker.position(&user, &mint).unwrap();
+        assert_eq!(position.tokens, 100);
+        assert_eq!(position.cost_basis, 2_000);
+        assert_eq!(position.realized_pnl, 400);
+        assert_eq!(position.fees_paid, 52);
+        assert_eq!(position.trades, 3);
+        assert_eq!(tracker.fee_basis_points(), 50);
+
+        //the rest at a loss
+        tracker
+            .write(&[trade(5, user, mint, false, 1_500, 100)])
+            .unwrap();
+
+        let position = tracker.position(&user, &mint).unwrap();
+        assert_eq!(position.tokens, 0);
+        assert_eq!(position.cost_basis, 0);
+        assert_eq!(position.average_cost(), None);
+        assert_eq!(position.realized_pnl, -100);
+        assert_eq!(tracker.holders(&mint), [(other, 50)]);
+    }
+
+    #[test]
+    fn test_tokens_from_elsewhere() {
+        let user = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let mut tracker = PositionTracker::new(0);
+
+        //bought 100, sold 150 after 50 more were sent to the wallet
+        tracker
+            .write(&[
+                trade(1, user, mint, true, 1_000, 100),
+                trade(2, user, mint, false, 1_800, 150),
+            ])
+            .unwrap();
+
+        let position = tracker.position(&user, &mint).unwrap();
+        assert_eq!(position.tokens, 0);
+        assert_eq!(position.cost_basis, 0);
+        assert_eq!(position.realized_pnl, 800);
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let user = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let events = [
+            trade(1, user, mint, true, 1_000, 100),
+            trade(2, user, mint, true, 1_000, 50),
+            trade(2, user, mint, false, 300, 25),
+            trade(3, user, mint, false, 300, 25),
+        ];
+
+        let mut tracker = PositionTracker::new(100);
+        tracker.write(&events[..3]).unwrap();
+
+        let mut restored = PositionTracker::restore(&tracker.snapshot().unwrap()).unwrap();
+        assert_eq!(restored, tracker);
+
+        //resuming replays from the last slot, nothing is applied twice
+        restored.write(&events[1..]).unwrap();
+        tracker.write(&events[3..]).unwrap();
+        assert_eq!(restored, tracker);
+
+        let position = restored.position(&user, &mint).unwrap();
+        assert_eq!(position.tokens, 100);
+        assert_eq!(position.trades, 4);
+    }
+}
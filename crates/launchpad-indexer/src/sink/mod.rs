@@ -0,0 +1,29 @@
+//! Where decoded events go.
+
+mod sqlite;
+
+use anyhow::Result;
+
+use crate::transaction::IndexedEvent;
+
+pub use sqlite::SqliteSink;
+
+pub trait Sink {
+    /// Writes one poll's events. Sinks should ignore events they already have,
+    /// so a source can be replayed from an earlier point.
+    fn write(&mut self, events: &[IndexedEvent]) -> Result<()>;
+}
+
+impl Sink for Vec<IndexedEvent> {
+    fn write(&mut self, events: &[IndexedEvent]) -> Result<()> {
+        for event in events {
+            if !self.iter().any(|existing| {
+                existing.signature == event.signature && existing.index == event.index
+            }) {
+                self.push(event.clone());
+            }
+        }
+
+        Ok(())
+    }
+}
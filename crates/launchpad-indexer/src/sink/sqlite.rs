@@ -0,0 +1,119 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+
+use crate::{events::LaunchpadEvent, sink::Sink, transaction::IndexedEvent};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS events (
+    signature TEXT NOT NULL,
+    event_index INTEGER NOT NULL,
+    slot INTEGER NOT NULL,
+    block_time INTEGER,
+    name TEXT NOT NULL,
+    discriminator BLOB NOT NULL,
+    mint TEXT,
+    data TEXT NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS events_by_mint ON events (mint, slot);
+
+CREATE TABLE IF NOT EXISTS trades (
+    signature TEXT NOT NULL,
+    event_index INTEGER NOT NULL,
+    slot INTEGER NOT NULL,
+    mint TEXT NOT NULL,
+    user TEXT NOT NULL,
+    is_buy INTEGER NOT NULL,
+    sol_amount INTEGER NOT NULL,
+    token_amount INTEGER NOT NULL,
+    timestamp INTEGER NOT NULL,
+    virtual_sol_reserves INTEGER NOT NULL,
+    virtual_token_reserves INTEGER NOT NULL,
+    real_sol_reserves INTEGER NOT NULL,
+    real_token_reserves INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS trades_by_mint ON trades (mint, timestamp);
+";
+
+/// Every event in `events`, with its fields as JSON in `data`, and trades
+/// again in typed columns in `trades` for analytics.
+pub struct SqliteSink {
+    connection: Connection,
+}
+
+impl SqliteSink {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::new(Connection::open(path)?)
+    }
+
+    pub fn in_memory() -> Result<Self> {
+        Self::new(Connection::open_in_memory()?)
+    }
+
+    fn new(connection: Connection) -> Result<Self> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(SqliteSink { connection })
+    }
+
+    /// For querying what has been indexed.
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+}
+
+impl Sink for SqliteSink {
+    fn write(&mut self, events: &[IndexedEvent]) -> Result<()> {
+        let transaction = self.connection.transaction()?;
+
+        for indexed in events {
+            let signature = indexed.signature.to_string();
+
+            transaction.execute(
+                "INSERT OR IGNORE INTO events (signature, event_index, slot, block_time, name, discriminator, mint, data)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    signature,
+                    indexed.index,
+                    indexed.slot,
+                    indexed.block_time,
+                    indexed.event.name(),
+                    &indexed.event.discriminator()[..],
+                    indexed.event.mint().map(|mint| mint.to_string()),
+                    indexed.event.to_json().to_string(),
+                ],
+            )?;
+
+            if let LaunchpadEvent::Trade(trade) = &indexed.event {
+                transaction
+                    .execute(
+                        "INSERT OR IGNORE INTO trades (signature, event_index, slot, mint, user, is_buy, sol_amount,
+                         token_amount, timestamp, virtual_sol_reserves, virtual_token_reserves, real_sol_reserves,
+                         real_token_reserves)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                        params![
+                            signature,
+                            indexed.index,
+                            indexed.slot,
+                            trade.mint.to_string(),
+                            trade.user.to_string(),
+                            trade.is_buy,
+                            trade.sol_amount,
+                            trade.token_amount,
+                            trade.timestamp,
+                            trade.virtual_sol_reserves,
+                            trade.virtual_token_reserves,
+                            trade.real_sol_reserves,
+                            trade.real_token_reserves,
+                        ],
+                    )
+                    .with_context(|| format!("failed to store trade in {signature}"))?;
+            }
+        }
+
+        transaction.commit()?;
+        Ok(())
+    }
+}
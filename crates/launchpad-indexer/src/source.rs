@@ -0,0 +1,160 @@
+//! Where transactions come from.
+
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{Context, Result};
+use curve_launchpad_client::curve_launchpad;
+use solana_client::{
+    rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient},
+    rpc_config::RpcTransactionConfig,
+};
+use solana_sdk::{commitment_config::CommitmentConfig, signature::Signature};
+use solana_transaction_status::UiTransactionEncoding;
+
+use crate::transaction::RawTransaction;
+
+pub trait Source {
+    /// Transactions not returned by an earlier poll, oldest first.
+    fn poll(&mut self) -> Result<Vec<RawTransaction>>;
+}
+
+/// A directory of transactions, `getTransaction` results as `.json` files or
+/// [`RawTransaction`]s as bincode `.bin` files. Files are read in name order,
+/// each once, so new files are picked up by later polls.
+pub struct FileSource {
+    dir: PathBuf,
+    seen: HashSet<PathBuf>,
+}
+
+impl FileSource {
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        FileSource {
+            dir: dir.as_ref().to_path_buf(),
+            seen: HashSet::new(),
+        }
+    }
+}
+
+impl Source for FileSource {
+    fn poll(&mut self) -> Result<Vec<RawTransaction>> {
+        let mut paths = fs::read_dir(&self.dir)
+            .with_context(|| format!("failed to read {}", self.dir.display()))?
+            .map(|entry| Ok(entry?.path()))
+            .collect::<Result<Vec<_>>>()?;
+        paths.sort();
+
+        let mut transactions = Vec::new();
+
+        for path in paths {
+            if self.seen.contains(&path) {
+                continue;
+            }
+
+            let transaction = match path.extension().and_then(|extension| extension.to_str()) {
+                Some("json") => RawTransaction::from_json(&fs::read_to_string(&path)?),
+                Some("bin") => RawTransaction::from_bincode(&fs::read(&path)?),
+                _ => continue,
+            }
+            .with_context(|| format!("failed to read {}", path.display()))?;
+
+            transactions.push(transaction);
+            self.seen.insert(path);
+        }
+
+        Ok(transactions)
+    }
+}
+
+/// Follows an address over RPC, the launchpad program unless told otherwise.
+/// Works against any node, including a local validator standing in for a cluster.
+pub struct RpcSource {
+    client: RpcClient,
+    address: Pubkey,
+    //newest signature seen, the next poll stops there
+    until: Option<Signature>,
+}
+
+impl RpcSource {
+    const PAGE_SIZE: usize = 1_000;
+
+    pub fn new(client: RpcClient) -> Self {
+        Self::for_address(client, curve_launchpad::ID)
+    }
+
+    pub fn for_address(client: RpcClient, address: Pubkey) -> Self {
+        RpcSource {
+            client,
+            address,
+            until: None,
+        }
+    }
+
+    /// Only transactions after `signature` will be returned.
+    pub fn starting_after(mut self, signature: Signature) -> Self {
+        self.until = Some(signature);
+        self
+    }
+}
+
+impl Source for RpcSource {
+    fn poll(&mut self) -> Result<Vec<RawTransaction>> {
+        let commitment = Some(CommitmentConfig::confirmed());
+        let mut signatures = Vec::new();
+        let mut before = None;
+
+        //signatures come newest first, page back until the last one seen
+        loop {
+            let page = self.client.get_signatures_for_address_with_config(
+                &self.address,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    until: self.until,
+                    limit: Some(Self::PAGE_SIZE),
+                    commitment,
+                },
+            )?;
+
+            let full = page.len() == Self::PAGE_SIZE;
+            before = page
+                .last()
+                .map(|status| status.signature.parse())
+                .transpose()?;
+            signatures.extend(page);
+
+            if !full {
+                break;
+            }
+        }
+
+        let mut transactions = Vec::new();
+
+        for status in signatures
+            .iter()
+            .rev()
+            .filter(|status| status.err.is_none())
+        {
+            let signature: Signature = status.signature.parse()?;
+            let encoded = self.client.get_transaction_with_config(
+                &signature,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    commitment,
+                    max_supported_transaction_version: Some(0),
+                },
+            )?;
+
+            transactions.push(RawTransaction::from_encoded(encoded)?);
+        }
+
+        if let Some(newest) = signatures.first() {
+            self.until = Some(newest.signature.parse()?);
+        }
+
+        Ok(transactions)
+    }
+}
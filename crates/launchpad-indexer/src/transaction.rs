@@ -0,0 +1,196 @@
+//! Transactions reduced to what event extraction needs.
+//!
+//! [`RawTransaction`] is built from `getTransaction` responses in any
+//! encoding, and is itself serde so it can be archived with bincode.
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, Context, Result};
+use curve_launchpad_client::{curve_launchpad, pda};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::{
+    option_serializer::OptionSerializer, EncodedConfirmedTransactionWithStatusMeta,
+    EncodedTransaction, UiInstruction, UiMessage, UiParsedInstruction,
+};
+
+use crate::events::LaunchpadEvent;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RawInstruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<Pubkey>,
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RawTransaction {
+    pub signature: Signature,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub failed: bool,
+    /// Every inner instruction, in execution order.
+    pub inner_instructions: Vec<RawInstruction>,
+}
+
+/// A decoded event and where it came from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IndexedEvent {
+    pub signature: Signature,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    /// Position among the transaction's events.
+    pub index: u32,
+    pub event: LaunchpadEvent,
+}
+
+impl RawTransaction {
+    /// Parses a `getTransaction` result, or the whole JSON-RPC response around one.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let mut value: Value = serde_json::from_str(json)?;
+
+        if let Some(result) = value.get_mut("result") {
+            value = result.take();
+        }
+
+        Self::from_encoded(serde_json::from_value(value)?)
+    }
+
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self> {
+        Ok(bincode::deserialize(bytes)?)
+    }
+
+    pub fn to_bincode(&self) -> Result<Vec<u8>> {
+        Ok(bincode::serialize(self)?)
+    }
+
+    pub fn from_encoded(encoded: EncodedConfirmedTransactionWithStatusMeta) -> Result<Self> {
+        let transaction = encoded.transaction;
+        let meta = transaction.meta.context("transaction has no status meta")?;
+
+        let (signature, mut account_keys) = match &transaction.transaction {
+            EncodedTransaction::Json(ui_transaction) => {
+                let signature = ui_transaction
+                    .signatures
+                    .first()
+                    .context("transaction has no signatures")?
+                    .parse()?;
+
+                let account_keys = match &ui_transaction.message {
+                    UiMessage::Raw(message) => message
+                        .account_keys
+                        .iter()
+                        .map(|key| key.parse())
+                        .collect::<Result<Vec<Pubkey>, _>>()?,
+                    UiMessage::Parsed(message) => message
+                        .account_keys
+                        .iter()
+                        .map(|account| account.pubkey.parse())
+                        .collect::<Result<Vec<Pubkey>, _>>()?,
+                };
+
+                (signature, account_keys)
+            }
+            encoded => {
+                let versioned = encoded
+                    .decode()
+                    .ok_or_else(|| anyhow!("unsupported transaction encoding"))?;
+                (
+                    versioned.signatures[0],
+                    versioned.message.static_account_keys().to_vec(),
+                )
+            }
+        };
+
+        //parsed messages already list the lookup table accounts, raw and binary ones leave them to the meta
+        let is_parsed = matches!(
+            &transaction.transaction,
+            EncodedTransaction::Json(ui_transaction) if matches!(ui_transaction.message, UiMessage::Parsed(_))
+        );
+        if let (OptionSerializer::Some(loaded), false) = (&meta.loaded_addresses, is_parsed) {
+            for key in loaded.writable.iter().chain(&loaded.readonly) {
+                account_keys.push(key.parse()?);
+            }
+        }
+
+        let key = |index: u8| {
+            account_keys
+                .get(index as usize)
+                .copied()
+                .with_context(|| format!("account index {index} out of range"))
+        };
+
+        let mut inner_instructions = Vec::new();
+
+        if let OptionSerializer::Some(inner) = &meta.inner_instructions {
+            for instruction in inner.iter().flat_map(|inner| &inner.instructions) {
+                let raw = match instruction {
+                    UiInstruction::Compiled(compiled) => RawInstruction {
+                        program_id: key(compiled.program_id_index)?,
+                        accounts: compiled
+                            .accounts
+                            .iter()
+                            .map(|&index| key(index))
+                            .collect::<Result<_>>()?,
+                        data: bs58::decode(&compiled.data).into_vec()?,
+                    },
+                    UiInstruction::Parsed(UiParsedInstruction::PartiallyDecoded(decoded)) => {
+                        RawInstruction {
+                            program_id: decoded.program_id.parse()?,
+                            accounts: decoded
+                                .accounts
+                                .iter()
+                                .map(|key| key.parse())
+                                .collect::<Result<_, _>>()?,
+                            data: bs58::decode(&decoded.data).into_vec()?,
+                        }
+                    }
+                    //fully parsed instructions belong to programs the RPC knows, never the launchpad
+                    UiInstruction::Parsed(UiParsedInstruction::Parsed(_)) => continue,
+                };
+
+                inner_instructions.push(raw);
+            }
+        }
+
+        Ok(RawTransaction {
+            signature,
+            slot: encoded.slot,
+            block_time: encoded.block_time,
+            failed: meta.err.is_some(),
+            inner_instructions,
+        })
+    }
+
+    /// The launchpad events the transaction emitted, none if it failed.
+    pub fn events(&self) -> Result<Vec<IndexedEvent>> {
+        if self.failed {
+            return Ok(Vec::new());
+        }
+
+        let event_authority = pda::event_authority();
+        let mut events = Vec::new();
+
+        //only the program's own calls signed by its event authority are events
+        let self_cpis = self.inner_instructions.iter().filter(|instruction| {
+            instruction.program_id == curve_launchpad::ID
+                && instruction.accounts.first() == Some(&event_authority)
+        });
+
+        for instruction in self_cpis {
+            if let Some(event) = LaunchpadEvent::decode(&instruction.data)
+                .with_context(|| format!("in transaction {}", self.signature))?
+            {
+                events.push(IndexedEvent {
+                    signature: self.signature,
+                    slot: self.slot,
+                    block_time: self.block_time,
+                    index: events.len() as u32,
+                    event,
+                });
+            }
+        }
+
+        Ok(events)
+    }
+}
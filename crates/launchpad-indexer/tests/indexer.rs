@@ -0,0 +1,475 @@
+//! Builds transactions the way a validator records them, with the events as
+//! self-CPIs among their inner instructions, and indexes them from every RPC
+//! encoding, from files and from a stand-in RPC node.
+
+use std::{
+    fs,
+    sync::{Arc, Mutex},
+};
+
+use anchor_lang::{event::EVENT_IX_TAG_LE, prelude::Pubkey, Event};
+use async_trait::async_trait;
+use curve_launchpad_client::{
+    curve_launchpad::instructions::{CompleteEvent, CreateEvent, SetParamsEvent, TradeEvent},
+    instructions, pda, PROGRAM_ID,
+};
+use launchpad_indexer::{FileSource, LaunchpadEvent, RawTransaction, RpcSource, Sink, SqliteSink};
+use serde_json::{json, Value};
+use solana_client::{
+    client_error::Result as ClientResult,
+    rpc_client::{RpcClient, RpcClientConfig},
+    rpc_request::RpcRequest,
+    rpc_sender::{RpcSender, RpcTransportStats},
+};
+use solana_sdk::{
+    hash::Hash,
+    instruction::{CompiledInstruction, InstructionError},
+    message::{v0, VersionedMessage},
+    signature::{Keypair, Signature},
+    signer::Signer,
+    transaction::{TransactionError, VersionedTransaction},
+};
+use solana_transaction_status::{
+    ConfirmedTransactionWithStatusMeta, InnerInstruction, InnerInstructions, TransactionStatusMeta,
+    TransactionWithStatusMeta, UiTransactionEncoding, VersionedTransactionWithStatusMeta,
+};
+
+fn emitted(event: &impl Event) -> Vec<u8> {
+    [&EVENT_IX_TAG_LE[..], &event.data()].concat()
+}
+
+fn trade_event(mint: Pubkey, user: Pubkey, sol_amount: u64) -> TradeEvent {
+    TradeEvent {
+        mint,
+        sol_amount,
+        token_amount: sol_amount * 30,
+        is_buy: true,
+        user,
+        timestamp: 1_700_000_000,
+        virtual_sol_reserves: 30_000_000_000 + sol_amount,
+        virtual_token_reserves: 1_073_000_000_000_000 - sol_amount * 30,
+        real_sol_reserves: sol_amount,
+        real_token_reserves: 793_100_000_000_000 - sol_amount * 30,
+    }
+}
+
+/// A buy as a validator records it, emitting `events` as self-CPIs. `slot` also varies the blockhash
+/// so every transaction has its own signature.
+fn buy_transaction(
+    slot: u64,
+    events: &[Vec<u8>],
+    failed: bool,
+) -> ConfirmedTransactionWithStatusMeta {
+    let user = Keypair::new();
+    let mint = Pubkey::new_unique();
+    let buy = instructions::buy(&user.pubkey(), &mint, &Pubkey::new_unique(), 1_000, 2_000);
+
+    let message = v0::Message::try_compile(
+        &user.pubkey(),
+        &[buy],
+        &[],
+        Hash::new_from_array([slot as u8; 32]),
+    )
+    .unwrap();
+    let index = |key: &Pubkey| message.account_keys.iter().position(|k| k == key).unwrap() as u8;
+
+    let mut inner = vec![
+        //the SOL transfer to the curve, which isn't an event
+        InnerInstruction {
+            instruction: CompiledInstruction::new_from_raw_parts(
+                index(&anchor_lang::system_program::ID),
+                vec![2, 0, 0, 0, 232, 3, 0, 0, 0, 0, 0, 0],
+                vec![index(&user.pubkey()), index(&pda::bonding_curve(&mint))],
+            ),
+            stack_height: Some(2),
+        },
+        //a call into the launchpad that isn't signed by its event authority
+        InnerInstruction {
+            instruction: CompiledInstruction::new_from_raw_parts(
+                index(&PROGRAM_ID),
+                events.first().cloned().unwrap_or_default(),
+                vec![index(&user.pubkey())],
+            ),
+            stack_height: Some(2),
+        },
+    ];
+
+    for data in events {
+        inner.push(InnerInstruction {
+            instruction: CompiledInstruction::new_from_raw_parts(
+                index(&PROGRAM_ID),
+                data.clone(),
+                vec![index(&pda::event_authority())],
+            ),
+            stack_height: Some(2),
+        });
+    }
+
+    let transaction =
+        VersionedTransaction::try_new(VersionedMessage::V0(message), &[&user]).unwrap();
+
+    ConfirmedTransactionWithStatusMeta {
+        slot,
+        tx_with_meta: TransactionWithStatusMeta::Complete(VersionedTransactionWithStatusMeta {
+            transaction,
+            meta: TransactionStatusMeta {
+                status: if failed {
+                    Err(TransactionError::InstructionError(
+                        0,
+                        InstructionError::Custom(6002),
+                    ))
+                } else {
+                    Ok(())
+                },
+                inner_instructions: Some(vec![InnerInstructions {
+                    index: 0,
+                    instructions: inner,
+                }]),
+                ..TransactionStatusMeta::default()
+            },
+        }),
+        block_time: Some(1_700_000_000 + slot as i64),
+    }
+}
+
+fn encode(
+    transaction: &ConfirmedTransactionWithStatusMeta,
+    encoding: UiTransactionEncoding,
+) -> Value {
+    serde_json::to_value(transaction.clone().encode(encoding, Some(0)).unwrap()).unwrap()
+}
+
+fn signature(transaction: &ConfirmedTransactionWithStatusMeta) -> Signature {
+    transaction.get_transaction().signatures[0]
+}
+
+fn sample_events() -> Vec<Vec<u8>> {
+    let mint = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+
+    vec![
+        emitted(&CreateEvent {
+            name: "test".to_string(),
+            symbol: "tst".to_string(),
+            uri: "https://www.test.com".to_string(),
+            mint,
+            bonding_curve: pda::bonding_curve(&mint),
+            creator: user,
+        }),
+        emitted(&trade_event(mint, user, 1_000_000)),
+        emitted(&CompleteEvent {
+            user,
+            mint,
+            bonding_curve: pda::bonding_curve(&mint),
+            timestamp: 1_700_000_000,
+        }),
+        emitted(&SetParamsEvent {
+            fee_recipient: Pubkey::new_unique(),
+            withdraw_authority: Pubkey::new_unique(),
+            initial_virtual_token_reserves: 1_073_000_000_000_000,
+            initial_virtual_sol_reserves: 30_000_000_000,
+            initial_real_token_reserves: 793_100_000_000_000,
+            initial_token_supply: 1_000_000_000_000_000,
+            fee_basis_points: 50,
+        }),
+    ]
+}
+
+#[test]
+fn decodes_every_encoding() {
+    let transaction = buy_transaction(10, &sample_events(), false);
+
+    let expected = RawTransaction::from_encoded(
+        transaction
+            .clone()
+            .encode(UiTransactionEncoding::Base64, Some(0))
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(expected.signature, signature(&transaction));
+    assert_eq!(expected.slot, 10);
+    assert_eq!(expected.block_time, Some(1_700_000_010));
+
+    let events = expected.events().unwrap();
+    let names: Vec<_> = events.iter().map(|indexed| indexed.event.name()).collect();
+    assert_eq!(
+        names,
+        [
+            "CreateEvent",
+            "TradeEvent",
+            "CompleteEvent",
+            "SetParamsEvent"
+        ]
+    );
+    assert_eq!(
+        events
+            .iter()
+            .map(|indexed| indexed.index)
+            .collect::<Vec<_>>(),
+        [0, 1, 2, 3]
+    );
+
+    for encoding in [
+        UiTransactionEncoding::Json,
+        UiTransactionEncoding::JsonParsed,
+        UiTransactionEncoding::Base58,
+    ] {
+        let json = encode(&transaction, encoding).to_string();
+        assert_eq!(
+            RawTransaction::from_json(&json).unwrap().events().unwrap(),
+            events,
+            "{encoding:?}"
+        );
+    }
+
+    //as the body of a JSON-RPC response
+    let response = json!({ "jsonrpc": "2.0", "id": 1, "result": encode(&transaction, UiTransactionEncoding::Json) });
+    assert_eq!(
+        RawTransaction::from_json(&response.to_string()).unwrap(),
+        expected
+    );
+
+    //and archived with bincode
+    assert_eq!(
+        RawTransaction::from_bincode(&expected.to_bincode().unwrap()).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn failed_transactions_have_no_events() {
+    let transaction = buy_transaction(11, &sample_events(), true);
+    let raw = RawTransaction::from_encoded(
+        transaction
+            .encode(UiTransactionEncoding::Base64, Some(0))
+            .unwrap(),
+    )
+    .unwrap();
+
+    assert!(raw.failed);
+    assert!(raw.events().unwrap().is_empty());
+}
+
+#[test]
+fn broken_events_are_errors() {
+    let mut data = sample_events().remove(1);
+    data.truncate(data.len() - 4);
+
+    let transaction = buy_transaction(12, &[data], false);
+    let raw = RawTransaction::from_encoded(
+        transaction
+            .encode(UiTransactionEncoding::Base64, Some(0))
+            .unwrap(),
+    )
+    .unwrap();
+
+    assert!(raw.events().is_err());
+}
+
+#[test]
+fn indexes_files_into_sqlite() {
+    let dir = std::env::temp_dir().join(format!("launchpad-indexer-{}", Pubkey::new_unique()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let first = buy_transaction(20, &sample_events(), false);
+    let second = buy_transaction(21, &sample_events()[1..2], false);
+    let failed = buy_transaction(22, &sample_events(), true);
+
+    fs::write(
+        dir.join("0020.json"),
+        encode(&first, UiTransactionEncoding::Json).to_string(),
+    )
+    .unwrap();
+    fs::write(
+        dir.join("0022.json"),
+        encode(&failed, UiTransactionEncoding::Base64).to_string(),
+    )
+    .unwrap();
+    let second_raw = RawTransaction::from_encoded(
+        second
+            .clone()
+            .encode(UiTransactionEncoding::Base64, Some(0))
+            .unwrap(),
+    )
+    .unwrap();
+    fs::write(dir.join("0021.bin"), second_raw.to_bincode().unwrap()).unwrap();
+    fs::write(dir.join("README"), "not a transaction").unwrap();
+
+    let mut source = FileSource::new(&dir);
+    let mut sink = SqliteSink::in_memory().unwrap();
+
+    assert_eq!(launchpad_indexer::run(&mut source, &mut sink).unwrap(), 5);
+    //nothing new
+    assert_eq!(launchpad_indexer::run(&mut source, &mut sink).unwrap(), 0);
+
+    let count = |sink: &SqliteSink, table: &str| -> i64 {
+        sink.connection()
+            .query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| {
+                row.get(0)
+            })
+            .unwrap()
+    };
+    assert_eq!(count(&sink, "events"), 5);
+    assert_eq!(count(&sink, "trades"), 2);
+
+    let (name, mint, data): (String, String, String) = sink
+        .connection()
+        .query_row(
+            "SELECT name, mint, data FROM events WHERE signature = ?1 AND event_index = 0",
+            [signature(&second).to_string()],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    let data: Value = serde_json::from_str(&data).unwrap();
+    assert_eq!(name, "TradeEvent");
+    assert_eq!(data["mint"], mint);
+    assert_eq!(data["solAmount"], "1000000");
+
+    let sol_amount: i64 = sink
+        .connection()
+        .query_row(
+            "SELECT sol_amount FROM trades WHERE signature = ?1",
+            [signature(&second).to_string()],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(sol_amount, 1_000_000);
+
+    //a later file is picked up by the next poll, replaying from scratch adds nothing twice
+    let third = buy_transaction(23, &sample_events()[1..2], false);
+    fs::write(
+        dir.join("0023.json"),
+        encode(&third, UiTransactionEncoding::JsonParsed).to_string(),
+    )
+    .unwrap();
+    assert_eq!(launchpad_indexer::run(&mut source, &mut sink).unwrap(), 1);
+
+    assert_eq!(
+        launchpad_indexer::run(&mut FileSource::new(&dir), &mut sink).unwrap(),
+        6
+    );
+    assert_eq!(count(&sink, "events"), 6);
+    assert_eq!(count(&sink, "trades"), 3);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+/// A node that only knows the transactions it's given, answering the
+/// requests `RpcSource` makes.
+#[derive(Clone, Default)]
+struct StandInRpc {
+    //oldest first
+    transactions: Arc<Mutex<Vec<ConfirmedTransactionWithStatusMeta>>>,
+}
+
+impl StandInRpc {
+    fn push(&self, transaction: ConfirmedTransactionWithStatusMeta) {
+        self.transactions.lock().unwrap().push(transaction);
+    }
+}
+
+#[async_trait]
+impl RpcSender for StandInRpc {
+    async fn send(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        let transactions = self.transactions.lock().unwrap();
+
+        match request {
+            RpcRequest::GetSignaturesForAddress => {
+                let config = &params[1];
+                let limit = config["limit"].as_u64().unwrap() as usize;
+
+                let mut newest_first: Vec<_> = transactions.iter().rev().collect();
+                if let Some(before) = config["before"].as_str() {
+                    let position = newest_first
+                        .iter()
+                        .position(|t| signature(t).to_string() == before)
+                        .unwrap();
+                    newest_first.drain(..=position);
+                }
+                if let Some(until) = config["until"].as_str() {
+                    let position = newest_first
+                        .iter()
+                        .position(|t| signature(t).to_string() == until)
+                        .unwrap();
+                    newest_first.truncate(position);
+                }
+
+                let statuses: Vec<_> = newest_first
+                    .into_iter()
+                    .take(limit)
+                    .map(|transaction| {
+                        let err = match &transaction.tx_with_meta {
+                            TransactionWithStatusMeta::Complete(complete) => {
+                                complete.meta.status.clone().err()
+                            }
+                            TransactionWithStatusMeta::MissingMetadata(_) => None,
+                        };
+
+                        json!({
+                            "signature": signature(transaction).to_string(),
+                            "slot": transaction.slot,
+                            "err": err,
+                            "memo": null,
+                            "blockTime": transaction.block_time,
+                            "confirmationStatus": "confirmed",
+                        })
+                    })
+                    .collect();
+
+                Ok(json!(statuses))
+            }
+            RpcRequest::GetTransaction => {
+                let requested = params[0].as_str().unwrap();
+                let encoding = serde_json::from_value(params[1]["encoding"].clone()).unwrap();
+
+                let transaction = transactions
+                    .iter()
+                    .find(|transaction| signature(transaction).to_string() == requested)
+                    .unwrap();
+
+                Ok(encode(transaction, encoding))
+            }
+            //asked before the other requests to pick method names for old nodes
+            RpcRequest::GetVersion => Ok(json!({ "solana-core": "1.18.26", "feature-set": 0 })),
+            request => panic!("stand-in RPC doesn't answer {request}"),
+        }
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        RpcTransportStats::default()
+    }
+
+    fn url(&self) -> String {
+        "stand-in".to_string()
+    }
+}
+
+#[test]
+fn follows_the_program_over_rpc() {
+    let rpc = StandInRpc::default();
+    rpc.push(buy_transaction(30, &sample_events(), false));
+    rpc.push(buy_transaction(31, &sample_events(), true));
+    rpc.push(buy_transaction(32, &sample_events()[1..2], false));
+
+    let client = RpcClient::new_sender(rpc.clone(), RpcClientConfig::default());
+    let mut source = RpcSource::new(client);
+    let mut sink: Vec<_> = Vec::new();
+
+    assert_eq!(launchpad_indexer::run(&mut source, &mut sink).unwrap(), 5);
+    //oldest first
+    assert_eq!(sink[0].slot, 30);
+    assert_eq!(sink[4].slot, 32);
+    assert!(matches!(sink[4].event, LaunchpadEvent::Trade(_)));
+
+    assert_eq!(launchpad_indexer::run(&mut source, &mut sink).unwrap(), 0);
+
+    rpc.push(buy_transaction(33, &sample_events()[..1], false));
+    assert_eq!(launchpad_indexer::run(&mut source, &mut sink).unwrap(), 1);
+    assert_eq!(sink.len(), 6);
+    assert!(matches!(sink[5].event, LaunchpadEvent::Create(_)));
+
+    //vec sinks skip what they already have too
+    let events = sink.clone();
+    sink.write(&events).unwrap();
+    assert_eq!(sink.len(), 6);
+}
@@ -0,0 +1,63 @@
+//! Drives random buy and sell sequences through `AMM` at mainnet-scale
+//! reserves, checking the curve's invariants after every trade.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use curve_launchpad::amm::AMM;
+use libfuzzer_sys::fuzz_target;
+
+const INITIAL_VIRTUAL_SOL_RESERVES: u128 = 30_000_000_000;
+const INITIAL_VIRTUAL_TOKEN_RESERVES: u128 = 1_073_000_000_000_000;
+const INITIAL_REAL_TOKEN_RESERVES: u128 = 793_100_000_000_000;
+
+#[derive(Arbitrary, Debug)]
+enum Trade {
+    Buy(u64),
+    Sell(u64),
+}
+
+fuzz_target!(|trades: Vec<Trade>| {
+    let mut amm = AMM::new(
+        INITIAL_VIRTUAL_SOL_RESERVES,
+        INITIAL_VIRTUAL_TOKEN_RESERVES,
+        0,
+        INITIAL_REAL_TOKEN_RESERVES,
+    );
+
+    //tokens bought so far and the SOL paid for them, sells can't exceed either
+    let mut held: u128 = 0;
+    let mut paid: u128 = 0;
+
+    for trade in trades {
+        let k = amm.virtual_sol_reserves * amm.virtual_token_reserves;
+
+        match trade {
+            Trade::Buy(tokens) => {
+                if let Some(result) = amm.apply_buy(tokens as u128) {
+                    held += result.token_amount as u128;
+                    paid += result.sol_amount as u128;
+                }
+            }
+            Trade::Sell(tokens) => {
+                let tokens = tokens as u128 % (held + 1);
+                if tokens == 0 {
+                    continue;
+                }
+
+                if let Some(result) = amm.apply_sell(tokens) {
+                    held -= tokens;
+                    paid = paid
+                        .checked_sub(result.sol_amount as u128)
+                        .expect("sells returned more SOL than buys paid");
+                }
+            }
+        }
+
+        assert!(amm.virtual_sol_reserves * amm.virtual_token_reserves >= k);
+        assert!(amm.real_sol_reserves <= amm.virtual_sol_reserves);
+        assert!(amm.real_token_reserves <= amm.virtual_token_reserves);
+        assert_eq!(amm.real_token_reserves + held, INITIAL_REAL_TOKEN_RESERVES);
+        assert_eq!(amm.real_sol_reserves, paid);
+    }
+});
@@ -1,5 +1,7 @@
 use std::fmt;
 
+use crate::amm::math::{mul_div_ceil, mul_div_floor};
+
 #[derive(Debug)]
 pub struct BuyResult {
     pub token_amount: u64,
@@ -18,7 +20,6 @@ pub struct AMM {
     pub virtual_token_reserves: u128,
     pub real_sol_reserves: u128,
     pub real_token_reserves: u128,
-    pub initial_virtual_token_reserves: u128,
 }
 
 impl AMM {
@@ -27,14 +28,12 @@ impl AMM {
         virtual_token_reserves: u128,
         real_sol_reserves: u128,
         real_token_reserves: u128,
-        initial_virtual_token_reserves: u128,
     ) -> Self {
         AMM {
             virtual_sol_reserves,
             virtual_token_reserves,
             real_sol_reserves,
             real_token_reserves,
-            initial_virtual_token_reserves,
         }
     }
 
@@ -43,9 +42,14 @@ impl AMM {
             return None;
         }
 
-        let product_of_reserves = self.virtual_sol_reserves.checked_mul(self.virtual_token_reserves)?;
         let new_virtual_token_reserves = self.virtual_token_reserves.checked_sub(tokens)?;
-        let new_virtual_sol_reserves = product_of_reserves.checked_div(new_virtual_token_reserves)?.checked_add(1)?;
+
+        //the curve keeps at least its product of reserves, so the SOL owed rounds up
+        let new_virtual_sol_reserves = mul_div_ceil(
+            self.virtual_sol_reserves,
+            self.virtual_token_reserves,
+            new_virtual_token_reserves,
+        )?;
         let amount_needed = new_virtual_sol_reserves.checked_sub(self.virtual_sol_reserves)?;
 
         Some(amount_needed)
@@ -87,28 +91,79 @@ impl AMM {
         })
     }
 
+    // expects `tokens` to already be added to the virtual token reserves, as `apply_sell` does
     pub fn get_sell_price(&self, tokens: u128) -> Option<u128> {
-        if tokens <= 0 || tokens > self.virtual_token_reserves {
+        if tokens == 0 || tokens > self.virtual_token_reserves {
             return None;
         }
 
-        let scaling_factor = self.initial_virtual_token_reserves;
-
-        let scaled_tokens = tokens.checked_mul(scaling_factor)?;
-        let token_sell_proportion = scaled_tokens.checked_div(self.virtual_token_reserves)?;
-        let sol_received = (self.virtual_sol_reserves.checked_mul(token_sell_proportion)?).checked_div(scaling_factor)?;
+        //the SOL paid out rounds down, keeping the product of reserves from shrinking
+        let sol_received = mul_div_floor(self.virtual_sol_reserves, tokens, self.virtual_token_reserves)?;
 
+        //only binds when more tokens come back than the curve ever sold, which
+        //can't happen on chain since holders can only sell what they bought
         Some(sol_received.min(self.real_sol_reserves))
     }
-}
 
+    // smallest token amount whose buy price covers `sol`
+    pub fn get_tokens_to_raise(&self, sol: u128) -> Option<u128> {
+        if sol == 0 {
+            return Some(0);
+        }
+
+        self.find_min_tokens(|tokens| Some(self.get_buy_price(tokens)? >= sol))
+    }
+
+    pub fn get_market_cap(&self, token_total_supply: u128) -> Option<u128> {
+        self.virtual_sol_reserves
+            .checked_mul(token_total_supply)?
+            .checked_div(self.virtual_token_reserves)
+    }
+
+    // smallest token amount whose purchase lifts the market cap to `market_cap`
+    pub fn get_tokens_to_market_cap(&self, market_cap: u128, token_total_supply: u128) -> Option<u128> {
+        if self.get_market_cap(token_total_supply)? >= market_cap {
+            return Some(0);
+        }
+
+        self.find_min_tokens(|tokens| {
+            let market_cap_after = self
+                .virtual_sol_reserves
+                .checked_add(self.get_buy_price(tokens)?)?
+                .checked_mul(token_total_supply)?
+                .checked_div(self.virtual_token_reserves.checked_sub(tokens)?)?;
+            Some(market_cap_after >= market_cap)
+        })
+    }
+
+    // binary search for the smallest buy out of the real reserves that satisfies `reached`,
+    // which must be monotonic in the token amount
+    fn find_min_tokens(&self, reached: impl Fn(u128) -> Option<bool>) -> Option<u128> {
+        let mut low = 1;
+        let mut high = self.real_token_reserves;
+        if high < low || !reached(high)? {
+            return None;
+        }
+
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if reached(mid)? {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+
+        Some(low)
+    }
+}
 
 impl fmt::Display for AMM {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "AMM {{ virtual_sol_reserves: {}, virtual_token_reserves: {}, real_sol_reserves: {}, real_token_reserves: {}, initial_virtual_token_reserves: {} }}",
-            self.virtual_sol_reserves, self.virtual_token_reserves, self.real_sol_reserves, self.real_token_reserves, self.initial_virtual_token_reserves
+            "AMM {{ virtual_sol_reserves: {}, virtual_token_reserves: {}, real_sol_reserves: {}, real_token_reserves: {} }}",
+            self.virtual_sol_reserves, self.virtual_token_reserves, self.real_sol_reserves, self.real_token_reserves
         )
     }
 }
@@ -124,16 +179,15 @@ mod tests {
         let virtual_token_reserves = 600;
         let real_sol_reserves = 0;
         let real_token_reserves = 500;
-        let initial_virtual_token_reserves = 1000;
 
-        let mut amm = AMM::new(virtual_sol_reserves, virtual_token_reserves, real_sol_reserves, real_token_reserves, initial_virtual_token_reserves);
+        let mut amm = AMM::new(virtual_sol_reserves, virtual_token_reserves, real_sol_reserves, real_token_reserves);
 
         //println!("{} \n", 1/0);
         // Attempt to buy more tokens than available in reserves
         let buy_result = amm.apply_buy(2000).unwrap();
         println!("{:?} \n", buy_result);
         assert_eq!(buy_result.token_amount, 500); // Should buy up to available real_token_reserves
-        assert_eq!(buy_result.sol_amount, 3001);
+        assert_eq!(buy_result.sol_amount, 3000); // 600 * 600 / 100 divides exactly, nothing to round up
         assert_eq!(amm.real_token_reserves, real_token_reserves - buy_result.token_amount as u128);
         assert_eq!(amm.virtual_token_reserves, virtual_token_reserves - buy_result.token_amount as u128); 
         assert_eq!(amm.real_sol_reserves, real_sol_reserves + buy_result.sol_amount as u128);   
@@ -144,7 +198,7 @@ mod tests {
         // Attempt to sell more tokens than available in reserves
         let sell_result = amm.apply_sell(2000).unwrap();
         assert_eq!(sell_result.token_amount, 2000); // Should sell requested amount
-        assert_eq!(sell_result.sol_amount, 3001);    
+        assert_eq!(sell_result.sol_amount, 3000); // Capped at what the buy paid in
         assert_eq!(amm.real_sol_reserves, 0); 
         assert_eq!(amm.virtual_sol_reserves, 600);  
         assert_eq!(amm.real_token_reserves, 2000);  
@@ -155,7 +209,7 @@ mod tests {
 
     #[test]
     fn test_apply_sell() {
-        let mut amm = AMM::new(1000, 1000, 500, 500, 1000);
+        let mut amm = AMM::new(1000, 1000, 500, 500);
         let result = amm.apply_sell(100).unwrap();
 
         assert_eq!(result.token_amount, 100);
@@ -168,7 +222,7 @@ mod tests {
 
     #[test]
     fn test_get_sell_price() {
-        let amm = AMM::new(1000, 1000, 500, 500, 1000);
+        let amm = AMM::new(1000, 1000, 500, 500);
 
         // Edge case: zero tokens
         assert_eq!(amm.get_sell_price(0), None);
@@ -186,14 +240,12 @@ mod tests {
         let virtual_token_reserves = 600;
         let real_sol_reserves = 500;
         let real_token_reserves = 500;
-        let initial_virtual_token_reserves = 1000;
 
         let mut amm = AMM::new(
             virtual_sol_reserves, 
             virtual_token_reserves, 
             real_sol_reserves, 
-            real_token_reserves, 
-            initial_virtual_token_reserves
+            real_token_reserves
         );
 
         let purchase_amount = 100;
@@ -201,16 +253,16 @@ mod tests {
         let result = amm.apply_buy(100).unwrap();
         
         assert_eq!(result.token_amount, purchase_amount as u64);
-        assert_eq!(result.sol_amount, 121); 
+        assert_eq!(result.sol_amount, 120); 
         assert_eq!(amm.virtual_token_reserves, virtual_token_reserves - purchase_amount);
         assert_eq!(amm.real_token_reserves, real_token_reserves - purchase_amount);
-        assert_eq!(amm.virtual_sol_reserves, 721);
-        assert_eq!(amm.real_sol_reserves, 621);
+        assert_eq!(amm.virtual_sol_reserves, 720);
+        assert_eq!(amm.real_sol_reserves, 620);
     }
 
     #[test]
     fn test_get_buy_price() {
-        let amm = AMM::new(1000, 1000, 500, 500, 1000);
+        let amm = AMM::new(1000, 1000, 500, 500);
         
         assert_eq!(amm.get_buy_price(0), None);
         
@@ -220,4 +272,70 @@ mod tests {
         // Edge case: very large token amount
         assert_eq!(amm.get_buy_price(2000), None); 
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_rounding_favours_the_curve() {
+        let amm = AMM::new(1000, 1000, 500, 500);
+
+        // 1000 * 1000 / 997 = 1003.009..., the buyer pays the fraction
+        assert_eq!(amm.get_buy_price(3), Some(4));
+
+        // 1000 * 3 / 1003 = 2.99..., the seller loses the fraction
+        let mut amm = AMM::new(1000, 1000, 500, 500);
+        assert_eq!(amm.apply_sell(3).unwrap().sol_amount, 2);
+        assert!(amm.virtual_sol_reserves * amm.virtual_token_reserves >= 1000 * 1000);
+    }
+
+    #[test]
+    fn test_get_tokens_to_raise() {
+        let amm = AMM::new(1000, 1000, 500, 500);
+
+        assert_eq!(amm.get_tokens_to_raise(0), Some(0));
+
+        let tokens = amm.get_tokens_to_raise(112).unwrap();
+        assert_eq!(tokens, 100);
+        assert!(amm.get_buy_price(tokens).unwrap() >= 112);
+        assert!(amm.get_buy_price(tokens - 1).unwrap() < 112);
+
+        // raising more than the virtual reserves can pay for
+        assert_eq!(amm.get_tokens_to_raise(u64::MAX as u128), None);
+    }
+
+    #[test]
+    fn test_get_tokens_to_raise_mainnet_scale() {
+        let amm = AMM::new(30_000_000_000, 1_073_000_000_000_000, 0, 793_100_000_000_000);
+
+        let sol = 85_000_000_000;
+        let tokens = amm.get_tokens_to_raise(sol).unwrap();
+        assert!(amm.get_buy_price(tokens).unwrap() >= sol);
+        assert!(amm.get_buy_price(tokens - 1).unwrap() < sol);
+    }
+
+    #[test]
+    fn test_get_market_cap() {
+        let amm = AMM::new(30_000_000_000, 1_073_000_000_000_000, 0, 793_100_000_000_000);
+
+        assert_eq!(amm.get_market_cap(1_000_000_000_000_000), Some(27_958_993_476));
+    }
+
+    #[test]
+    fn test_get_tokens_to_market_cap() {
+        let mut amm = AMM::new(30_000_000_000, 1_073_000_000_000_000, 0, 793_100_000_000_000);
+        let supply = 1_000_000_000_000_000;
+        let target = 4 * amm.get_market_cap(supply).unwrap();
+
+        // already above the target
+        assert_eq!(amm.get_tokens_to_market_cap(1, supply), Some(0));
+
+        // quadrupling the market cap takes roughly half the virtual tokens
+        let tokens = amm.get_tokens_to_market_cap(target, supply).unwrap();
+        assert_eq!(tokens, 536_499_999_995_494);
+
+        let mut short = AMM::new(30_000_000_000, 1_073_000_000_000_000, 0, 793_100_000_000_000);
+        short.apply_buy(tokens - 1).unwrap();
+        assert!(short.get_market_cap(supply).unwrap() < target);
+
+        amm.apply_buy(tokens).unwrap();
+        assert!(amm.get_market_cap(supply).unwrap() >= target);
+    }
+}
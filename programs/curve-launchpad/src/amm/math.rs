@@ -0,0 +1,56 @@
+//! `a * b / c` with the rounding direction made explicit. The curve always
+//! rounds in its own favour: what a trader pays rounds up, what a trader
+//! receives rounds down.
+
+/// `a * b / c` rounded down, `None` on overflow or division by zero.
+pub fn mul_div_floor(a: u128, b: u128, c: u128) -> Option<u128> {
+    a.checked_mul(b)?.checked_div(c)
+}
+
+/// `a * b / c` rounded up, `None` on overflow or division by zero.
+pub fn mul_div_ceil(a: u128, b: u128, c: u128) -> Option<u128> {
+    let product = a.checked_mul(b)?;
+    let quotient = product.checked_div(c)?;
+
+    if product % c == 0 {
+        Some(quotient)
+    } else {
+        quotient.checked_add(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mul_div_floor() {
+        assert_eq!(mul_div_floor(10, 10, 3), Some(33));
+        assert_eq!(mul_div_floor(10, 9, 3), Some(30));
+        assert_eq!(mul_div_floor(0, 9, 3), Some(0));
+        assert_eq!(mul_div_floor(1, 1, 0), None);
+        assert_eq!(mul_div_floor(u128::MAX, 2, 2), None);
+    }
+
+    #[test]
+    fn test_mul_div_ceil() {
+        assert_eq!(mul_div_ceil(10, 10, 3), Some(34));
+        assert_eq!(mul_div_ceil(10, 9, 3), Some(30));
+        assert_eq!(mul_div_ceil(0, 9, 3), Some(0));
+        assert_eq!(mul_div_ceil(1, 1, 0), None);
+        assert_eq!(mul_div_ceil(u128::MAX, 2, 2), None);
+        assert_eq!(mul_div_ceil(u128::MAX, 1, 2), Some(u128::MAX / 2 + 1));
+    }
+
+    #[test]
+    fn test_floor_and_ceil_bracket_the_exact_value() {
+        for (a, b, c) in [(7, 11, 5), (30_000_000_000, 1_073_000_000_000_000, 279_900_000_000_000), (1, 1, 1)] {
+            let floor = mul_div_floor(a, b, c).unwrap();
+            let ceil = mul_div_ceil(a, b, c).unwrap();
+
+            assert!(floor * c <= a * b);
+            assert!(ceil * c >= a * b);
+            assert!(ceil - floor <= 1);
+        }
+    }
+}
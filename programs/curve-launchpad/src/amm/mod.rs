@@ -1,2 +1,4 @@
+#[allow(clippy::module_inception)]
 pub mod amm;
-pub use amm::*;
\ No newline at end of file
+pub mod math;
+pub use amm::*;
@@ -1,8 +1,8 @@
 use anchor_lang::{prelude::*, solana_program::system_instruction};
-use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 
 use crate::{
-    amm, calculate_fee, state::{BondingCurve, Global}, CompleteEvent, CurveLaunchpadError, TradeEvent
+    amm, calculate_fee, state::{BondingCurve, CurveOracle, CurveStatus, Global}, CompleteEvent, CurveLaunchpadError, StatusChangedEvent, TradeEvent
 };
 
 #[event_cpi]
@@ -30,6 +30,13 @@ pub struct Buy<'info> {
     )]
     bonding_curve: Box<Account<'info, BondingCurve>>,
 
+    #[account(
+        mut,
+        seeds = [CurveOracle::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    curve_oracle: Box<Account<'info, CurveOracle>>,
+
     #[account(
         mut,
         associated_token::mint = mint,
@@ -49,7 +56,19 @@ pub struct Buy<'info> {
     token_program: Program<'info, Token>,
 }
 
-pub fn buy(ctx: Context<Buy>, token_amount: u64, max_sol_cost: u64) -> Result<()> {
+/// What a `buy` or `sell` executed, returned so programs calling them through CPI don't have to
+/// diff balances.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TradeResult {
+    /// Tokens bought or sold, a buy that graduates the curve is only partially filled.
+    pub token_amount: u64,
+    /// SOL paid into or taken out of the curve, before the fee.
+    pub sol_amount: u64,
+    /// Paid on top of `sol_amount` for a buy, taken out of it for a sell.
+    pub fee: u64,
+}
+
+pub fn buy(ctx: Context<Buy>, token_amount: u64, max_sol_cost: u64) -> Result<TradeResult> {
     require!(
         ctx.accounts.global.initialized,
         CurveLaunchpadError::NotInitialized
@@ -61,28 +80,61 @@ pub fn buy(ctx: Context<Buy>, token_amount: u64, max_sol_cost: u64) -> Result<()
         CurveLaunchpadError::InvalidFeeRecipient,
     );
 
-    //bonding curve has enough tokens
+    let now = Clock::get()?.unix_timestamp;
+
+    //a scheduled curve opens with the first buy after its start time
+    if ctx.accounts.bonding_curve.status == CurveStatus::Scheduled
+        && now >= ctx.accounts.bonding_curve.start_time
+    {
+        let previous_status = ctx.accounts.bonding_curve.transition(CurveStatus::Trading)?;
+
+        emit_cpi!(StatusChangedEvent {
+            mint: *ctx.accounts.mint.to_account_info().key,
+            bonding_curve: *ctx.accounts.bonding_curve.to_account_info().key,
+            previous_status,
+            status: CurveStatus::Trading,
+            timestamp: now,
+        });
+    }
+
+    //past its graduation deadline the curve only redeems
     require!(
-        ctx.accounts.bonding_curve.real_token_reserves >= token_amount,
-        CurveLaunchpadError::InsufficientTokens,
+        !ctx.accounts.bonding_curve.missed_deadline(now),
+        CurveLaunchpadError::BondingCurveRefunding,
     );
 
-    require!(token_amount > 0, CurveLaunchpadError::MinBuy,);
+    ctx.accounts.bonding_curve.require_status(&[CurveStatus::Trading])?;
 
-    let targe_token_amount = if ctx.accounts.bonding_curve_token_account.amount < token_amount {
-        ctx.accounts.bonding_curve_token_account.amount
-    } else {
-        token_amount
-    };
+    require!(token_amount > 0, CurveLaunchpadError::MinBuy,);
 
     let mut amm = amm::amm::AMM::new(
         ctx.accounts.bonding_curve.virtual_sol_reserves as u128,
         ctx.accounts.bonding_curve.virtual_token_reserves as u128,
         ctx.accounts.bonding_curve.real_sol_reserves as u128,
         ctx.accounts.bonding_curve.real_token_reserves as u128,
-        ctx.accounts.global.initial_virtual_token_reserves as u128,
     );
 
+    //a curve with a graduation target stops selling at the target, the final buy is partially filled
+    //and the user is only charged for the filled amount
+    let tokens_to_graduate = ctx.accounts.bonding_curve.tokens_to_graduate(&amm);
+
+    let fill_amount = match tokens_to_graduate {
+        Some(tokens) if tokens < token_amount as u128 => tokens as u64,
+        _ => token_amount,
+    };
+
+    //bonding curve has enough tokens
+    require!(
+        ctx.accounts.bonding_curve.real_token_reserves >= fill_amount,
+        CurveLaunchpadError::InsufficientTokens,
+    );
+
+    let targe_token_amount = if ctx.accounts.bonding_curve_token_account.amount < fill_amount {
+        ctx.accounts.bonding_curve_token_account.amount
+    } else {
+        fill_amount
+    };
+
     let buy_result = amm.apply_buy(targe_token_amount as u128).unwrap();
     let fee = calculate_fee(buy_result.sol_amount, ctx.accounts.global.fee_basis_points);
     let buy_amount_with_fee = buy_result.sol_amount + fee;
@@ -145,13 +197,14 @@ pub fn buy(ctx: Context<Buy>, token_amount: u64, max_sol_cost: u64) -> Result<()
     ]];
 
 
-    token::mint_to(
+    //transfer tokens to user from the bonding curve
+    token::transfer(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            MintTo {
-                mint: ctx.accounts.mint.to_account_info().clone(),
-                to: ctx.accounts.user_token_account.to_account_info().clone(),
-                authority: ctx.accounts.bonding_curve.to_account_info().clone(),
+            Transfer {
+                from: ctx.accounts.bonding_curve_token_account.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.bonding_curve.to_account_info(),
             },
             &signer,
         ),
@@ -165,32 +218,48 @@ pub fn buy(ctx: Context<Buy>, token_amount: u64, max_sol_cost: u64) -> Result<()
     bonding_curve.virtual_token_reserves = amm.virtual_token_reserves as u64;
     bonding_curve.virtual_sol_reserves = amm.virtual_sol_reserves as u64;
 
+    ctx.accounts.curve_oracle.update(bonding_curve.spot_price(), now);
+
     emit_cpi!(TradeEvent {
         mint: *ctx.accounts.mint.to_account_info().key,
         sol_amount: buy_result.sol_amount,
         token_amount: buy_result.token_amount,
         is_buy: true,
         user: *ctx.accounts.user.to_account_info().key,
-        timestamp: Clock::get()?.unix_timestamp,
+        timestamp: now,
         virtual_sol_reserves: bonding_curve.virtual_sol_reserves,
         virtual_token_reserves: bonding_curve.virtual_token_reserves,
         real_sol_reserves: bonding_curve.real_sol_reserves,
         real_token_reserves: bonding_curve.real_token_reserves,
     });
 
-    if bonding_curve.real_token_reserves == 0 {
-        bonding_curve.complete = true;
+    let reached_target = tokens_to_graduate.is_some_and(|tokens| buy_result.token_amount as u128 >= tokens);
+
+    if reached_target || bonding_curve.real_token_reserves == 0 {
+        let previous_status = bonding_curve.transition(CurveStatus::Complete)?;
+
+        emit_cpi!(StatusChangedEvent {
+            mint: *ctx.accounts.mint.to_account_info().key,
+            bonding_curve: *ctx.accounts.bonding_curve.to_account_info().key,
+            previous_status,
+            status: CurveStatus::Complete,
+            timestamp: now,
+        });
 
         emit_cpi!(CompleteEvent {
             user: *ctx.accounts.user.to_account_info().key,
             mint: *ctx.accounts.mint.to_account_info().key,
             bonding_curve: *ctx.accounts.bonding_curve.to_account_info().key,
-            timestamp: Clock::get()?.unix_timestamp,
+            timestamp: now,
         });
     }
 
     msg!("bonding_curve: {:?}", amm);
 
-    Ok(())
+    Ok(TradeResult {
+        token_amount: buy_result.token_amount,
+        sol_amount: buy_result.sol_amount,
+        fee,
+    })
 }
 
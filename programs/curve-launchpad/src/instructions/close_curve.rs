@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount};
+
+use crate::{
+    state::{BondingCurve, CurveOracle, CurveStatus, Global, GraduatedCurve}, CloseCurveEvent, CurveLaunchpadError
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseCurve<'info> {
+    #[account(mut)]
+    user: Signer<'info>,
+
+    /// CHECK: Using bonding curve state to validate creator account
+    #[account(mut)]
+    creator: AccountInfo<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [CurveOracle::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    curve_oracle: Box<Account<'info, CurveOracle>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+    )]
+    bonding_curve_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + GraduatedCurve::INIT_SPACE,
+        seeds = [GraduatedCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    graduated_curve: Box<Account<'info, GraduatedCurve>>,
+
+    system_program: Program<'info, System>,
+
+    token_program: Program<'info, Token>,
+}
+
+pub fn close_curve(ctx: Context<CloseCurve>) -> Result<()> {
+    require!(
+        ctx.accounts.global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //only curves whose liquidity has been withdrawn can be closed
+    ctx.accounts.bonding_curve.require_status(&[CurveStatus::Migrated])?;
+
+    //rent goes back to whoever paid it in create
+    require!(
+        ctx.accounts.creator.key == &ctx.accounts.bonding_curve.creator,
+        CurveLaunchpadError::InvalidCreator,
+    );
+
+    require!(
+        ctx.accounts.bonding_curve_token_account.amount == 0,
+        CurveLaunchpadError::BondingCurveNotEmpty,
+    );
+
+    let lamports = ctx.accounts.bonding_curve.get_lamports()
+        + ctx.accounts.curve_oracle.get_lamports()
+        + ctx.accounts.bonding_curve_token_account.get_lamports();
+
+    let signer: [&[&[u8]]; 1] = [&[
+        BondingCurve::SEED_PREFIX,
+        ctx.accounts.mint.to_account_info().key.as_ref(),
+        &[ctx.bumps.bonding_curve],
+    ]];
+
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.bonding_curve_token_account.to_account_info(),
+            destination: ctx.accounts.creator.to_account_info(),
+            authority: ctx.accounts.bonding_curve.to_account_info(),
+        },
+        &signer,
+    ))?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let bonding_curve = &ctx.accounts.bonding_curve;
+
+    //the bonding curve and its oracle are closed to the creator by anchor once this returns
+    let graduated_curve = &mut ctx.accounts.graduated_curve;
+    graduated_curve.mint = *ctx.accounts.mint.to_account_info().key;
+    graduated_curve.creator = bonding_curve.creator;
+    graduated_curve.virtual_sol_reserves = bonding_curve.virtual_sol_reserves;
+    graduated_curve.virtual_token_reserves = bonding_curve.virtual_token_reserves;
+    graduated_curve.real_sol_reserves = bonding_curve.real_sol_reserves;
+    graduated_curve.token_total_supply = bonding_curve.token_total_supply;
+    graduated_curve.closed_at = now;
+
+    emit_cpi!(CloseCurveEvent {
+        mint: *ctx.accounts.mint.to_account_info().key,
+        bonding_curve: *ctx.accounts.bonding_curve.to_account_info().key,
+        creator: *ctx.accounts.creator.key,
+        lamports,
+        timestamp: now,
+    });
+
+    Ok(())
+}
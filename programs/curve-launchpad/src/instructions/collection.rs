@@ -0,0 +1,212 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{
+        create_master_edition_v3, create_metadata_accounts_v3, mpl_token_metadata::types::DataV2,
+        CreateMasterEditionV3, CreateMetadataAccountsV3, Metadata as Metaplex,
+    },
+    token::{mint_to, Mint, MintTo, Token, TokenAccount},
+};
+
+use crate::{state::Global, validate_metadata, CurveLaunchpadError};
+
+pub const COLLECTION_MINT_SEED: &[u8; 15] = b"collection-mint";
+
+/// Creates the launchpad collection for a deployment initialized before there was one.
+#[derive(Accounts)]
+pub struct InitCollection<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    /// CHECK: Using seed to validate mint_authority account
+    #[account(
+        seeds=[b"mint-authority"],
+        bump,
+    )]
+    mint_authority: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [COLLECTION_MINT_SEED],
+        bump,
+        mint::decimals = 0,
+        mint::authority = mint_authority,
+        mint::freeze_authority = mint_authority,
+    )]
+    collection_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = collection_mint,
+        associated_token::authority = mint_authority,
+    )]
+    collection_token_account: Box<Account<'info, TokenAccount>>,
+
+    ///CHECK: Using seed to validate metadata account
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key.as_ref(),
+            collection_mint.to_account_info().key.as_ref()
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump,
+    )]
+    collection_metadata: AccountInfo<'info>,
+
+    ///CHECK: Using seed to validate master edition account
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key.as_ref(),
+            collection_mint.to_account_info().key.as_ref(),
+            b"edition"
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump,
+    )]
+    collection_master_edition: AccountInfo<'info>,
+
+    system_program: Program<'info, System>,
+
+    token_program: Program<'info, Token>,
+
+    associated_token_program: Program<'info, AssociatedToken>,
+
+    token_metadata_program: Program<'info, Metaplex>,
+
+    rent: Sysvar<'info, Rent>,
+}
+
+pub fn init_collection(ctx: Context<InitCollection>, name: String, symbol: String, uri: String) -> Result<()> {
+    //confirm program is initialized
+    require!(
+        ctx.accounts.global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the authority
+    require!(
+        ctx.accounts.global.authority == *ctx.accounts.authority.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    let accounts = CollectionAccounts {
+        payer: ctx.accounts.authority.to_account_info(),
+        mint_authority: ctx.accounts.mint_authority.to_account_info(),
+        mint_authority_bump: ctx.bumps.mint_authority,
+        collection_mint: ctx.accounts.collection_mint.to_account_info(),
+        collection_token_account: ctx.accounts.collection_token_account.to_account_info(),
+        collection_metadata: ctx.accounts.collection_metadata.to_account_info(),
+        collection_master_edition: ctx.accounts.collection_master_edition.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+    };
+
+    mint_collection(accounts, CollectionMetadata { name, symbol, uri })
+}
+
+//accounts minting the collection takes, shared by initialize and init_collection
+pub(crate) struct CollectionAccounts<'info> {
+    pub payer: AccountInfo<'info>,
+    pub mint_authority: AccountInfo<'info>,
+    pub mint_authority_bump: u8,
+    pub collection_mint: AccountInfo<'info>,
+    pub collection_token_account: AccountInfo<'info>,
+    pub collection_metadata: AccountInfo<'info>,
+    pub collection_master_edition: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub token_metadata_program: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>,
+}
+
+//name, symbol and uri of the collection NFT, checked like a launched token's
+pub(crate) struct CollectionMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+//mints the one collection NFT to the mint authority and gives it metadata and a
+//master edition, which takes over the mint so no more can be minted
+pub(crate) fn mint_collection(accounts: CollectionAccounts, metadata: CollectionMetadata) -> Result<()> {
+    validate_metadata(&metadata.name, &metadata.symbol, &metadata.uri)?;
+
+    let seeds = &["mint-authority".as_bytes(), &[accounts.mint_authority_bump]];
+    let signer = [&seeds[..]];
+
+    mint_to(
+        CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            MintTo {
+                authority: accounts.mint_authority.clone(),
+                to: accounts.collection_token_account.clone(),
+                mint: accounts.collection_mint.clone(),
+            },
+            &signer,
+        ),
+        1,
+    )?;
+
+    create_metadata_accounts_v3(
+        CpiContext::new_with_signer(
+            accounts.token_metadata_program.clone(),
+            CreateMetadataAccountsV3 {
+                payer: accounts.payer.clone(),
+                update_authority: accounts.mint_authority.clone(),
+                mint: accounts.collection_mint.clone(),
+                metadata: accounts.collection_metadata.clone(),
+                mint_authority: accounts.mint_authority.clone(),
+                system_program: accounts.system_program.clone(),
+                rent: accounts.rent.clone(),
+            },
+            &signer,
+        ),
+        DataV2 {
+            name: metadata.name,
+            symbol: metadata.symbol,
+            uri: metadata.uri,
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        },
+        true,
+        true,
+        None,
+    )?;
+
+    create_master_edition_v3(
+        CpiContext::new_with_signer(
+            accounts.token_metadata_program.clone(),
+            CreateMasterEditionV3 {
+                edition: accounts.collection_master_edition,
+                mint: accounts.collection_mint,
+                update_authority: accounts.mint_authority.clone(),
+                mint_authority: accounts.mint_authority,
+                payer: accounts.payer,
+                metadata: accounts.collection_metadata,
+                token_program: accounts.token_program,
+                system_program: accounts.system_program,
+                rent: accounts.rent,
+            },
+            &signer,
+        ),
+        Some(0),
+    )?;
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
 pub const DEFAULT_DECIMALS: u32 = 6;
-pub const DEFAULT_TOKEN_LAMPORTS: u64 = (10 as u64).pow(DEFAULT_DECIMALS);
-pub const DEFAULT_TOKEN_SUPPLY: u64 = 1_000_000_000 * DEFAULT_TOKEN_LAMPORTS;
\ No newline at end of file
+pub const DEFAULT_TOKEN_LAMPORTS: u64 = 10_u64.pow(DEFAULT_DECIMALS);
+pub const DEFAULT_TOKEN_SUPPLY: u64 = 1_000_000_000 * DEFAULT_TOKEN_LAMPORTS;
+pub const MAX_DECIMALS: u8 = 9;
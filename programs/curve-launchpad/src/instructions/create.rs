@@ -1,34 +1,35 @@
 use crate::{
-    state::{BondingCurve, Global}, CreateEvent, CurveLaunchpadError, DEFAULT_DECIMALS
+    amm, state::{BondingCurve, CreatorStats, CurveOracle, CurveStatus, Global, GraduationTarget, MintVault}, CreateEvent, CurveLaunchpadError, validate_metadata, COLLECTION_MINT_SEED, DEFAULT_DECIMALS, MAX_DECIMALS
+};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program_pack::Pack, system_instruction},
+    system_program::{create_account, CreateAccount},
 };
-use anchor_lang::{prelude::*, solana_program::program::invoke};
 use anchor_spl::{
-    associated_token::{spl_associated_token_account::instruction::create_associated_token_account, AssociatedToken},
+    associated_token::{self, create_idempotent, AssociatedToken},
     metadata::{
-        create_metadata_accounts_v3, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3,
-        Metadata as Metaplex,
+        create_metadata_accounts_v3, mpl_token_metadata::types::{Collection, Creator, DataV2}, sign_metadata,
+        verify_collection, CreateMetadataAccountsV3, Metadata as Metaplex, SignMetadata, VerifyCollection,
     },
     token::{
-        mint_to,  Mint, MintTo, Token, TokenAccount,
+        initialize_mint2, mint_to, spl_token, InitializeMint2, Mint, MintTo, Token,
     },
 };
-declare_program!(dynamic_amm);
+
 #[event_cpi]
 #[derive(Accounts)]
 pub struct Create<'info> {
-    #[account(
-        init,
-        payer = creator,
-        mint::decimals = DEFAULT_DECIMALS as u8,
-        mint::authority = mint_authority,
-    )]
-    mint: Account<'info, Mint>,
+    /// CHECK: A fresh keypair created below, or a mint queued in `mint_vault`
+    #[account(mut)]
+    mint: AccountInfo<'info>,
 
     #[account(mut)]
     creator: Signer<'info>,
 
-    /// CHECK: Using seed to validate mint_authority account
+    /// CHECK: Using seed to validate mint_authority account, writable as the collection authority
     #[account(
+        mut,
         seeds=[b"mint-authority"],
         bump,
     )]
@@ -44,12 +45,17 @@ pub struct Create<'info> {
     bonding_curve: Box<Account<'info, BondingCurve>>,
 
     #[account(
-        init_if_needed,
+        init,
         payer = creator,
-        associated_token::mint = mint,
-        associated_token::authority = bonding_curve,
+        seeds = [CurveOracle::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+        space = 8 + CurveOracle::INIT_SPACE,
     )]
-    bonding_curve_token_account: Box<Account<'info, TokenAccount>>,
+    curve_oracle: Box<Account<'info, CurveOracle>>,
+
+    /// CHECK: Created below once the mint exists, the associated token program checks the address
+    #[account(mut)]
+    bonding_curve_token_account: AccountInfo<'info>,
 
     #[account(
         seeds = [Global::SEED_PREFIX],
@@ -57,6 +63,19 @@ pub struct Create<'info> {
     )]
     global: Box<Account<'info, Global>>,
 
+    /// CHECK: Using global state to validate fee_recipient account
+    #[account(mut)]
+    fee_recipient: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + CreatorStats::INIT_SPACE,
+        seeds = [CreatorStats::SEED_PREFIX, creator.key.as_ref()],
+        bump,
+    )]
+    creator_stats: Box<Account<'info, CreatorStats>>,
+
     ///CHECK: Using seed to validate metadata account
     #[account(
         mut,
@@ -70,6 +89,37 @@ pub struct Create<'info> {
     )]
     metadata: AccountInfo<'info>,
 
+    #[account(
+        seeds = [COLLECTION_MINT_SEED],
+        bump,
+    )]
+    collection_mint: Box<Account<'info, Mint>>,
+
+    ///CHECK: Using seed to validate collection metadata account
+    #[account(
+        seeds = [
+            b"metadata",
+            token_metadata_program.key.as_ref(),
+            collection_mint.to_account_info().key.as_ref()
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump,
+    )]
+    collection_metadata: AccountInfo<'info>,
+
+    ///CHECK: Using seed to validate collection master edition account
+    #[account(
+        seeds = [
+            b"metadata",
+            token_metadata_program.key.as_ref(),
+            collection_mint.to_account_info().key.as_ref(),
+            b"edition"
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump,
+    )]
+    collection_master_edition: AccountInfo<'info>,
+
     system_program: Program<'info, System>,
 
     token_program: Program<'info, Token>,
@@ -79,28 +129,195 @@ pub struct Create<'info> {
     token_metadata_program: Program<'info, Metaplex>,
 
     rent: Sysvar<'info, Rent>,
+
+    /// Launch a mint from the vault instead of a fresh keypair.
+    #[account(
+        mut,
+        seeds = [MintVault::SEED_PREFIX],
+        bump,
+    )]
+    mint_vault: Option<Box<Account<'info, MintVault>>>,
 }
 
 
-pub fn create(ctx: Context<Create>, name: String, symbol: String, uri: String) -> Result<()> {
+pub fn create(
+    ctx: Context<Create>,
+    name: String,
+    symbol: String,
+    uri: String,
+    graduation_deadline: Option<i64>,
+    graduation_target: Option<GraduationTarget>,
+    start_time: Option<i64>,
+    verify_creator: bool,
+    decimals: Option<u8>,
+    token_supply: Option<u64>,
+) -> Result<()> {
     //confirm program is initialized
     require!(
         ctx.accounts.global.initialized,
         CurveLaunchpadError::NotInitialized
     );
 
+    validate_metadata(&name, &symbol, &uri)?;
+
+    //the global reserves are a template, scaled to this mint so a whole token starts at the same price
+    let decimals = decimals.unwrap_or(DEFAULT_DECIMALS as u8);
+    require!(decimals <= MAX_DECIMALS, CurveLaunchpadError::InvalidDecimals);
+
+    let reserves = ctx
+        .accounts
+        .global
+        .scaled_reserves(decimals, token_supply)
+        .ok_or(CurveLaunchpadError::InvalidTokenSupply)?;
+
+    let mint = *ctx.accounts.mint.key;
+    require!(
+        ctx.accounts.global.is_vanity_mint(&mint),
+        CurveLaunchpadError::RequireVanityMint
+    );
+
+    //a curve without a future start time trades immediately
+    let now = Clock::get()?.unix_timestamp;
+    let start_time = start_time.unwrap_or(now).max(now);
+
+    if let Some(deadline) = graduation_deadline {
+        require!(
+            deadline > start_time,
+            CurveLaunchpadError::InvalidGraduationDeadline
+        );
+    }
+
+    //anti-spam controls, all off unless the authority sets them
+    let global = &ctx.accounts.global;
+
+    require!(
+        ctx.accounts.fee_recipient.key == &global.fee_recipient,
+        CurveLaunchpadError::InvalidFeeRecipient,
+    );
+
+    require!(
+        !global.creator_allowlist || ctx.accounts.creator_stats.allowed,
+        CurveLaunchpadError::CreatorNotAllowed
+    );
+
+    let creator_stats = &mut ctx.accounts.creator_stats;
+    creator_stats.version = CreatorStats::CURRENT_VERSION;
+    creator_stats.creator = *ctx.accounts.creator.to_account_info().key;
+    require!(
+        creator_stats.record_launch(now, global.creator_rate_limit, global.creator_rate_limit_window),
+        CurveLaunchpadError::CreatorRateLimited
+    );
+
+    if global.creation_fee_lamports > 0 {
+        let transfer_instruction = system_instruction::transfer(
+            ctx.accounts.creator.key,
+            ctx.accounts.fee_recipient.key,
+            global.creation_fee_lamports,
+        );
+
+        anchor_lang::solana_program::program::invoke(
+            &transfer_instruction,
+            &[
+                ctx.accounts.creator.to_account_info(),
+                ctx.accounts.fee_recipient.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+    }
+
+    //the target has to be reachable by buying from the starting curve
+    if let Some(target) = graduation_target {
+        let amm = amm::amm::AMM::new(
+            reserves.virtual_sol_reserves as u128,
+            reserves.virtual_token_reserves as u128,
+            0,
+            reserves.real_token_reserves as u128,
+        );
+        let tokens_to_target = match target {
+            GraduationTarget::SolRaised(lamports) => amm.get_tokens_to_raise(lamports as u128),
+            GraduationTarget::MarketCap(lamports) => {
+                amm.get_tokens_to_market_cap(lamports as u128, reserves.token_supply as u128)
+            }
+        };
+        require!(
+            matches!(tokens_to_target, Some(tokens) if tokens > 0),
+            CurveLaunchpadError::InvalidGraduationTarget
+        );
+    }
+
+    //vault mints were created when they were deposited, a fresh keypair is created here
+    match ctx.accounts.mint_vault.as_mut() {
+        Some(mint_vault) => {
+            require!(mint_vault.take(&mint), CurveLaunchpadError::InvalidVaultMint);
+
+            let vault_mint = spl_token::state::Mint::unpack(&ctx.accounts.mint.try_borrow_data()?)?;
+            require!(vault_mint.decimals == decimals, CurveLaunchpadError::InvalidDecimals);
+        }
+        None => {
+            create_account(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    CreateAccount {
+                        from: ctx.accounts.creator.to_account_info(),
+                        to: ctx.accounts.mint.to_account_info(),
+                    },
+                ),
+                Rent::get()?.minimum_balance(Mint::LEN),
+                Mint::LEN as u64,
+                ctx.accounts.token_program.key,
+            )?;
+
+            initialize_mint2(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    InitializeMint2 {
+                        mint: ctx.accounts.mint.to_account_info(),
+                    },
+                ),
+                decimals,
+                ctx.accounts.mint_authority.key,
+                None,
+            )?;
+        }
+    }
+
+    create_idempotent(CpiContext::new(
+        ctx.accounts.associated_token_program.to_account_info(),
+        associated_token::Create {
+            payer: ctx.accounts.creator.to_account_info(),
+            associated_token: ctx.accounts.bonding_curve_token_account.to_account_info(),
+            authority: ctx.accounts.bonding_curve.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+    ))?;
+
     msg!("create::BondingCurve::get_lamports: {:?}", &ctx.accounts.bonding_curve.get_lamports());
 
     let seeds = &["mint-authority".as_bytes(), &[ctx.bumps.mint_authority]];
     let signer = [&seeds[..]];
 
+    //the launcher is listed unverified, then verifies itself by signing below
+    let creators = verify_creator.then(|| {
+        vec![Creator {
+            address: *ctx.accounts.creator.to_account_info().key,
+            verified: false,
+            share: 100,
+        }]
+    });
+
     let token_data: DataV2 = DataV2 {
         name: name.clone(),
         symbol: symbol.clone(),
         uri: uri.clone(),
         seller_fee_basis_points: 0,
-        creators: None,
-        collection: None,
+        creators,
+        //verified below
+        collection: Some(Collection {
+            verified: false,
+            key: *ctx.accounts.collection_mint.to_account_info().key,
+        }),
         uses: None,
     };
 
@@ -118,7 +335,34 @@ pub fn create(ctx: Context<Create>, name: String, symbol: String, uri: String) -
         &signer,
     );
 
-    create_metadata_accounts_v3(metadata_ctx, token_data, false, true, None)?;
+    //mutable so the creator can update it until the curve graduates
+    create_metadata_accounts_v3(metadata_ctx, token_data, true, true, None)?;
+
+    verify_collection(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            VerifyCollection {
+                payer: ctx.accounts.creator.to_account_info(),
+                metadata: ctx.accounts.metadata.to_account_info(),
+                collection_authority: ctx.accounts.mint_authority.to_account_info(),
+                collection_mint: ctx.accounts.collection_mint.to_account_info(),
+                collection_metadata: ctx.accounts.collection_metadata.to_account_info(),
+                collection_master_edition: ctx.accounts.collection_master_edition.to_account_info(),
+            },
+            &signer,
+        ),
+        None,
+    )?;
+
+    if verify_creator {
+        sign_metadata(CpiContext::new(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            SignMetadata {
+                creator: ctx.accounts.creator.to_account_info(),
+                metadata: ctx.accounts.metadata.to_account_info(),
+            },
+        ))?;
+    }
 
     //mint tokens to bonding_curve_token_account
     mint_to(
@@ -131,27 +375,31 @@ pub fn create(ctx: Context<Create>, name: String, symbol: String, uri: String) -
             },
             &signer,
         ),
-        ctx.accounts.global.initial_token_supply,
+        reserves.token_supply,
     )?;
 
-    //remove mint_authority
-    /*let cpi_context = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        token::SetAuthority {
-            current_authority: ctx.accounts.mint_authority.to_account_info(),
-            account_or_mint: ctx.accounts.mint.to_account_info(),
-        },
-        &signer,
-    );
-    token::set_authority(cpi_context, AuthorityType::MintTokens, None)?;
-    */
+    //the mint authority stays until withdraw graduates the curve, which revokes it
     let bonding_curve = &mut ctx.accounts.bonding_curve;
-    bonding_curve.virtual_sol_reserves = ctx.accounts.global.initial_virtual_sol_reserves;
-    bonding_curve.virtual_token_reserves = ctx.accounts.global.initial_virtual_token_reserves;
+    bonding_curve.version = BondingCurve::CURRENT_VERSION;
+    bonding_curve.creator = *ctx.accounts.creator.to_account_info().key;
+    bonding_curve.virtual_sol_reserves = reserves.virtual_sol_reserves;
+    bonding_curve.virtual_token_reserves = reserves.virtual_token_reserves;
     bonding_curve.real_sol_reserves = 0;
-    bonding_curve.real_token_reserves = ctx.accounts.global.initial_real_token_reserves;
-    bonding_curve.token_total_supply = ctx.accounts.global.initial_token_supply;
-    bonding_curve.complete = false;
+    bonding_curve.real_token_reserves = reserves.real_token_reserves;
+    bonding_curve.token_total_supply = reserves.token_supply;
+    bonding_curve.status = if start_time > now {
+        CurveStatus::Scheduled
+    } else {
+        CurveStatus::Trading
+    };
+    bonding_curve.start_time = start_time;
+    bonding_curve.graduation_deadline = graduation_deadline;
+    bonding_curve.graduation_target = graduation_target;
+
+    let price = bonding_curve.spot_price();
+    ctx.accounts
+        .curve_oracle
+        .init(*ctx.accounts.mint.to_account_info().key, price, now);
 
     emit_cpi!(CreateEvent {
         name,
@@ -164,194 +412,3 @@ pub fn create(ctx: Context<Create>, name: String, symbol: String, uri: String) -
 
     Ok(())
 }
-pub fn create_permissionless_constant_product_pool_with_config(ctx: Context<CreatePermissionlessConstantProductPoolWithConfig>) -> Result<()> {
-    let accounts = dynamic_amm::cpi::accounts::InitializePermissionlessConstantProductPoolWithConfig {
-        pool: ctx.accounts.pool.to_account_info(),
-        config: ctx.accounts.config.to_account_info(),
-        lp_mint: ctx.accounts.lp_mint.to_account_info(),
-        token_a_mint: ctx.accounts.token_a_mint.to_account_info(),
-        token_b_mint: ctx.accounts.token_b_mint.to_account_info(),
-        a_vault: ctx.accounts.a_vault.to_account_info(),
-        b_vault: ctx.accounts.b_vault.to_account_info(),
-        a_token_vault: ctx.accounts.a_token_vault.to_account_info(),
-        b_token_vault: ctx.accounts.b_token_vault.to_account_info(),
-        a_vault_lp_mint: ctx.accounts.a_vault_lp_mint.to_account_info(),
-        b_vault_lp_mint: ctx.accounts.b_vault_lp_mint.to_account_info(),
-        a_vault_lp: ctx.accounts.a_vault_lp.to_account_info(),
-        b_vault_lp: ctx.accounts.b_vault_lp.to_account_info(),
-        payer_token_a: ctx.accounts.payer_token_a.to_account_info(),
-        payer_token_b: ctx.accounts.payer_token_b.to_account_info(),
-        payer_pool_lp: ctx.accounts.payer_pool_lp.to_account_info(),
-        protocol_token_a_fee: ctx.accounts.protocol_token_a_fee.to_account_info(),
-        protocol_token_b_fee: ctx.accounts.protocol_token_b_fee.to_account_info(),
-        payer: ctx.accounts.payer.to_account_info(),
-        rent: ctx.accounts.rent.to_account_info(),
-        mint_metadata: ctx.accounts.mint_metadata.to_account_info(),
-        metadata_program: ctx.accounts.metadata_program.to_account_info(),
-        vault_program: ctx.accounts.vault_program.to_account_info(),
-        token_program: ctx.accounts.token_program.to_account_info(),
-        associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
-        system_program: ctx.accounts.system_program.to_account_info(),
-    };
-    let seeds = [BondingCurve::SEED_PREFIX, ctx.accounts.token_b_mint.to_account_info().key.as_ref(), &[ctx.bumps.bonding_curve]];
-    let signer = [&seeds[..]];
-
-    let cpi_context = CpiContext::new_with_signer(ctx.accounts.dynamic_amm_program.to_account_info(), accounts, &signer);
-
-    dynamic_amm::cpi::initialize_permissionless_constant_product_pool_with_config(cpi_context, ctx.accounts.payer_token_a.amount, ctx.accounts.payer_token_b.amount)?;
-    let accounts = dynamic_amm::cpi::accounts::CreateLockEscrow {
-        lock_escrow: ctx.accounts.lock_escrow.to_account_info(),
-        payer: ctx.accounts.payer.to_account_info(),
-        system_program: ctx.accounts.system_program.to_account_info(),
-        pool: ctx.accounts.pool.to_account_info(),
-        owner: ctx.accounts.payer.to_account_info(),
-        lp_mint: ctx.accounts.lp_mint.to_account_info(),
-    };
-    let cpi_context = CpiContext::new_with_signer(ctx.accounts.vault_program.to_account_info(), accounts, &signer);
-    dynamic_amm::cpi::create_lock_escrow(cpi_context)?;
-    let accounts = create_associated_token_account(ctx.accounts.payer.to_account_info().key, ctx.accounts.lock_escrow.to_account_info().key, ctx.accounts.lp_mint.to_account_info().key, ctx.accounts.token_program.to_account_info().key);
-    
-    invoke(&accounts, &
-    [
-        ctx.accounts.payer.to_account_info(),
-        ctx.accounts.lock_escrow.to_account_info(),
-        ctx.accounts.lp_mint.to_account_info(),
-        ctx.accounts.lock_escrow_token_account.to_account_info(),
-        ctx.accounts.token_program.to_account_info(),
-    ]
-    )?;
-
-    let accounts = dynamic_amm::cpi::accounts::Lock {
-        pool: ctx.accounts.pool.to_account_info(),
-        lp_mint: ctx.accounts.lp_mint.to_account_info(),
-        lock_escrow: ctx.accounts.lock_escrow.to_account_info(),
-        owner: ctx.accounts.payer.to_account_info(),
-        source_tokens: ctx.accounts.payer_token_a.to_account_info(),
-        escrow_vault: ctx.accounts.lock_escrow_token_account.to_account_info(),
-        token_program: ctx.accounts.token_program.to_account_info(),
-        a_vault: ctx.accounts.a_vault.to_account_info(),
-        b_vault: ctx.accounts.b_vault.to_account_info(),
-            a_vault_lp: ctx.accounts.a_vault_lp.to_account_info(),
-        b_vault_lp: ctx.accounts.b_vault_lp.to_account_info(),
-        a_vault_lp_mint: ctx.accounts.a_vault_lp_mint.to_account_info(),
-        b_vault_lp_mint: ctx.accounts.b_vault_lp_mint.to_account_info(),
-    };
-    let cpi_context = CpiContext::new_with_signer(ctx.accounts.vault_program.to_account_info(), accounts, &signer);
-    dynamic_amm::cpi::lock(cpi_context, ctx.accounts.payer_token_a.amount)?;
-    Ok(())
-}
-#[derive(Accounts)]
-pub struct CreatePermissionlessConstantProductPoolWithConfig<'info> {
-    #[account(mut)]
-    pub pool: UncheckedAccount<'info>,
-
-    pub config: UncheckedAccount<'info>,
-
-    /// LP token mint of the pool
-    #[account(
-       mut
-    )]
-    pub lp_mint: UncheckedAccount<'info>,
-
-    /// Token A mint of the pool. Eg: USDT
-    pub token_a_mint: Box<Account<'info, Mint>>,
-    /// Token B mint of the pool. Eg: USDC
-    pub token_b_mint: Box<Account<'info, Mint>>,
-
-    #[account(mut)]
-    /// CHECK: Vault account for token A. Token A of the pool will be deposit / withdraw from this vault account.
-    pub a_vault: UncheckedAccount<'info>,
-    #[account(mut)]
-    /// CHECK: Vault account for token B. Token B of the pool will be deposit / withdraw from this vault account.
-    pub b_vault: UncheckedAccount<'info>,
-
-    #[account(mut)]
-    /// Token vault account of vault A
-    pub a_token_vault: UncheckedAccount<'info>,
-    #[account(mut)]
-    /// Token vault account of vault B
-    pub b_token_vault: UncheckedAccount<'info>,
-
-    #[account(mut)]
-    /// LP token mint of vault A
-    pub a_vault_lp_mint: UncheckedAccount<'info>,
-
-    #[account(mut)]
-    /// LP token mint of vault B
-    pub b_vault_lp_mint: UncheckedAccount<'info>,
-    /// LP token account of vault A. Used to receive/burn the vault LP upon deposit/withdraw from the vault.
-    #[account(
-        mut
-    )]
-    pub a_vault_lp: UncheckedAccount<'info>,
-    /// LP token account of vault B. Used to receive/burn vault LP upon deposit/withdraw from the vault.
-    #[account(
-        mut
-    )]
-    pub b_vault_lp: UncheckedAccount<'info>,
-
-    #[account(mut)]
-    /// Payer token account for pool token A mint. Used to bootstrap the pool with initial liquidity.
-    pub payer_token_a: Account<'info, TokenAccount>,
-
-    #[account(mut)]
-    /// Admin token account for pool token B mint. Used to bootstrap the pool with initial liquidity.
-    pub payer_token_b: Account<'info, TokenAccount>,
-
-    /// CHECK: Payer pool LP token account. Used to receive LP during first deposit (initialize pool)
-    #[account(
-        mut
-    )]
-    pub payer_pool_lp: UncheckedAccount<'info>,
-
-    #[account(
-        mut
-    )]
-    /// Protocol fee token account for token A. Used to receive trading fee.
-    pub protocol_token_a_fee: UncheckedAccount<'info>,
-
-    /// Protocol fee token account for token B. Used to receive trading fee.
-    #[account(
-        mut
-    )]
-    pub protocol_token_b_fee: UncheckedAccount<'info>,
-
-    /// Admin account. This account will be the admin of the pool, and the payer for PDA during initialize pool.
-    #[account(mut)]
-    pub payer: Signer<'info>,
-
-    /// Rent account.
-    pub rent: Sysvar<'info, Rent>,
-
-    /// CHECK: LP mint metadata PDA. Metaplex do the checking.
-    #[account(mut)]
-    pub mint_metadata: UncheckedAccount<'info>,
-
-    /// CHECK: Metadata program
-    pub metadata_program: UncheckedAccount<'info>,
-
-    /// CHECK: Vault program. The pool will deposit/withdraw liquidity from the vault.
-    pub vault_program: UncheckedAccount<'info>,
-    /// Token program.
-    pub token_program: Program<'info, Token>,
-    /// Associated token program.
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    /// System program.
-    pub system_program: Program<'info, System>,
-
-    pub dynamic_amm_program: UncheckedAccount<'info>,
-
-    #[account(
-        seeds = [BondingCurve::SEED_PREFIX, token_b_mint.to_account_info().key.as_ref()],
-        bump,
-    )]
-    pub bonding_curve: Box<Account<'info, BondingCurve>>,
-    #[account(
-        mut
-    )]
-    pub lock_escrow: UncheckedAccount<'info>,
-    #[account(
-        mut
-    )]
-    pub lock_escrow_token_account: UncheckedAccount<'info>,
-}
\ No newline at end of file
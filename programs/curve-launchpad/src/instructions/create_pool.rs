@@ -0,0 +1,230 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{
+    dynamic_amm,
+    state::{BondingCurve, CurveStatus, Global},
+    CurveLaunchpadError,
+};
+
+#[derive(Accounts)]
+pub struct CreatePool<'info> {
+    /// CHECK: Pool to create, the AMM checks its address
+    #[account(mut)]
+    pub pool: UncheckedAccount<'info>,
+
+    /// CHECK: AMM config the pool is created with, the AMM checks it
+    pub config: UncheckedAccount<'info>,
+
+    /// CHECK: LP token mint of the pool, created by the AMM
+    #[account(mut)]
+    pub lp_mint: UncheckedAccount<'info>,
+
+    /// Token A mint of the pool, the quote token.
+    pub token_a_mint: Box<Account<'info, Mint>>,
+    /// Token B mint of the pool, the graduated token.
+    pub token_b_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    /// CHECK: Vault account for token A. Token A of the pool will be deposit / withdraw from this vault account.
+    pub a_vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Vault account for token B. Token B of the pool will be deposit / withdraw from this vault account.
+    pub b_vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Token vault account of vault A
+    pub a_token_vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Token vault account of vault B
+    pub b_token_vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: LP token mint of vault A
+    pub a_vault_lp_mint: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: LP token mint of vault B
+    pub b_vault_lp_mint: UncheckedAccount<'info>,
+
+    /// CHECK: LP token account of vault A. Used to receive/burn the vault LP upon deposit/withdraw from the vault.
+    #[account(mut)]
+    pub a_vault_lp: UncheckedAccount<'info>,
+    /// CHECK: LP token account of vault B. Used to receive/burn vault LP upon deposit/withdraw from the vault.
+    #[account(mut)]
+    pub b_vault_lp: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = token_a_mint,
+        token::authority = payer,
+    )]
+    /// Payer token account for pool token A mint. Used to bootstrap the pool with initial liquidity.
+    pub payer_token_a: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = token_b_mint,
+        token::authority = payer,
+    )]
+    /// Payer token account for pool token B mint. Used to bootstrap the pool with initial liquidity.
+    pub payer_token_b: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Payer pool LP token account. Used to receive LP during first deposit (initialize pool)
+    #[account(mut)]
+    pub payer_pool_lp: UncheckedAccount<'info>,
+
+    /// CHECK: Protocol fee token account for token A. Used to receive trading fee.
+    #[account(mut)]
+    pub protocol_token_a_fee: UncheckedAccount<'info>,
+    /// CHECK: Protocol fee token account for token B. Used to receive trading fee.
+    #[account(mut)]
+    pub protocol_token_b_fee: UncheckedAccount<'info>,
+
+    /// The withdraw authority, which seeds the pool with the curve's liquidity and owns the locked LP.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: LP mint metadata PDA. Metaplex do the checking.
+    #[account(mut)]
+    pub mint_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Metadata program
+    pub metadata_program: UncheckedAccount<'info>,
+
+    /// CHECK: Vault program. The pool will deposit/withdraw liquidity from the vault.
+    pub vault_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Using address to validate the AMM program
+    #[account(address = dynamic_amm::ID)]
+    pub dynamic_amm_program: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    pub global: Box<Account<'info, Global>>,
+
+    #[account(
+        seeds = [BondingCurve::SEED_PREFIX, token_b_mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    /// CHECK: Lock escrow of the payer, created by the AMM
+    #[account(mut)]
+    pub lock_escrow: UncheckedAccount<'info>,
+
+    /// CHECK: Associated token account of the lock escrow for the LP mint, created here
+    #[account(mut)]
+    pub lock_escrow_token_account: UncheckedAccount<'info>,
+}
+
+pub fn create_pool(ctx: Context<CreatePool>) -> Result<()> {
+    require!(
+        ctx.accounts.global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    ctx.accounts.bonding_curve.require_status(&[CurveStatus::Migrated])?;
+
+    require!(
+        ctx.accounts.payer.key() == ctx.accounts.global.withdraw_authority,
+        CurveLaunchpadError::InvalidWithdrawAuthority,
+    );
+
+    //seed the pool with everything the withdraw authority took off the curve
+    let accounts = dynamic_amm::cpi::accounts::InitializePermissionlessConstantProductPoolWithConfig {
+        pool: ctx.accounts.pool.to_account_info(),
+        config: ctx.accounts.config.to_account_info(),
+        lp_mint: ctx.accounts.lp_mint.to_account_info(),
+        token_a_mint: ctx.accounts.token_a_mint.to_account_info(),
+        token_b_mint: ctx.accounts.token_b_mint.to_account_info(),
+        a_vault: ctx.accounts.a_vault.to_account_info(),
+        b_vault: ctx.accounts.b_vault.to_account_info(),
+        a_token_vault: ctx.accounts.a_token_vault.to_account_info(),
+        b_token_vault: ctx.accounts.b_token_vault.to_account_info(),
+        a_vault_lp_mint: ctx.accounts.a_vault_lp_mint.to_account_info(),
+        b_vault_lp_mint: ctx.accounts.b_vault_lp_mint.to_account_info(),
+        a_vault_lp: ctx.accounts.a_vault_lp.to_account_info(),
+        b_vault_lp: ctx.accounts.b_vault_lp.to_account_info(),
+        payer_token_a: ctx.accounts.payer_token_a.to_account_info(),
+        payer_token_b: ctx.accounts.payer_token_b.to_account_info(),
+        payer_pool_lp: ctx.accounts.payer_pool_lp.to_account_info(),
+        protocol_token_a_fee: ctx.accounts.protocol_token_a_fee.to_account_info(),
+        protocol_token_b_fee: ctx.accounts.protocol_token_b_fee.to_account_info(),
+        payer: ctx.accounts.payer.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+        mint_metadata: ctx.accounts.mint_metadata.to_account_info(),
+        metadata_program: ctx.accounts.metadata_program.to_account_info(),
+        vault_program: ctx.accounts.vault_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+    };
+    let cpi_context = CpiContext::new(ctx.accounts.dynamic_amm_program.to_account_info(), accounts);
+    dynamic_amm::cpi::initialize_permissionless_constant_product_pool_with_config(
+        cpi_context,
+        ctx.accounts.payer_token_a.amount,
+        ctx.accounts.payer_token_b.amount,
+    )?;
+
+    let accounts = dynamic_amm::cpi::accounts::CreateLockEscrow {
+        pool: ctx.accounts.pool.to_account_info(),
+        lock_escrow: ctx.accounts.lock_escrow.to_account_info(),
+        owner: ctx.accounts.payer.to_account_info(),
+        lp_mint: ctx.accounts.lp_mint.to_account_info(),
+        payer: ctx.accounts.payer.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+    };
+    let cpi_context = CpiContext::new(ctx.accounts.dynamic_amm_program.to_account_info(), accounts);
+    dynamic_amm::cpi::create_lock_escrow(cpi_context)?;
+
+    associated_token::create(CpiContext::new(
+        ctx.accounts.associated_token_program.to_account_info(),
+        associated_token::Create {
+            payer: ctx.accounts.payer.to_account_info(),
+            associated_token: ctx.accounts.lock_escrow_token_account.to_account_info(),
+            authority: ctx.accounts.lock_escrow.to_account_info(),
+            mint: ctx.accounts.lp_mint.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+    ))?;
+
+    //lock all of the LP the first deposit minted
+    let lp_amount = {
+        let data = ctx.accounts.payer_pool_lp.try_borrow_data()?;
+        TokenAccount::try_deserialize(&mut &data[..])?.amount
+    };
+
+    let accounts = dynamic_amm::cpi::accounts::Lock {
+        pool: ctx.accounts.pool.to_account_info(),
+        lp_mint: ctx.accounts.lp_mint.to_account_info(),
+        lock_escrow: ctx.accounts.lock_escrow.to_account_info(),
+        owner: ctx.accounts.payer.to_account_info(),
+        source_tokens: ctx.accounts.payer_pool_lp.to_account_info(),
+        escrow_vault: ctx.accounts.lock_escrow_token_account.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        a_vault: ctx.accounts.a_vault.to_account_info(),
+        b_vault: ctx.accounts.b_vault.to_account_info(),
+        a_vault_lp: ctx.accounts.a_vault_lp.to_account_info(),
+        b_vault_lp: ctx.accounts.b_vault_lp.to_account_info(),
+        a_vault_lp_mint: ctx.accounts.a_vault_lp_mint.to_account_info(),
+        b_vault_lp_mint: ctx.accounts.b_vault_lp_mint.to_account_info(),
+    };
+    let cpi_context = CpiContext::new(ctx.accounts.dynamic_amm_program.to_account_info(), accounts);
+    dynamic_amm::cpi::lock(cpi_context, lp_amount)?;
+
+    Ok(())
+}
@@ -29,4 +29,56 @@ pub enum CurveLaunchpadError {
     InvalidFeeRecipient,
     #[msg("Invalid Withdraw Authority")]
     InvalidWithdrawAuthority,
+    #[msg("Bonding Curve Refunding")]
+    BondingCurveRefunding,
+    #[msg("Bonding Curve Not Refunding")]
+    BondingCurveNotRefunding,
+    #[msg("Graduation deadline must be in the future")]
+    InvalidGraduationDeadline,
+    #[msg("Min redeem is 1 Token")]
+    MinRedeem,
+    #[msg("Graduation target must be above the starting curve")]
+    InvalidGraduationTarget,
+    #[msg("Bonding Curve Not Started")]
+    BondingCurveNotStarted,
+    #[msg("Bonding Curve Migrated")]
+    BondingCurveMigrated,
+    #[msg("Bonding Curve Frozen")]
+    BondingCurveFrozen,
+    #[msg("Invalid Bonding Curve Status Transition")]
+    InvalidStatusTransition,
+    #[msg("Bonding Curve Token Account Not Empty")]
+    BondingCurveNotEmpty,
+    #[msg("Invalid Creator")]
+    InvalidCreator,
+    #[msg("Unknown Account Version")]
+    UnknownAccountVersion,
+    #[msg("TWAP window must be positive")]
+    InvalidTwapWindow,
+    #[msg("Not enough oracle history for the TWAP window")]
+    OracleWindowTooLong,
+    #[msg("Creator Not Allowed")]
+    CreatorNotAllowed,
+    #[msg("Creator Rate Limited")]
+    CreatorRateLimited,
+    #[msg("Rate limit needs a window")]
+    InvalidRateLimit,
+    #[msg("Name must be 1-32 bytes without control characters")]
+    InvalidName,
+    #[msg("Symbol must be 1-10 ASCII letters or digits")]
+    InvalidSymbol,
+    #[msg("URI must be 1-200 printable ASCII characters without spaces")]
+    InvalidUri,
+    #[msg("Decimals must be at most 9")]
+    InvalidDecimals,
+    #[msg("Token supply is too small or too large for the curve")]
+    InvalidTokenSupply,
+    #[msg("Mint must end in the configured suffix")]
+    RequireVanityMint,
+    #[msg("Mint suffix must be at most 8 base58 characters")]
+    InvalidMintSuffix,
+    #[msg("Mint vault is full")]
+    MintVaultFull,
+    #[msg("Mint is not in the vault")]
+    InvalidVaultMint,
 }
\ No newline at end of file
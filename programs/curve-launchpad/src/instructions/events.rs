@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 
+use crate::state::CurveStatus;
+
 #[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct CreateEvent {
     pub name: String,
     pub symbol: String,
@@ -11,6 +14,7 @@ pub struct CreateEvent {
 }
 
 #[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TradeEvent {
     pub mint: Pubkey,
     pub sol_amount: u64,
@@ -25,6 +29,7 @@ pub struct TradeEvent {
 }
 
 #[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct CompleteEvent {
     pub user: Pubkey,
     pub mint: Pubkey,
@@ -33,6 +38,51 @@ pub struct CompleteEvent {
 }
 
 #[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StatusChangedEvent {
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub previous_status: CurveStatus,
+    pub status: CurveStatus,
+    pub timestamp: i64,
+}
+
+/// Emitted by `withdraw`, with what it locked down on the token.
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GraduatedEvent {
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub mint_authority_revoked: bool,
+    pub freeze_authority_revoked: bool,
+    pub metadata_made_immutable: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RedeemEvent {
+    pub mint: Pubkey,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub user: Pubkey,
+    pub timestamp: i64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CloseCurveEvent {
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub creator: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SetParamsEvent {
     pub fee_recipient: Pubkey,
     pub withdraw_authority: Pubkey,
@@ -41,4 +91,44 @@ pub struct SetParamsEvent {
     pub initial_real_token_reserves: u64,
     pub initial_token_supply: u64,
     pub fee_basis_points: u64,
-}
\ No newline at end of file
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SetCreateControlsEvent {
+    pub creation_fee_lamports: u64,
+    pub creator_rate_limit: u32,
+    pub creator_rate_limit_window: i64,
+    pub creator_allowlist: bool,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CreatorAllowedEvent {
+    pub creator: Pubkey,
+    pub allowed: bool,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MetadataUpdatedEvent {
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SetMintSuffixEvent {
+    pub mint_suffix: String,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VaultMintDepositedEvent {
+    pub mint: Pubkey,
+    /// Mints waiting in the vault, including this one.
+    pub vault_size: u32,
+}
@@ -1,5 +1,12 @@
-use crate::{state::Global, CurveLaunchpadError, DEFAULT_TOKEN_SUPPLY};
+use crate::{
+    mint_collection, state::Global, CollectionAccounts, CollectionMetadata, CurveLaunchpadError, COLLECTION_MINT_SEED, DEFAULT_TOKEN_SUPPLY
+};
 use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::Metadata as Metaplex,
+    token::{Mint, Token, TokenAccount},
+};
 
 
 #[derive(Accounts)]
@@ -16,11 +23,77 @@ pub struct Initialize<'info> {
     )]
     global: Box<Account<'info, Global>>,
 
+    /// CHECK: Using seed to validate mint_authority account
+    #[account(
+        seeds=[b"mint-authority"],
+        bump,
+    )]
+    mint_authority: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [COLLECTION_MINT_SEED],
+        bump,
+        mint::decimals = 0,
+        mint::authority = mint_authority,
+        mint::freeze_authority = mint_authority,
+    )]
+    collection_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = collection_mint,
+        associated_token::authority = mint_authority,
+    )]
+    collection_token_account: Box<Account<'info, TokenAccount>>,
+
+    ///CHECK: Using seed to validate metadata account
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key.as_ref(),
+            collection_mint.to_account_info().key.as_ref()
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump,
+    )]
+    collection_metadata: AccountInfo<'info>,
+
+    ///CHECK: Using seed to validate master edition account
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key.as_ref(),
+            collection_mint.to_account_info().key.as_ref(),
+            b"edition"
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump,
+    )]
+    collection_master_edition: AccountInfo<'info>,
+
     system_program: Program<'info, System>,
+
+    token_program: Program<'info, Token>,
+
+    associated_token_program: Program<'info, AssociatedToken>,
+
+    token_metadata_program: Program<'info, Metaplex>,
+
+    rent: Sysvar<'info, Rent>,
 }
 
 
-pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+pub fn initialize(
+    ctx: Context<Initialize>,
+    collection_name: String,
+    collection_symbol: String,
+    collection_uri: String,
+) -> Result<()> {
     let global = &mut ctx.accounts.global;
 
     require!(
@@ -28,6 +101,7 @@ pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
         CurveLaunchpadError::AlreadyInitialized,
     );
 
+    global.version = Global::CURRENT_VERSION;
     global.authority = *ctx.accounts.authority.to_account_info().key;
     global.initialized = true;
     global.initial_token_supply = DEFAULT_TOKEN_SUPPLY;
@@ -37,6 +111,27 @@ pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
     global.initial_virtual_token_reserves = 1_073_000_000_000_000;
     global.fee_basis_points = 50;
 
+    //the collection every launched token is verified into
+    let accounts = CollectionAccounts {
+        payer: ctx.accounts.authority.to_account_info(),
+        mint_authority: ctx.accounts.mint_authority.to_account_info(),
+        mint_authority_bump: ctx.bumps.mint_authority,
+        collection_mint: ctx.accounts.collection_mint.to_account_info(),
+        collection_token_account: ctx.accounts.collection_token_account.to_account_info(),
+        collection_metadata: ctx.accounts.collection_metadata.to_account_info(),
+        collection_master_edition: ctx.accounts.collection_master_edition.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+    };
+    let metadata = CollectionMetadata {
+        name: collection_name,
+        symbol: collection_symbol,
+        uri: collection_uri,
+    };
+    mint_collection(accounts, metadata)?;
+
     msg!("Initialized global state");
 
     Ok(())
@@ -0,0 +1,141 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::token::Mint;
+
+use crate::{
+    state::{upgrade_bonding_curve, upgrade_global, BondingCurve, Global},
+    CurveLaunchpadError,
+};
+
+#[derive(Accounts)]
+pub struct MigrateGlobal<'info> {
+    #[account(mut)]
+    user: Signer<'info>,
+
+    /// CHECK: Decoded by hand as it may still be in a legacy layout
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: UncheckedAccount<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateCurve<'info> {
+    #[account(mut)]
+    user: Signer<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    mint: Account<'info, Mint>,
+
+    /// CHECK: Decoded by hand as it may still be in a legacy layout
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    bonding_curve: UncheckedAccount<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+pub fn migrate_global(ctx: Context<MigrateGlobal>) -> Result<()> {
+    let upgraded = upgrade_global(&ctx.accounts.global.try_borrow_data()?)?;
+
+    let Some(global) = upgraded else {
+        msg!("Global already at version {}", Global::CURRENT_VERSION);
+        return Ok(());
+    };
+
+    //confirm user is the authority
+    require!(
+        global.authority == *ctx.accounts.user.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    write_upgraded(
+        &ctx.accounts.global,
+        &ctx.accounts.user,
+        &ctx.accounts.system_program,
+        8 + Global::INIT_SPACE,
+        &global,
+    )?;
+
+    msg!("Migrated global to version {}", Global::CURRENT_VERSION);
+
+    Ok(())
+}
+
+pub fn migrate_curve(ctx: Context<MigrateCurve>, creator: Pubkey) -> Result<()> {
+    let upgraded = upgrade_bonding_curve(&ctx.accounts.bonding_curve.try_borrow_data()?)?;
+
+    let Some(mut bonding_curve) = upgraded else {
+        msg!("Bonding curve already at version {}", BondingCurve::CURRENT_VERSION);
+        return Ok(());
+    };
+
+    //confirm user is the authority
+    require!(
+        ctx.accounts.global.authority == *ctx.accounts.user.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    //unversioned curves didn't record their creator, without one nobody could close the curve or update its metadata
+    bonding_curve.creator = creator;
+
+    write_upgraded(
+        &ctx.accounts.bonding_curve,
+        &ctx.accounts.user,
+        &ctx.accounts.system_program,
+        8 + BondingCurve::INIT_SPACE,
+        &bonding_curve,
+    )?;
+
+    msg!("Migrated bonding curve to version {}", BondingCurve::CURRENT_VERSION);
+
+    Ok(())
+}
+
+//grows the account to the current layout, tops up its rent and writes the upgraded state in place
+fn write_upgraded<'info, T: AccountSerialize>(
+    account: &UncheckedAccount<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+    upgraded: &T,
+) -> Result<()> {
+    //only the rent of the added bytes, lamports above the old rent may be a curve's SOL reserves
+    let rent = Rent::get()?;
+    let top_up = rent
+        .minimum_balance(new_len)
+        .saturating_sub(rent.minimum_balance(account.data_len()));
+
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.to_account_info(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+
+    account.realloc(new_len, true)?;
+
+    let mut data = account.try_borrow_mut_data()?;
+    upgraded.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token};
+
+use crate::{
+    state::{Global, MintVault}, CurveLaunchpadError, VaultMintDepositedEvent, DEFAULT_DECIMALS
+};
+
+/// Creates a pre-generated mint under the program's mint authority and queues it for `create`.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositVaultMint<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = DEFAULT_DECIMALS as u8,
+        mint::authority = mint_authority,
+    )]
+    mint: Account<'info, Mint>,
+
+    /// CHECK: Using seed to validate mint_authority account
+    #[account(
+        seeds=[b"mint-authority"],
+        bump,
+    )]
+    mint_authority: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + MintVault::INIT_SPACE,
+        seeds = [MintVault::SEED_PREFIX],
+        bump,
+    )]
+    mint_vault: Box<Account<'info, MintVault>>,
+
+    system_program: Program<'info, System>,
+
+    token_program: Program<'info, Token>,
+
+    rent: Sysvar<'info, Rent>,
+}
+
+pub fn deposit_vault_mint(ctx: Context<DepositVaultMint>) -> Result<()> {
+    //confirm program is initialized
+    require!(
+        ctx.accounts.global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the authority
+    require!(
+        ctx.accounts.global.authority == *ctx.accounts.authority.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    let mint = *ctx.accounts.mint.to_account_info().key;
+
+    //create checks again, the suffix may change while the mint waits
+    require!(
+        ctx.accounts.global.is_vanity_mint(&mint),
+        CurveLaunchpadError::RequireVanityMint
+    );
+
+    let mint_vault = &mut ctx.accounts.mint_vault;
+    require!(mint_vault.push(mint), CurveLaunchpadError::MintVaultFull);
+
+    emit_cpi!(VaultMintDepositedEvent {
+        mint,
+        vault_size: mint_vault.mints.len() as u32,
+    });
+
+    Ok(())
+}
@@ -8,6 +8,17 @@ pub mod constants;
 pub mod events;
 pub mod util;
 pub mod withdraw;
+pub mod redeem;
+pub mod set_curve_frozen;
+pub mod close_curve;
+pub mod migrate;
+pub mod create_pool;
+pub mod quote;
+pub mod oracle;
+pub mod set_creator_allowed;
+pub mod update_metadata;
+pub mod collection;
+pub mod mint_vault;
 
 pub use initialize::*;
 pub use errors::*;
@@ -18,4 +29,15 @@ pub use set_params::*;
 pub use constants::*;
 pub use events::*;
 pub use util::*;
-pub use withdraw::*;
\ No newline at end of file
+pub use withdraw::*;
+pub use redeem::*;
+pub use set_curve_frozen::*;
+pub use close_curve::*;
+pub use migrate::*;
+pub use create_pool::*;
+pub use quote::*;
+pub use oracle::*;
+pub use set_creator_allowed::*;
+pub use update_metadata::*;
+pub use mint_vault::*;
+pub use collection::*;
\ No newline at end of file
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{
+    state::{BondingCurve, CurveOracle}, CurveLaunchpadError
+};
+
+/// Creates the oracle of a curve created before curves had one. Anyone can pay for it.
+#[derive(Accounts)]
+pub struct InitCurveOracle<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + CurveOracle::INIT_SPACE,
+        seeds = [CurveOracle::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    curve_oracle: Box<Account<'info, CurveOracle>>,
+
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetTwap<'info> {
+    mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [CurveOracle::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    curve_oracle: Box<Account<'info, CurveOracle>>,
+}
+
+/// Returned by `get_twap`, prices are lamports per base unit of the token as Q64.64 fixed point.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Twap {
+    pub price: u128,
+    /// Seconds the TWAP covers, at least the window asked for.
+    pub window: i64,
+    pub spot_price: u128,
+}
+
+pub fn init_curve_oracle(ctx: Context<InitCurveOracle>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let price = ctx.accounts.bonding_curve.spot_price();
+
+    ctx.accounts
+        .curve_oracle
+        .init(*ctx.accounts.mint.to_account_info().key, price, now);
+
+    Ok(())
+}
+
+pub fn get_twap(ctx: Context<GetTwap>, window: i64) -> Result<Twap> {
+    require!(window > 0, CurveLaunchpadError::InvalidTwapWindow);
+
+    let now = Clock::get()?.unix_timestamp;
+    let oracle = &ctx.accounts.curve_oracle;
+
+    let (price, window) = oracle
+        .twap(window, now)
+        .ok_or(CurveLaunchpadError::OracleWindowTooLong)?;
+
+    Ok(Twap {
+        price,
+        window,
+        spot_price: oracle.price,
+    })
+}
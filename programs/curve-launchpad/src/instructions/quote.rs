@@ -0,0 +1,178 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+use crate::{
+    amm, calculate_fee, state::{BondingCurve, CurveStatus, Global}, CurveLaunchpadError
+};
+
+#[derive(Accounts)]
+pub struct QuoteCurve<'info> {
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+    )]
+    bonding_curve_token_account: Box<Account<'info, TokenAccount>>,
+}
+
+/// What a `buy` or `sell` of the same amount would do right now, returned by `quote_buy` and `quote_sell`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Quote {
+    /// Tokens bought or sold, a buy that graduates the curve is only partially filled.
+    pub token_amount: u64,
+    /// SOL paid into or taken out of the curve, before the fee.
+    pub sol_amount: u64,
+    /// Paid on top of `sol_amount` for a buy, taken out of it for a sell.
+    pub fee: u64,
+    /// How far the average price is from the spot price before the trade, in basis points.
+    pub price_impact_bps: u64,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+}
+
+pub fn quote_buy(ctx: Context<QuoteCurve>, token_amount: u64) -> Result<Quote> {
+    let global = &ctx.accounts.global;
+    let bonding_curve = &ctx.accounts.bonding_curve;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(global.initialized, CurveLaunchpadError::NotInitialized);
+
+    //same checks as buy, a scheduled curve past its start time opens with the buy
+    require!(!bonding_curve.missed_deadline(now), CurveLaunchpadError::BondingCurveRefunding);
+
+    if !(bonding_curve.status == CurveStatus::Scheduled && now >= bonding_curve.start_time) {
+        bonding_curve.require_status(&[CurveStatus::Trading])?;
+    }
+
+    require!(token_amount > 0, CurveLaunchpadError::MinBuy);
+
+    let mut amm = curve_amm(bonding_curve);
+
+    let fill_amount = match bonding_curve.tokens_to_graduate(&amm) {
+        Some(tokens) if tokens < token_amount as u128 => tokens as u64,
+        _ => token_amount,
+    };
+
+    require!(
+        bonding_curve.real_token_reserves >= fill_amount,
+        CurveLaunchpadError::InsufficientTokens,
+    );
+
+    //buy only hands out what the curve's token account holds
+    let fill_amount = fill_amount.min(ctx.accounts.bonding_curve_token_account.amount);
+
+    let result = amm
+        .apply_buy(fill_amount as u128)
+        .ok_or(CurveLaunchpadError::InsufficientTokens)?;
+
+    Ok(quote(
+        bonding_curve,
+        &amm,
+        result.token_amount,
+        result.sol_amount,
+        calculate_fee(result.sol_amount, global.fee_basis_points),
+    ))
+}
+
+pub fn quote_sell(ctx: Context<QuoteCurve>, token_amount: u64) -> Result<Quote> {
+    let global = &ctx.accounts.global;
+    let bonding_curve = &ctx.accounts.bonding_curve;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(global.initialized, CurveLaunchpadError::NotInitialized);
+
+    //same checks as sell
+    require!(!bonding_curve.missed_deadline(now), CurveLaunchpadError::BondingCurveRefunding);
+
+    bonding_curve.require_status(&[CurveStatus::Trading])?;
+
+    require!(token_amount > 0, CurveLaunchpadError::MinSell);
+
+    let mut amm = curve_amm(bonding_curve);
+
+    let result = amm
+        .apply_sell(token_amount as u128)
+        .ok_or(CurveLaunchpadError::InsufficientTokens)?;
+
+    Ok(quote(
+        bonding_curve,
+        &amm,
+        result.token_amount,
+        result.sol_amount,
+        calculate_fee(result.sol_amount, global.fee_basis_points),
+    ))
+}
+
+fn curve_amm(bonding_curve: &BondingCurve) -> amm::AMM {
+    amm::AMM::new(
+        bonding_curve.virtual_sol_reserves as u128,
+        bonding_curve.virtual_token_reserves as u128,
+        bonding_curve.real_sol_reserves as u128,
+        bonding_curve.real_token_reserves as u128,
+    )
+}
+
+//`before` is the curve as it is on chain, `after` the amm with the trade applied
+fn quote(before: &BondingCurve, after: &amm::AMM, token_amount: u64, sol_amount: u64, fee: u64) -> Quote {
+    Quote {
+        token_amount,
+        sol_amount,
+        fee,
+        price_impact_bps: price_impact_bps(
+            before.virtual_sol_reserves,
+            before.virtual_token_reserves,
+            token_amount,
+            sol_amount,
+        ),
+        virtual_sol_reserves: after.virtual_sol_reserves as u64,
+        virtual_token_reserves: after.virtual_token_reserves as u64,
+        real_sol_reserves: after.real_sol_reserves as u64,
+        real_token_reserves: after.real_token_reserves as u64,
+    }
+}
+
+//compares sol_amount / token_amount against the spot price virtual_sol / virtual_token, cross multiplied
+//so it stays in integers. buys pay above the spot price and sells receive below it, either way it's the distance
+fn price_impact_bps(virtual_sol_reserves: u64, virtual_token_reserves: u64, token_amount: u64, sol_amount: u64) -> u64 {
+    let at_spot = virtual_sol_reserves as u128 * token_amount as u128;
+    let executed = sol_amount as u128 * virtual_token_reserves as u128;
+
+    if at_spot == 0 {
+        return 0;
+    }
+
+    (at_spot.abs_diff(executed) * 10_000 / at_spot).min(u64::MAX as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_impact_bps() {
+        let (virtual_sol, virtual_token) = (30_000_000_000, 1_073_000_000_000_000);
+
+        //exactly at the spot price
+        assert_eq!(price_impact_bps(virtual_sol, virtual_token, 1_073_000_000, 30_000), 0);
+        //10% above and below it
+        assert_eq!(price_impact_bps(virtual_sol, virtual_token, 1_073_000_000, 33_000), 1_000);
+        assert_eq!(price_impact_bps(virtual_sol, virtual_token, 1_073_000_000, 27_000), 1_000);
+
+        assert_eq!(price_impact_bps(virtual_sol, virtual_token, 0, 0), 0);
+    }
+}
@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::{
+    calculate_redeem_amount, state::{BondingCurve, CurveStatus, Global}, CurveLaunchpadError, RedeemEvent, StatusChangedEvent
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Redeem<'info> {
+    #[account(mut)]
+    user: Signer<'info>,
+
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+    )]
+    bonding_curve_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+    )]
+    user_token_account: Box<Account<'info, TokenAccount>>,
+
+    system_program: Program<'info, System>,
+
+    token_program: Program<'info, Token>,
+}
+
+pub fn redeem(ctx: Context<Redeem>, token_amount: u64) -> Result<()> {
+    require!(
+        ctx.accounts.global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+
+    //the first redeem after the graduation deadline moves the curve into refunding
+    if ctx.accounts.bonding_curve.missed_deadline(now) {
+        let previous_status = ctx.accounts.bonding_curve.transition(CurveStatus::Refunding)?;
+
+        emit_cpi!(StatusChangedEvent {
+            mint: *ctx.accounts.mint.to_account_info().key,
+            bonding_curve: *ctx.accounts.bonding_curve.to_account_info().key,
+            previous_status,
+            status: CurveStatus::Refunding,
+            timestamp: now,
+        });
+    }
+
+    //only curves that missed their graduation deadline can be redeemed
+    ctx.accounts.bonding_curve.require_status(&[CurveStatus::Refunding])?;
+
+    require!(token_amount > 0, CurveLaunchpadError::MinRedeem,);
+
+    //confirm user has enough tokens
+    require!(
+        ctx.accounts.user_token_account.amount >= token_amount,
+        CurveLaunchpadError::InsufficientTokens,
+    );
+
+    //every token outside the curve's vault has a claim on the real SOL reserves
+    let outstanding_tokens = ctx
+        .accounts
+        .mint
+        .supply
+        .checked_sub(ctx.accounts.bonding_curve_token_account.amount)
+        .ok_or(CurveLaunchpadError::InsufficientTokens)?;
+
+    let sol_amount = calculate_redeem_amount(
+        token_amount,
+        ctx.accounts.bonding_curve.real_sol_reserves,
+        outstanding_tokens,
+    )
+    .ok_or(CurveLaunchpadError::InsufficientTokens)?;
+
+    //return the tokens to the bonding curve
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                to: ctx.accounts.bonding_curve_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        token_amount,
+    )?;
+
+    //transfer the pro-rata SOL back to user, no fee is taken on refunds
+    let from_account = &ctx.accounts.bonding_curve;
+    let to_account = &ctx.accounts.user;
+
+    **from_account.to_account_info().try_borrow_mut_lamports()? -= sol_amount;
+    **to_account.try_borrow_mut_lamports()? += sol_amount;
+
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    bonding_curve.real_sol_reserves -= sol_amount;
+    bonding_curve.real_token_reserves += token_amount;
+
+    emit_cpi!(RedeemEvent {
+        mint: *ctx.accounts.mint.to_account_info().key,
+        sol_amount,
+        token_amount,
+        user: *ctx.accounts.user.to_account_info().key,
+        timestamp: now,
+        real_sol_reserves: bonding_curve.real_sol_reserves,
+        real_token_reserves: bonding_curve.real_token_reserves,
+    });
+
+    Ok(())
+}
@@ -1,11 +1,11 @@
-use std::{char::MAX, str::FromStr};
+use std::str::FromStr;
 
 use crate::{
-    amm, calculate_fee, state::{BondingCurve, Global}, CurveLaunchpadError, TradeEvent
+    amm, calculate_fee, dynamic_amm, state::{BondingCurve, CurveOracle, CurveStatus, Global}, CurveLaunchpadError, TradeEvent, TradeResult
 };
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
-declare_program!(dynamic_amm);
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
 #[event_cpi]
 #[derive(Accounts)]
 pub struct Sell<'info> {
@@ -31,6 +31,13 @@ pub struct Sell<'info> {
     )]
     bonding_curve: Box<Account<'info, BondingCurve>>,
 
+    #[account(
+        mut,
+        seeds = [CurveOracle::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    curve_oracle: Box<Account<'info, CurveOracle>>,
+
     #[account(
         mut,
         associated_token::mint = mint,
@@ -50,9 +57,17 @@ pub struct Sell<'info> {
     token_program: Program<'info, Token>,
 }
 
-pub fn sell(ctx: Context<Sell>, token_amount: u64, min_sol_output: u64) -> Result<()> {
-    //check if bonding curve is complete
-    
+pub fn sell(ctx: Context<Sell>, token_amount: u64, min_sol_output: u64) -> Result<TradeResult> {
+    let now = Clock::get()?.unix_timestamp;
+
+    //past its graduation deadline the curve only redeems
+    require!(
+        !ctx.accounts.bonding_curve.missed_deadline(now),
+        CurveLaunchpadError::BondingCurveRefunding,
+    );
+
+    //check if bonding curve is trading
+    ctx.accounts.bonding_curve.require_status(&[CurveStatus::Trading])?;
 
     //confirm user has enough tokens
     require!(
@@ -79,7 +94,6 @@ pub fn sell(ctx: Context<Sell>, token_amount: u64, min_sol_output: u64) -> Resul
         ctx.accounts.bonding_curve.virtual_token_reserves as u128,
         ctx.accounts.bonding_curve.real_sol_reserves as u128,
         ctx.accounts.bonding_curve.real_token_reserves as u128,
-        ctx.accounts.global.initial_virtual_token_reserves as u128,
     );
 
     let sell_result = amm.apply_sell(token_amount as u128).unwrap();
@@ -94,55 +108,56 @@ pub fn sell(ctx: Context<Sell>, token_amount: u64, min_sol_output: u64) -> Resul
         CurveLaunchpadError::MinSOLOutputExceeded,
     );
 
-    //transfer SPL
-    let cpi_accounts = Burn {
-        from: ctx.accounts.user_token_account.to_account_info().clone(),
-        mint: ctx.accounts.mint.to_account_info().clone(),
-        authority: ctx.accounts.user.to_account_info().clone(),
-    };
-
-    token::burn(
-        CpiContext::new_with_signer(
+    //transfer SPL back to the bonding curve
+    token::transfer(
+        CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            cpi_accounts,
-            &[],
+            Transfer {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                to: ctx.accounts.bonding_curve_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
         ),
         sell_result.token_amount,
     )?;
 
-    //transfer SOL back to user
-    //TODO: check if this is correct
+    //transfer SOL back to user, the fee comes out of the sale rather than the curve's reserves
     let from_account = &ctx.accounts.bonding_curve;
     let to_account = &ctx.accounts.user;
 
-    **from_account.to_account_info().try_borrow_mut_lamports()? -= sell_result.sol_amount;
-    **to_account.try_borrow_mut_lamports()? += sell_result.sol_amount;
+    **from_account.to_account_info().try_borrow_mut_lamports()? -= sell_amount_minus_fee;
+    **to_account.try_borrow_mut_lamports()? += sell_amount_minus_fee;
 
     //transfer fee to fee recipient
     **from_account.to_account_info().try_borrow_mut_lamports()? -= fee;
     **ctx.accounts.fee_recipient.try_borrow_mut_lamports()? += fee;
 
-
     let bonding_curve = &mut ctx.accounts.bonding_curve;
     bonding_curve.real_token_reserves = amm.real_token_reserves as u64;
     bonding_curve.real_sol_reserves = amm.real_sol_reserves as u64;
     bonding_curve.virtual_token_reserves = amm.virtual_token_reserves as u64;
     bonding_curve.virtual_sol_reserves = amm.virtual_sol_reserves as u64;
 
+    ctx.accounts.curve_oracle.update(bonding_curve.spot_price(), now);
+
     emit_cpi!(TradeEvent {
         mint: *ctx.accounts.mint.to_account_info().key,
         sol_amount: sell_result.sol_amount,
         token_amount: sell_result.token_amount,
         is_buy: false,
         user: *ctx.accounts.user.to_account_info().key,
-        timestamp: Clock::get()?.unix_timestamp,
+        timestamp: now,
         virtual_sol_reserves: bonding_curve.virtual_sol_reserves,
         virtual_token_reserves: bonding_curve.virtual_token_reserves,
         real_sol_reserves: bonding_curve.real_sol_reserves,
         real_token_reserves: bonding_curve.real_token_reserves,
     });
 
-    Ok(())
+    Ok(TradeResult {
+        token_amount: sell_result.token_amount,
+        sol_amount: sell_result.sol_amount,
+        fee,
+    })
 }
 
 pub fn claim_fee(ctx: Context<ClaimFee>) -> Result<()> {
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::{CreatorStats, Global}, CreatorAllowedEvent, CurveLaunchpadError
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetCreatorAllowed<'info> {
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    #[account(mut)]
+    user: Signer<'info>,
+
+    /// CHECK: any wallet can be allowed to create
+    creator: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + CreatorStats::INIT_SPACE,
+        seeds = [CreatorStats::SEED_PREFIX, creator.key.as_ref()],
+        bump,
+    )]
+    creator_stats: Box<Account<'info, CreatorStats>>,
+
+    system_program: Program<'info, System>,
+}
+
+pub fn set_creator_allowed(ctx: Context<SetCreatorAllowed>, allowed: bool) -> Result<()> {
+    //confirm program is initialized
+    require!(
+        ctx.accounts.global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the authority
+    require!(
+        ctx.accounts.global.authority == *ctx.accounts.user.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    let creator = *ctx.accounts.creator.key;
+    let creator_stats = &mut ctx.accounts.creator_stats;
+    creator_stats.version = CreatorStats::CURRENT_VERSION;
+    creator_stats.creator = creator;
+    creator_stats.allowed = allowed;
+
+    emit_cpi!(CreatorAllowedEvent { creator, allowed });
+
+    Ok(())
+}
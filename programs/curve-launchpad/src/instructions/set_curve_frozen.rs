@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{
+    state::{BondingCurve, CurveStatus, Global}, CurveLaunchpadError, StatusChangedEvent
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetCurveFrozen<'info> {
+    #[account(
+        seeds = [Global::SEED_PREFIX],
+        bump,
+    )]
+    global: Box<Account<'info, Global>>,
+
+    user: Signer<'info>,
+
+    mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+}
+
+pub fn set_curve_frozen(ctx: Context<SetCurveFrozen>, frozen: bool) -> Result<()> {
+    //confirm program is initialized
+    require!(
+        ctx.accounts.global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the authority
+    require!(
+        ctx.accounts.global.authority == *ctx.accounts.user.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+
+    //an unfrozen curve goes back to waiting for its start time if it hasn't been reached
+    let status = if frozen {
+        CurveStatus::Frozen
+    } else if now < bonding_curve.start_time {
+        CurveStatus::Scheduled
+    } else {
+        CurveStatus::Trading
+    };
+
+    let previous_status = bonding_curve.transition(status)?;
+
+    emit_cpi!(StatusChangedEvent {
+        mint: *ctx.accounts.mint.to_account_info().key,
+        bonding_curve: *ctx.accounts.bonding_curve.to_account_info().key,
+        previous_status,
+        status,
+        timestamp: now,
+    });
+
+    Ok(())
+}
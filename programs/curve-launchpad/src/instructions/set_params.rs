@@ -1,4 +1,4 @@
-use crate::{state::Global, CurveLaunchpadError, SetParamsEvent};
+use crate::{state::Global, CurveLaunchpadError, SetCreateControlsEvent, SetMintSuffixEvent, SetParamsEvent};
 use anchor_lang::prelude::*;
 
 #[event_cpi]
@@ -16,6 +16,7 @@ pub struct SetParams<'info> {
     system_program: Program<'info, System>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn set_params(
     ctx: Context<SetParams>,
     fee_recipient: Pubkey,
@@ -60,3 +61,71 @@ pub fn set_params(
 
     Ok(())
 }
+
+/// The anti-spam controls `create` enforces, set through the same accounts as `set_params`.
+pub fn set_create_controls(
+    ctx: Context<SetParams>,
+    creation_fee_lamports: u64,
+    creator_rate_limit: u32,
+    creator_rate_limit_window: i64,
+    creator_allowlist: bool,
+) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+
+    //confirm program is initialized
+    require!(
+        global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the authority
+    require!(
+        global.authority == *ctx.accounts.user.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    require!(
+        creator_rate_limit == 0 || creator_rate_limit_window > 0,
+        CurveLaunchpadError::InvalidRateLimit
+    );
+
+    global.creation_fee_lamports = creation_fee_lamports;
+    global.creator_rate_limit = creator_rate_limit;
+    global.creator_rate_limit_window = creator_rate_limit_window;
+    global.creator_allowlist = creator_allowlist;
+
+    emit_cpi!(SetCreateControlsEvent {
+        creation_fee_lamports,
+        creator_rate_limit,
+        creator_rate_limit_window,
+        creator_allowlist,
+    });
+
+    Ok(())
+}
+
+/// The base58 suffix `create` requires of mints, empty to take any mint.
+pub fn set_mint_suffix(ctx: Context<SetParams>, mint_suffix: String) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+
+    //confirm program is initialized
+    require!(
+        global.initialized,
+        CurveLaunchpadError::NotInitialized
+    );
+
+    //confirm user is the authority
+    require!(
+        global.authority == *ctx.accounts.user.to_account_info().key,
+        CurveLaunchpadError::InvalidAuthority
+    );
+
+    require!(
+        global.set_mint_suffix(&mint_suffix),
+        CurveLaunchpadError::InvalidMintSuffix
+    );
+
+    emit_cpi!(SetMintSuffixEvent { mint_suffix });
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::{
+        mpl_token_metadata::{accounts::Metadata, types::DataV2},
+        update_metadata_accounts_v2, Metadata as Metaplex, UpdateMetadataAccountsV2,
+    },
+    token::Mint,
+};
+
+use crate::{
+    state::{BondingCurve, CurveStatus}, validate_metadata, CurveLaunchpadError, MetadataUpdatedEvent
+};
+
+/// Lets a curve's creator change its token's name, symbol and uri until the curve graduates.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateMetadata<'info> {
+    creator: Signer<'info>,
+
+    mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [BondingCurve::SEED_PREFIX, mint.to_account_info().key.as_ref()],
+        bump,
+    )]
+    bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    /// CHECK: Using seed to validate mint_authority account
+    #[account(
+        seeds=[b"mint-authority"],
+        bump,
+    )]
+    mint_authority: AccountInfo<'info>,
+
+    ///CHECK: Using seed to validate metadata account
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key.as_ref(),
+            mint.to_account_info().key.as_ref()
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump,
+    )]
+    metadata: AccountInfo<'info>,
+
+    token_metadata_program: Program<'info, Metaplex>,
+}
+
+pub fn update_metadata(ctx: Context<UpdateMetadata>, name: String, symbol: String, uri: String) -> Result<()> {
+    require!(
+        ctx.accounts.bonding_curve.creator == *ctx.accounts.creator.to_account_info().key,
+        CurveLaunchpadError::InvalidCreator
+    );
+
+    //holders of a graduated token rely on what they bought
+    ctx.accounts
+        .bonding_curve
+        .require_status(&[CurveStatus::Scheduled, CurveStatus::Trading])?;
+
+    validate_metadata(&name, &symbol, &uri)?;
+
+    //everything but the text stays as create set it
+    let metadata = Metadata::safe_deserialize(&ctx.accounts.metadata.try_borrow_data()?)?;
+    let data = DataV2 {
+        name: name.clone(),
+        symbol: symbol.clone(),
+        uri: uri.clone(),
+        seller_fee_basis_points: metadata.seller_fee_basis_points,
+        creators: metadata.creators,
+        collection: metadata.collection,
+        uses: metadata.uses,
+    };
+
+    let seeds = &["mint-authority".as_bytes(), &[ctx.bumps.mint_authority]];
+    let signer = [&seeds[..]];
+
+    update_metadata_accounts_v2(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            UpdateMetadataAccountsV2 {
+                metadata: ctx.accounts.metadata.to_account_info(),
+                update_authority: ctx.accounts.mint_authority.to_account_info(),
+            },
+            &signer,
+        ),
+        None,
+        Some(data),
+        None,
+        None,
+    )?;
+
+    emit_cpi!(MetadataUpdatedEvent {
+        mint: *ctx.accounts.mint.to_account_info().key,
+        name,
+        symbol,
+        uri,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,4 +1,9 @@
 
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::mpl_token_metadata::{MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH};
+
+use crate::CurveLaunchpadError;
+
 pub fn calculate_fee(
     amount: u64,
     fee_basis_points: u64,
@@ -6,6 +11,48 @@ pub fn calculate_fee(
     amount * fee_basis_points / 10000
 }
 
+//pro-rata share of the curve's real SOL for tokens redeemed while refunding
+pub fn calculate_redeem_amount(
+    token_amount: u64,
+    real_sol_reserves: u64,
+    outstanding_tokens: u64,
+) -> Option<u64> {
+    if token_amount == 0 || token_amount > outstanding_tokens {
+        return None;
+    }
+
+    let sol_amount = (real_sol_reserves as u128)
+        .checked_mul(token_amount as u128)?
+        .checked_div(outstanding_tokens as u128)?;
+
+    u64::try_from(sol_amount).ok()
+}
+
+//what create and update_metadata accept, lengths are Metaplex's limits in bytes
+pub fn validate_metadata(name: &str, symbol: &str, uri: &str) -> Result<()> {
+    require!(
+        !name.trim().is_empty()
+            && name.len() <= MAX_NAME_LENGTH
+            && !name.chars().any(char::is_control),
+        CurveLaunchpadError::InvalidName
+    );
+
+    require!(
+        !symbol.is_empty()
+            && symbol.len() <= MAX_SYMBOL_LENGTH
+            && symbol.bytes().all(|byte| byte.is_ascii_alphanumeric()),
+        CurveLaunchpadError::InvalidSymbol
+    );
+
+    require!(
+        !uri.is_empty()
+            && uri.len() <= MAX_URI_LENGTH
+            && uri.bytes().all(|byte| byte.is_ascii_graphic()),
+        CurveLaunchpadError::InvalidUri
+    );
+
+    Ok(())
+}
 
 #[cfg(test)]
 mod tests {
@@ -21,4 +68,41 @@ mod tests {
         assert_eq!(calculate_fee(1000, 50), 5); //0.5% fee
         assert_eq!(calculate_fee(100, 0), 0); //0% fee
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_calculate_redeem_amount() {
+        assert_eq!(calculate_redeem_amount(50, 1000, 100), Some(500)); //half the holders' tokens
+        assert_eq!(calculate_redeem_amount(100, 1000, 100), Some(1000)); //last holder takes the rest
+        assert_eq!(calculate_redeem_amount(1, 10, 3), Some(3)); //rounds down
+        assert_eq!(calculate_redeem_amount(0, 1000, 100), None);
+        assert_eq!(calculate_redeem_amount(101, 1000, 100), None);
+        assert_eq!(
+            calculate_redeem_amount(u64::MAX, u64::MAX, u64::MAX),
+            Some(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn test_validate_metadata() {
+        let invalid = |result: Result<()>, expected: CurveLaunchpadError| {
+            assert_eq!(result.unwrap_err(), expected.into());
+        };
+
+        assert!(validate_metadata("test", "tst", "https://www.test.com").is_ok());
+        assert!(validate_metadata("Dog Wif Hat 🐶", "WIF", "ipfs://bafy").is_ok());
+        assert!(validate_metadata(&"n".repeat(32), &"S".repeat(10), &"u".repeat(200)).is_ok());
+
+        invalid(validate_metadata("", "tst", "https://www.test.com"), CurveLaunchpadError::InvalidName);
+        invalid(validate_metadata("   ", "tst", "https://www.test.com"), CurveLaunchpadError::InvalidName);
+        invalid(validate_metadata(&"n".repeat(33), "tst", "https://www.test.com"), CurveLaunchpadError::InvalidName);
+        invalid(validate_metadata("te\0st", "tst", "https://www.test.com"), CurveLaunchpadError::InvalidName);
+
+        invalid(validate_metadata("test", "", "https://www.test.com"), CurveLaunchpadError::InvalidSymbol);
+        invalid(validate_metadata("test", &"S".repeat(11), "https://www.test.com"), CurveLaunchpadError::InvalidSymbol);
+        invalid(validate_metadata("test", "$TST", "https://www.test.com"), CurveLaunchpadError::InvalidSymbol);
+
+        invalid(validate_metadata("test", "tst", ""), CurveLaunchpadError::InvalidUri);
+        invalid(validate_metadata("test", "tst", &"u".repeat(201)), CurveLaunchpadError::InvalidUri);
+        invalid(validate_metadata("test", "tst", "https://www.test.com/a b"), CurveLaunchpadError::InvalidUri);
+    }
+}
@@ -1,14 +1,19 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Mint, Token, TokenAccount, Transfer},
+    metadata::{
+        mpl_token_metadata::accounts::Metadata, update_metadata_accounts_v2, Metadata as Metaplex,
+        UpdateMetadataAccountsV2,
+    },
+    token::{self, spl_token::instruction::AuthorityType, Mint, SetAuthority, Token, TokenAccount, Transfer},
 };
 
 use crate::{
-    state::{BondingCurve, Global, LastWithdraw},
-    CurveLaunchpadError,
+    state::{BondingCurve, CurveStatus, Global, LastWithdraw},
+    CurveLaunchpadError, GraduatedEvent, StatusChangedEvent,
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Withdraw<'info> {
     #[account(mut)]
@@ -20,6 +25,7 @@ pub struct Withdraw<'info> {
     )]
     global: Box<Account<'info, Global>>,
 
+    #[account(mut)]
     mint: Account<'info, Mint>,
 
     #[account(
@@ -58,6 +64,28 @@ pub struct Withdraw<'info> {
     system_program: Program<'info, System>,
 
     token_program: Program<'info, Token>,
+
+    /// CHECK: Using seed to validate mint_authority account
+    #[account(
+        seeds=[b"mint-authority"],
+        bump,
+    )]
+    mint_authority: AccountInfo<'info>,
+
+    ///CHECK: Using seed to validate metadata account
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key.as_ref(),
+            mint.to_account_info().key.as_ref()
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump,
+    )]
+    metadata: AccountInfo<'info>,
+
+    token_metadata_program: Program<'info, Metaplex>,
 }
 
 pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
@@ -66,10 +94,7 @@ pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
         CurveLaunchpadError::NotInitialized
     );
 
-    require!(
-        ctx.accounts.bonding_curve.complete == true,
-        CurveLaunchpadError::BondingCurveNotComplete,
-    );
+    ctx.accounts.bonding_curve.require_status(&[CurveStatus::Complete])?;
 
     require!(
         ctx.accounts.user.key() == ctx.accounts.global.withdraw_authority,
@@ -106,7 +131,7 @@ pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
     let from_account = &ctx.accounts.bonding_curve;
     let to_account = &ctx.accounts.user;
 
-    let min_balance = Rent::get()?.minimum_balance(8 + BondingCurve::INIT_SPACE as usize);
+    let min_balance = Rent::get()?.minimum_balance(8 + BondingCurve::INIT_SPACE);
 
     let total_bonding_curve_lamports = from_account.get_lamports() - min_balance;
 
@@ -114,8 +139,78 @@ pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
     **to_account.try_borrow_mut_lamports()? += total_bonding_curve_lamports;
 
     //update last withdraw
+    let now = Clock::get()?.unix_timestamp;
     let last_withdraw = &mut ctx.accounts.last_withdraw;
-    last_withdraw.last_withdraw_timestamp = Clock::get()?.unix_timestamp;
+    last_withdraw.last_withdraw_timestamp = now;
+
+    //the token is final once it graduates, holders can rely on its supply and metadata
+    let seeds = &["mint-authority".as_bytes(), &[ctx.bumps.mint_authority]];
+    let signer = [&seeds[..]];
+    let mint_authority = Some(*ctx.accounts.mint_authority.key);
+
+    let revoke = |authority_type: AuthorityType| {
+        token::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: ctx.accounts.mint_authority.to_account_info(),
+                    account_or_mint: ctx.accounts.mint.to_account_info(),
+                },
+                &signer,
+            ),
+            authority_type,
+            None,
+        )
+    };
+
+    let mint_authority_revoked = Option::from(ctx.accounts.mint.mint_authority) == mint_authority;
+    if mint_authority_revoked {
+        revoke(AuthorityType::MintTokens)?;
+    }
+
+    let freeze_authority_revoked = Option::from(ctx.accounts.mint.freeze_authority) == mint_authority;
+    if freeze_authority_revoked {
+        revoke(AuthorityType::FreezeAccount)?;
+    }
+
+    //curves from before update_metadata are already immutable
+    let metadata_made_immutable = Metadata::safe_deserialize(&ctx.accounts.metadata.try_borrow_data()?)?.is_mutable;
+    if metadata_made_immutable {
+        update_metadata_accounts_v2(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                UpdateMetadataAccountsV2 {
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    update_authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                &signer,
+            ),
+            None,
+            None,
+            None,
+            Some(false),
+        )?;
+    }
+
+    //the liquidity has left the curve for migration
+    let previous_status = ctx.accounts.bonding_curve.transition(CurveStatus::Migrated)?;
+
+    emit_cpi!(StatusChangedEvent {
+        mint: *ctx.accounts.mint.to_account_info().key,
+        bonding_curve: *ctx.accounts.bonding_curve.to_account_info().key,
+        previous_status,
+        status: CurveStatus::Migrated,
+        timestamp: now,
+    });
+
+    emit_cpi!(GraduatedEvent {
+        mint: *ctx.accounts.mint.to_account_info().key,
+        bonding_curve: *ctx.accounts.bonding_curve.to_account_info().key,
+        mint_authority_revoked,
+        freeze_authority_revoked,
+        metadata_made_immutable,
+        timestamp: now,
+    });
 
     Ok(())
 }
@@ -1,3 +1,6 @@
+//set_params takes the whole config as arguments, which the generated cpi client repeats
+#![allow(clippy::too_many_arguments)]
+
 use anchor_lang::prelude::*;
 
 use instructions::*;
@@ -13,26 +16,101 @@ pub mod curve_launchpad {
 
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        initialize::initialize(ctx)
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        collection_name: String,
+        collection_symbol: String,
+        collection_uri: String,
+    ) -> Result<()> {
+        initialize::initialize(ctx, collection_name, collection_symbol, collection_uri)
     }
 
-    pub fn create(ctx: Context<Create>, name: String, symbol: String, uri: String) -> Result<()> {
-        create::create(ctx, name, symbol, uri)
+    pub fn create(
+        ctx: Context<Create>,
+        name: String,
+        symbol: String,
+        uri: String,
+        graduation_deadline: Option<i64>,
+        graduation_target: Option<state::GraduationTarget>,
+        start_time: Option<i64>,
+        verify_creator: bool,
+        decimals: Option<u8>,
+        token_supply: Option<u64>,
+    ) -> Result<()> {
+        create::create(
+            ctx,
+            name,
+            symbol,
+            uri,
+            graduation_deadline,
+            graduation_target,
+            start_time,
+            verify_creator,
+            decimals,
+            token_supply,
+        )
     }
 
-    pub fn buy(ctx: Context<Buy>, token_amount: u64, max_sol_cost: u64) -> Result<()> {
+    pub fn buy(ctx: Context<Buy>, token_amount: u64, max_sol_cost: u64) -> Result<TradeResult> {
         buy::buy(ctx, token_amount, max_sol_cost)
     }
 
-    pub fn sell(ctx: Context<Sell>, token_amount: u64, min_sol_output: u64) -> Result<()> {
+    pub fn sell(ctx: Context<Sell>, token_amount: u64, min_sol_output: u64) -> Result<TradeResult> {
         sell::sell(ctx, token_amount, min_sol_output)
     }
 
+    pub fn quote_buy(ctx: Context<QuoteCurve>, token_amount: u64) -> Result<Quote> {
+        quote::quote_buy(ctx, token_amount)
+    }
+
+    pub fn quote_sell(ctx: Context<QuoteCurve>, token_amount: u64) -> Result<Quote> {
+        quote::quote_sell(ctx, token_amount)
+    }
+
+    pub fn init_collection(ctx: Context<InitCollection>, name: String, symbol: String, uri: String) -> Result<()> {
+        collection::init_collection(ctx, name, symbol, uri)
+    }
+
+    pub fn init_curve_oracle(ctx: Context<InitCurveOracle>) -> Result<()> {
+        oracle::init_curve_oracle(ctx)
+    }
+
+    pub fn get_twap(ctx: Context<GetTwap>, window: i64) -> Result<Twap> {
+        oracle::get_twap(ctx, window)
+    }
+
     pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
         withdraw::withdraw(ctx)
     }
 
+    pub fn create_pool(ctx: Context<CreatePool>) -> Result<()> {
+        create_pool::create_pool(ctx)
+    }
+
+    pub fn redeem(ctx: Context<Redeem>, token_amount: u64) -> Result<()> {
+        redeem::redeem(ctx, token_amount)
+    }
+
+    pub fn update_metadata(ctx: Context<UpdateMetadata>, name: String, symbol: String, uri: String) -> Result<()> {
+        update_metadata::update_metadata(ctx, name, symbol, uri)
+    }
+
+    pub fn set_curve_frozen(ctx: Context<SetCurveFrozen>, frozen: bool) -> Result<()> {
+        set_curve_frozen::set_curve_frozen(ctx, frozen)
+    }
+
+    pub fn close_curve(ctx: Context<CloseCurve>) -> Result<()> {
+        close_curve::close_curve(ctx)
+    }
+
+    pub fn migrate_global(ctx: Context<MigrateGlobal>) -> Result<()> {
+        migrate::migrate_global(ctx)
+    }
+
+    pub fn migrate_curve(ctx: Context<MigrateCurve>, creator: Pubkey) -> Result<()> {
+        migrate::migrate_curve(ctx, creator)
+    }
+
     pub fn set_params(
         ctx: Context<SetParams>,
         fee_recipient: Pubkey,
@@ -54,4 +132,32 @@ pub mod curve_launchpad {
             fee_basis_points,
         )
     }
-}
+
+    pub fn set_create_controls(
+        ctx: Context<SetParams>,
+        creation_fee_lamports: u64,
+        creator_rate_limit: u32,
+        creator_rate_limit_window: i64,
+        creator_allowlist: bool,
+    ) -> Result<()> {
+        set_params::set_create_controls(
+            ctx,
+            creation_fee_lamports,
+            creator_rate_limit,
+            creator_rate_limit_window,
+            creator_allowlist,
+        )
+    }
+
+    pub fn set_creator_allowed(ctx: Context<SetCreatorAllowed>, allowed: bool) -> Result<()> {
+        set_creator_allowed::set_creator_allowed(ctx, allowed)
+    }
+
+    pub fn set_mint_suffix(ctx: Context<SetParams>, mint_suffix: String) -> Result<()> {
+        set_params::set_mint_suffix(ctx, mint_suffix)
+    }
+
+    pub fn deposit_vault_mint(ctx: Context<DepositVaultMint>) -> Result<()> {
+        mint_vault::deposit_vault_mint(ctx)
+    }
+}
\ No newline at end of file
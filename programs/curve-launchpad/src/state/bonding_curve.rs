@@ -1,32 +1,83 @@
 use anchor_lang::prelude::*;
 use std::fmt;
 
+use crate::{amm::AMM, state::CurveStatus};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum GraduationTarget {
+    /// Graduate once `real_sol_reserves` reaches this many lamports.
+    SolRaised(u64),
+    /// Graduate once the market cap implied by the virtual reserves reaches this many lamports.
+    MarketCap(u64),
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct BondingCurve {
+    pub version: u8,
+    pub creator: Pubkey,
     pub virtual_sol_reserves: u64,
     pub virtual_token_reserves: u64,
     pub real_sol_reserves: u64,
     pub real_token_reserves: u64,
     pub token_total_supply: u64,
-    pub complete: bool,
+    pub status: CurveStatus,
+    pub start_time: i64,
+    pub graduation_deadline: Option<i64>,
+    pub graduation_target: Option<GraduationTarget>,
+    /// Zeroed space new fields are carved out of without a realloc.
+    pub reserved: [u8; 128],
 }
 
 impl BondingCurve {
     pub const SEED_PREFIX: &'static [u8; 13] = b"bonding-curve";
+    pub const CURRENT_VERSION: u8 = 1;
+
+    /// Tokens left to sell before the curve reaches its graduation target,
+    /// `None` if it has no target or the target is beyond its real reserves.
+    pub fn tokens_to_graduate(&self, amm: &AMM) -> Option<u128> {
+        match self.graduation_target? {
+            GraduationTarget::SolRaised(lamports) => {
+                amm.get_tokens_to_raise((lamports as u128).saturating_sub(amm.real_sol_reserves))
+            }
+            GraduationTarget::MarketCap(lamports) => {
+                amm.get_tokens_to_market_cap(lamports as u128, self.token_total_supply as u128)
+            }
+        }
+        .filter(|tokens| *tokens > 0)
+    }
+
+    /// Lamports per base unit of the token as Q64.64 fixed point, the price `CurveOracle` tracks.
+    pub fn spot_price(&self) -> u128 {
+        ((self.virtual_sol_reserves as u128) << 64)
+            .checked_div(self.virtual_token_reserves as u128)
+            .unwrap_or(0)
+    }
+
+    /// A trading or frozen curve that misses its graduation deadline moves
+    /// to `Refunding` and is redeemed pro-rata instead of traded.
+    pub fn missed_deadline(&self, now: i64) -> bool {
+        matches!(self.status, CurveStatus::Trading | CurveStatus::Frozen)
+            && matches!(self.graduation_deadline, Some(deadline) if now >= deadline)
+    }
 }
 
 impl fmt::Display for BondingCurve {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "virtual_sol_reserves: {}, virtual_token_reserves: {}, real_sol_reserves: {}, real_token_reserves: {}, token_total_supply: {}, complete: {}",
+            "version: {}, creator: {}, virtual_sol_reserves: {}, virtual_token_reserves: {}, real_sol_reserves: {}, real_token_reserves: {}, token_total_supply: {}, status: {:?}, start_time: {}, graduation_deadline: {:?}, graduation_target: {:?}",
+            self.version,
+            self.creator,
             self.virtual_sol_reserves,
             self.virtual_token_reserves,
             self.real_sol_reserves,
             self.real_token_reserves,
             self.token_total_supply,
-            self.complete
+            self.status,
+            self.start_time,
+            self.graduation_deadline,
+            self.graduation_target
         )
     }
 }
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+/// A creator's launches, for the rate limit and allowlist `create` enforces.
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorStats {
+    pub version: u8,
+    pub creator: Pubkey,
+    /// Set by the authority, only checked while `Global::creator_allowlist` is on.
+    pub allowed: bool,
+    pub curves_created: u64,
+    pub window_start: i64,
+    /// Launches since `window_start`.
+    pub window_count: u32,
+    /// Zeroed space new fields are carved out of without a realloc.
+    pub reserved: [u8; 64],
+}
+
+impl CreatorStats {
+    pub const SEED_PREFIX: &'static [u8; 13] = b"creator-stats";
+    pub const CURRENT_VERSION: u8 = 1;
+
+    /// Counts a launch at `now` against `limit` launches per `window` seconds,
+    /// `false` if the creator is over the limit. A `limit` of 0 never is.
+    pub fn record_launch(&mut self, now: i64, limit: u32, window: i64) -> bool {
+        //a window starts with the first launch in it
+        if self.window_count == 0 || now >= self.window_start.saturating_add(window) {
+            self.window_start = now;
+            self.window_count = 0;
+        }
+
+        if limit > 0 && self.window_count >= limit {
+            return false;
+        }
+
+        self.window_count += 1;
+        self.curves_created += 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats() -> CreatorStats {
+        CreatorStats {
+            version: CreatorStats::CURRENT_VERSION,
+            creator: Pubkey::new_unique(),
+            allowed: false,
+            curves_created: 0,
+            window_start: 0,
+            window_count: 0,
+            reserved: [0; 64],
+        }
+    }
+
+    #[test]
+    fn test_record_launch() {
+        let mut stats = stats();
+
+        //two an hour
+        assert!(stats.record_launch(1_000, 2, 3_600));
+        assert!(stats.record_launch(1_001, 2, 3_600));
+        assert!(!stats.record_launch(4_599, 2, 3_600));
+        assert_eq!(stats.curves_created, 2);
+
+        //the next window starts with the next launch
+        assert!(stats.record_launch(4_600, 2, 3_600));
+        assert_eq!(stats.window_start, 4_600);
+        assert_eq!(stats.window_count, 1);
+        assert_eq!(stats.curves_created, 3);
+    }
+
+    #[test]
+    fn test_no_limit() {
+        let mut stats = stats();
+
+        for _ in 0..100 {
+            assert!(stats.record_launch(1_000, 0, 0));
+        }
+        assert_eq!(stats.curves_created, 100);
+    }
+}
@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+
+/// The oracle's accumulator as of `timestamp`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct Observation {
+    pub timestamp: i64,
+    pub cumulative_price: u128,
+}
+
+/// Time-weighted price of a curve's token, updated by every buy and sell.
+///
+/// Prices are lamports per base unit of the token as Q64.64 fixed point.
+/// `cumulative_price` adds up the price in effect for each second, so the
+/// difference between two readings divided by the seconds between them is the
+/// TWAP over that time. A price only counts for as long as it holds, so moving
+/// the curve and back within a slot moves the TWAP by nothing.
+#[account]
+#[derive(InitSpace)]
+pub struct CurveOracle {
+    pub version: u8,
+    pub mint: Pubkey,
+    /// Spot price after the last trade.
+    pub price: u128,
+    /// Accumulated up to `last_update`, wraps on overflow.
+    pub cumulative_price: u128,
+    pub last_update: i64,
+    /// Slot in `observations` written last.
+    pub observation_index: u16,
+    pub observation_count: u16,
+    pub observations: [Observation; 32],
+    /// Zeroed space new fields are carved out of without a realloc.
+    pub reserved: [u8; 64],
+}
+
+impl CurveOracle {
+    pub const SEED_PREFIX: &'static [u8; 12] = b"curve-oracle";
+    pub const CURRENT_VERSION: u8 = 1;
+    /// Seconds between observations, so a burst of trades can't cycle out the history.
+    pub const OBSERVATION_INTERVAL: i64 = 60;
+
+    pub fn init(&mut self, mint: Pubkey, price: u128, now: i64) {
+        self.version = Self::CURRENT_VERSION;
+        self.mint = mint;
+        self.price = price;
+        self.cumulative_price = 0;
+        self.last_update = now;
+        self.observation_index = 0;
+        self.observation_count = 1;
+        self.observations[0] = Observation {
+            timestamp: now,
+            cumulative_price: 0,
+        };
+    }
+
+    /// `cumulative_price` as of `now`, with the current price counted up to then.
+    pub fn cumulative_price_at(&self, now: i64) -> u128 {
+        let elapsed = now.saturating_sub(self.last_update).max(0) as u128;
+        self.cumulative_price.wrapping_add(self.price.wrapping_mul(elapsed))
+    }
+
+    /// Counts the last price up to `now` and moves on to `price`, the spot price after a trade.
+    pub fn update(&mut self, price: u128, now: i64) {
+        self.cumulative_price = self.cumulative_price_at(now);
+        self.last_update = self.last_update.max(now);
+        self.price = price;
+
+        let last = self.observations[self.observation_index as usize];
+        if now >= last.timestamp + Self::OBSERVATION_INTERVAL {
+            let index = (self.observation_index as usize + 1) % self.observations.len();
+            self.observations[index] = Observation {
+                timestamp: now,
+                cumulative_price: self.cumulative_price,
+            };
+            self.observation_index = index as u16;
+            self.observation_count = (self.observation_count + 1).min(self.observations.len() as u16);
+        }
+    }
+
+    /// TWAP over the last `window` seconds, stretched back to the newest
+    /// observation at least that old, with the seconds it covers. `None` if
+    /// there's no observation that old.
+    pub fn twap(&self, window: i64, now: i64) -> Option<(u128, i64)> {
+        let observation = self.observations[..self.observation_count as usize]
+            .iter()
+            .filter(|observation| observation.timestamp <= now.saturating_sub(window))
+            .max_by_key(|observation| observation.timestamp)?;
+
+        let elapsed = now - observation.timestamp;
+        if elapsed <= 0 {
+            return None;
+        }
+
+        let price = self
+            .cumulative_price_at(now)
+            .wrapping_sub(observation.cumulative_price)
+            / elapsed as u128;
+
+        Some((price, elapsed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn oracle(price: u128, now: i64) -> CurveOracle {
+        let mut oracle = CurveOracle {
+            version: 0,
+            mint: Pubkey::default(),
+            price: 0,
+            cumulative_price: 0,
+            last_update: 0,
+            observation_index: 0,
+            observation_count: 0,
+            observations: [Observation::default(); 32],
+            reserved: [0; 64],
+        };
+        oracle.init(Pubkey::new_unique(), price, now);
+        oracle
+    }
+
+    #[test]
+    fn test_twap() {
+        let mut oracle = oracle(100, 1_000);
+
+        //100 for 60s, then 400 for 120s
+        oracle.update(400, 1_060);
+        assert_eq!(oracle.twap(180, 1_180), Some(((100 * 60 + 400 * 120) / 180, 180)));
+        assert_eq!(oracle.twap(120, 1_180), Some((400, 120)));
+
+        //a window between observations reaches back to the one before it
+        assert_eq!(oracle.twap(100, 1_180), Some((400, 120)));
+
+        //nothing that old
+        assert_eq!(oracle.twap(181, 1_180), None);
+        assert_eq!(oracle.twap(0, 1_000), None);
+    }
+
+    #[test]
+    fn test_spike_within_a_slot() {
+        let mut oracle = oracle(100, 1_000);
+
+        //pushed up and back at the same time
+        oracle.update(1_000_000, 1_100);
+        oracle.update(100, 1_100);
+
+        assert_eq!(oracle.twap(200, 1_200), Some((100, 200)));
+    }
+
+    #[test]
+    fn test_observation_interval() {
+        let mut oracle = oracle(100, 0);
+
+        //trades more often than the interval only observe once an interval
+        for now in (1..=300).step_by(10) {
+            oracle.update(100, now);
+        }
+        assert_eq!(oracle.observation_count, 5);
+
+        //the ring buffer keeps the newest observations
+        for now in (360..=360 * 60).step_by(60) {
+            oracle.update(100, now);
+        }
+        assert_eq!(oracle.observation_count, 32);
+        let oldest = oracle.observations.iter().map(|observation| observation.timestamp).min().unwrap();
+        assert_eq!(oldest, 360 * 60 - 31 * 60);
+        assert_eq!(oracle.twap(31 * 60, 360 * 60), Some((100, 31 * 60)));
+        assert_eq!(oracle.twap(32 * 60, 360 * 60), None);
+    }
+}
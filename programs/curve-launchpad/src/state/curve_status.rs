@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+
+use crate::{state::BondingCurve, CurveLaunchpadError};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum CurveStatus {
+    /// Created with a start time that has not been reached yet.
+    Scheduled,
+    /// Open for buys and sells.
+    Trading,
+    /// Graduated, waiting for the withdraw authority to migrate the liquidity.
+    Complete,
+    /// Liquidity has been withdrawn for migration.
+    Migrated,
+    /// Missed its graduation deadline, holders redeem pro-rata.
+    Refunding,
+    /// Halted by the global authority.
+    Frozen,
+}
+
+impl CurveStatus {
+    pub fn can_transition_to(self, next: CurveStatus) -> bool {
+        use CurveStatus::*;
+
+        matches!(
+            (self, next),
+            (Scheduled, Trading)
+                | (Scheduled, Frozen)
+                | (Trading, Complete)
+                | (Trading, Refunding)
+                | (Trading, Frozen)
+                | (Frozen, Scheduled)
+                | (Frozen, Trading)
+                | (Frozen, Refunding)
+                | (Complete, Migrated)
+        )
+    }
+
+    //error describing why an instruction allowed in `allowed` can't run in this status
+    fn rejection(self, allowed: &[CurveStatus]) -> CurveLaunchpadError {
+        match self {
+            CurveStatus::Scheduled => CurveLaunchpadError::BondingCurveNotStarted,
+            CurveStatus::Trading if allowed.contains(&CurveStatus::Refunding) => {
+                CurveLaunchpadError::BondingCurveNotRefunding
+            }
+            CurveStatus::Trading => CurveLaunchpadError::BondingCurveNotComplete,
+            CurveStatus::Complete => CurveLaunchpadError::BondingCurveComplete,
+            CurveStatus::Migrated => CurveLaunchpadError::BondingCurveMigrated,
+            CurveStatus::Refunding => CurveLaunchpadError::BondingCurveRefunding,
+            CurveStatus::Frozen => CurveLaunchpadError::BondingCurveFrozen,
+        }
+    }
+}
+
+impl BondingCurve {
+    /// Shared guard for every instruction that acts on a curve.
+    pub fn require_status(&self, allowed: &[CurveStatus]) -> Result<()> {
+        if allowed.contains(&self.status) {
+            return Ok(());
+        }
+
+        Err(self.status.rejection(allowed).into())
+    }
+
+    /// Moves the curve to `next`, returning the status it left.
+    pub fn transition(&mut self, next: CurveStatus) -> Result<CurveStatus> {
+        let previous = self.status;
+
+        require!(
+            previous.can_transition_to(next),
+            CurveLaunchpadError::InvalidStatusTransition
+        );
+
+        self.status = next;
+
+        Ok(previous)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CurveStatus::{self, *};
+
+    const ALL: [CurveStatus; 6] = [Scheduled, Trading, Complete, Migrated, Refunding, Frozen];
+
+    #[test]
+    fn test_can_transition_to() {
+        assert!(Scheduled.can_transition_to(Trading));
+        assert!(Trading.can_transition_to(Complete));
+        assert!(Trading.can_transition_to(Refunding));
+        assert!(Complete.can_transition_to(Migrated));
+        assert!(Trading.can_transition_to(Frozen));
+        assert!(Frozen.can_transition_to(Trading));
+        assert!(Frozen.can_transition_to(Refunding));
+
+        assert!(!Trading.can_transition_to(Scheduled));
+        assert!(!Complete.can_transition_to(Trading));
+        assert!(!Complete.can_transition_to(Frozen));
+        assert!(!Refunding.can_transition_to(Trading));
+        assert!(!Frozen.can_transition_to(Complete));
+    }
+
+    #[test]
+    fn test_terminal_statuses() {
+        for next in ALL {
+            assert!(!Migrated.can_transition_to(next));
+            assert!(!Refunding.can_transition_to(next));
+        }
+    }
+
+    #[test]
+    fn test_no_self_transitions() {
+        for status in ALL {
+            assert!(!status.can_transition_to(status));
+        }
+    }
+}
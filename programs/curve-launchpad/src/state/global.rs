@@ -1,10 +1,22 @@
 use anchor_lang::prelude::*;
 
+use crate::DEFAULT_DECIMALS;
 
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// A new curve's starting reserves and supply, in base units of its mint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CurveReserves {
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_token_reserves: u64,
+    pub token_supply: u64,
+}
 
 #[account]
 #[derive(InitSpace)]
 pub struct Global {
+    pub version: u8,
     pub authority: Pubkey,
     pub initialized: bool,
     pub fee_recipient: Pubkey,
@@ -15,8 +27,170 @@ pub struct Global {
     pub initial_token_supply: u64,
     pub fee_basis_points: u64,
     pub withdraw_authority: Pubkey,
+    /// Paid to `fee_recipient` by every `create`.
+    pub creation_fee_lamports: u64,
+    /// Curves a creator can launch per `creator_rate_limit_window` seconds, 0 for no limit.
+    pub creator_rate_limit: u32,
+    pub creator_rate_limit_window: i64,
+    /// Only creators the authority has allowed can launch.
+    pub creator_allowlist: bool,
+    /// Base58 suffix launched mints must end in, zero padded, all zeros for any mint.
+    pub mint_suffix: [u8; 8],
+    /// Zeroed space new fields are carved out of without a realloc.
+    pub reserved: [u8; 227],
 }
 
 impl Global {
    pub const SEED_PREFIX: &'static [u8; 6] = b"global";
-}
\ No newline at end of file
+   pub const CURRENT_VERSION: u8 = 1;
+
+    pub fn mint_suffix(&self) -> &str {
+        let len = self.mint_suffix.iter().position(|byte| *byte == 0).unwrap_or(self.mint_suffix.len());
+        std::str::from_utf8(&self.mint_suffix[..len]).unwrap_or_default()
+    }
+
+    /// Sets the suffix, `false` if it's too long or not base58.
+    pub fn set_mint_suffix(&mut self, suffix: &str) -> bool {
+        let base58 = suffix.bytes().all(|byte| BASE58_ALPHABET.contains(&byte));
+        if !base58 || suffix.len() > self.mint_suffix.len() {
+            return false;
+        }
+
+        self.mint_suffix = [0; 8];
+        self.mint_suffix[..suffix.len()].copy_from_slice(suffix.as_bytes());
+        true
+    }
+
+    pub fn is_vanity_mint(&self, mint: &Pubkey) -> bool {
+        let suffix = self.mint_suffix();
+        suffix.is_empty() || mint.to_string().ends_with(suffix)
+    }
+
+    /// The `initial_*` template, which is in `DEFAULT_DECIMALS`, scaled to a
+    /// mint with `decimals` and `token_supply` base units, the template's
+    /// number of whole tokens if unset. The token reserves keep their share
+    /// of the supply and a whole token starts at the same price in SOL,
+    /// rounded down. `None` if the curve would be empty or overflow.
+    pub fn scaled_reserves(&self, decimals: u8, token_supply: Option<u64>) -> Option<CurveReserves> {
+        let template_unit = 10u128.pow(DEFAULT_DECIMALS);
+        let unit = 10u128.checked_pow(decimals as u32)?;
+        let template_supply = self.initial_token_supply as u128;
+
+        let token_supply = match token_supply {
+            Some(token_supply) => token_supply as u128,
+            None => template_supply.checked_mul(unit)? / template_unit,
+        };
+
+        let scale = |amount: u64, numerator: u128, denominator: u128| -> Option<u64> {
+            let scaled = (amount as u128).checked_mul(numerator)?.checked_div(denominator)?;
+            u64::try_from(scaled).ok().filter(|scaled| *scaled > 0)
+        };
+
+        Some(CurveReserves {
+            virtual_sol_reserves: scale(
+                self.initial_virtual_sol_reserves,
+                token_supply.checked_mul(template_unit)?,
+                template_supply.checked_mul(unit)?,
+            )?,
+            virtual_token_reserves: scale(self.initial_virtual_token_reserves, token_supply, template_supply)?,
+            real_token_reserves: scale(self.initial_real_token_reserves, token_supply, template_supply)?,
+            token_supply: u64::try_from(token_supply).ok().filter(|supply| *supply > 0)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template() -> Global {
+        Global {
+            version: Global::CURRENT_VERSION,
+            authority: Pubkey::default(),
+            initialized: true,
+            fee_recipient: Pubkey::default(),
+            initial_virtual_token_reserves: 1_073_000_000_000_000,
+            initial_virtual_sol_reserves: 30_000_000_000,
+            initial_real_token_reserves: 793_100_000_000_000,
+            initial_real_sol_reserves: 0,
+            initial_token_supply: 1_000_000_000_000_000,
+            fee_basis_points: 50,
+            withdraw_authority: Pubkey::default(),
+            creation_fee_lamports: 0,
+            creator_rate_limit: 0,
+            creator_rate_limit_window: 0,
+            creator_allowlist: false,
+            mint_suffix: [0; 8],
+            reserved: [0; 227],
+        }
+    }
+
+    //lamports per whole token at the start of the curve
+    fn start_price(reserves: &CurveReserves, decimals: u8) -> f64 {
+        reserves.virtual_sol_reserves as f64 / reserves.virtual_token_reserves as f64 * 10f64.powi(decimals as i32)
+    }
+
+    #[test]
+    fn test_scaled_reserves_default() {
+        let global = template();
+
+        assert_eq!(
+            global.scaled_reserves(6, None),
+            Some(CurveReserves {
+                virtual_sol_reserves: global.initial_virtual_sol_reserves,
+                virtual_token_reserves: global.initial_virtual_token_reserves,
+                real_token_reserves: global.initial_real_token_reserves,
+                token_supply: global.initial_token_supply,
+            })
+        );
+    }
+
+    #[test]
+    fn test_scaled_reserves() {
+        let global = template();
+        let template_price = start_price(&global.scaled_reserves(6, None).unwrap(), 6);
+
+        //same whole tokens at 9 decimals
+        let reserves = global.scaled_reserves(9, None).unwrap();
+        assert_eq!(reserves.token_supply, 1_000_000_000_000_000_000);
+        assert_eq!(reserves.virtual_token_reserves, 1_073_000_000_000_000_000);
+        assert_eq!(reserves.virtual_sol_reserves, 30_000_000_000);
+        assert!((start_price(&reserves, 9) - template_price).abs() / template_price < 1e-9);
+
+        //a tenth of the supply at 0 decimals, the price holds and the SOL side shrinks with it
+        let reserves = global.scaled_reserves(0, Some(100_000_000)).unwrap();
+        assert_eq!(reserves.real_token_reserves, 79_310_000);
+        assert_eq!(reserves.virtual_sol_reserves, 3_000_000_000);
+        assert!((start_price(&reserves, 0) - template_price).abs() / template_price < 1e-9);
+
+        //nothing to sell, or more than a u64
+        assert_eq!(global.scaled_reserves(6, Some(0)), None);
+        assert_eq!(global.scaled_reserves(6, Some(1)), None);
+        assert_eq!(global.scaled_reserves(12, None), None);
+    }
+
+    #[test]
+    fn test_mint_suffix() {
+        let mut global = template();
+        let mint = Pubkey::new_unique();
+        let ending = &mint.to_string()[40..];
+
+        //unset takes any mint
+        assert_eq!(global.mint_suffix(), "");
+        assert!(global.is_vanity_mint(&mint));
+
+        assert!(global.set_mint_suffix(ending));
+        assert_eq!(global.mint_suffix(), ending);
+        assert!(global.is_vanity_mint(&mint));
+        assert!(!global.is_vanity_mint(&Pubkey::new_unique()));
+
+        //too long or outside base58 leaves it alone
+        assert!(!global.set_mint_suffix("123456789"));
+        assert!(!global.set_mint_suffix("pump0"));
+        assert!(!global.set_mint_suffix("lOl"));
+        assert_eq!(global.mint_suffix(), ending);
+
+        assert!(global.set_mint_suffix(""));
+        assert_eq!(global.mint_suffix, [0; 8]);
+    }
+}
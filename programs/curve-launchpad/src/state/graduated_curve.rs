@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+/// Left behind when a migrated curve is closed so indexers can still find it.
+#[account]
+#[derive(InitSpace)]
+pub struct GraduatedCurve {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub token_total_supply: u64,
+    pub closed_at: i64,
+}
+
+impl GraduatedCurve {
+    pub const SEED_PREFIX: &'static [u8; 15] = b"graduated-curve";
+}
@@ -0,0 +1,218 @@
+use anchor_lang::{prelude::*, Discriminator};
+
+use crate::{
+    state::{BondingCurve, CurveStatus, Global},
+    CurveLaunchpadError,
+};
+
+/// `Global` as laid out before accounts were versioned.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct GlobalV0 {
+    pub authority: Pubkey,
+    pub initialized: bool,
+    pub fee_recipient: Pubkey,
+    pub initial_virtual_token_reserves: u64,
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_real_token_reserves: u64,
+    pub initial_real_sol_reserves: u64,
+    pub initial_token_supply: u64,
+    pub fee_basis_points: u64,
+    pub withdraw_authority: Pubkey,
+}
+
+/// `BondingCurve` as laid out before accounts were versioned.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct BondingCurveV0 {
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub token_total_supply: u64,
+    pub complete: bool,
+}
+
+impl From<GlobalV0> for Global {
+    fn from(legacy: GlobalV0) -> Self {
+        Global {
+            version: Global::CURRENT_VERSION,
+            authority: legacy.authority,
+            initialized: legacy.initialized,
+            fee_recipient: legacy.fee_recipient,
+            initial_virtual_token_reserves: legacy.initial_virtual_token_reserves,
+            initial_virtual_sol_reserves: legacy.initial_virtual_sol_reserves,
+            initial_real_token_reserves: legacy.initial_real_token_reserves,
+            initial_real_sol_reserves: legacy.initial_real_sol_reserves,
+            initial_token_supply: legacy.initial_token_supply,
+            fee_basis_points: legacy.fee_basis_points,
+            withdraw_authority: legacy.withdraw_authority,
+            creation_fee_lamports: 0,
+            creator_rate_limit: 0,
+            creator_rate_limit_window: 0,
+            creator_allowlist: false,
+            mint_suffix: [0; 8],
+            reserved: [0; 227],
+        }
+    }
+}
+
+impl From<BondingCurveV0> for BondingCurve {
+    fn from(legacy: BondingCurveV0) -> Self {
+        //unversioned curves didn't record a creator or start time and had no deadline or target,
+        //`migrate_curve` takes the creator from the authority
+        BondingCurve {
+            version: BondingCurve::CURRENT_VERSION,
+            creator: Pubkey::default(),
+            virtual_sol_reserves: legacy.virtual_sol_reserves,
+            virtual_token_reserves: legacy.virtual_token_reserves,
+            real_sol_reserves: legacy.real_sol_reserves,
+            real_token_reserves: legacy.real_token_reserves,
+            token_total_supply: legacy.token_total_supply,
+            status: if legacy.complete {
+                CurveStatus::Complete
+            } else {
+                CurveStatus::Trading
+            },
+            start_time: 0,
+            graduation_deadline: None,
+            graduation_target: None,
+            reserved: [0; 128],
+        }
+    }
+}
+
+/// Decodes `Global` account data in any known layout, `None` if it is already current.
+pub fn upgrade_global(data: &[u8]) -> Result<Option<Global>> {
+    upgrade::<Global, GlobalV0>(data, 8 + GlobalV0::INIT_SPACE, 8 + Global::INIT_SPACE, Global::CURRENT_VERSION)
+}
+
+/// Decodes `BondingCurve` account data in any known layout, `None` if it is already current.
+pub fn upgrade_bonding_curve(data: &[u8]) -> Result<Option<BondingCurve>> {
+    upgrade::<BondingCurve, BondingCurveV0>(
+        data,
+        8 + BondingCurveV0::INIT_SPACE,
+        8 + BondingCurve::INIT_SPACE,
+        BondingCurve::CURRENT_VERSION,
+    )
+}
+
+//unversioned accounts have exactly the legacy length, versioned ones carry their version right after the discriminator
+fn upgrade<T: Discriminator, V0: AnchorDeserialize + Into<T>>(
+    data: &[u8],
+    legacy_len: usize,
+    current_len: usize,
+    current_version: u8,
+) -> Result<Option<T>> {
+    require!(
+        data.len() >= 8 && data[..8] == T::DISCRIMINATOR,
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+
+    if data.len() == legacy_len {
+        let legacy = V0::deserialize(&mut &data[8..])
+            .map_err(|_| error!(CurveLaunchpadError::UnknownAccountVersion))?;
+
+        return Ok(Some(legacy.into()));
+    }
+
+    require!(
+        data.len() >= current_len && data[8] == current_version,
+        CurveLaunchpadError::UnknownAccountVersion
+    );
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::GraduationTarget;
+
+    const GLOBAL_V0: &[u8] = include_bytes!("../../tests/fixtures/global_v0.bin");
+    const BONDING_CURVE_V0: &[u8] = include_bytes!("../../tests/fixtures/bonding_curve_v0.bin");
+
+    #[test]
+    fn test_upgrade_global() {
+        let global = upgrade_global(GLOBAL_V0).unwrap().unwrap();
+
+        assert_eq!(global.version, Global::CURRENT_VERSION);
+        assert_eq!(global.authority, Pubkey::new_from_array([1; 32]));
+        assert!(global.initialized);
+        assert_eq!(global.fee_recipient, Pubkey::new_from_array([2; 32]));
+        assert_eq!(global.initial_virtual_token_reserves, 1_073_000_000_000_000);
+        assert_eq!(global.initial_virtual_sol_reserves, 30_000_000_000);
+        assert_eq!(global.initial_real_token_reserves, 793_100_000_000_000);
+        assert_eq!(global.initial_real_sol_reserves, 0);
+        assert_eq!(global.initial_token_supply, 1_000_000_000_000_000);
+        assert_eq!(global.fee_basis_points, 50);
+        assert_eq!(global.withdraw_authority, Pubkey::new_from_array([3; 32]));
+        assert_eq!(global.creation_fee_lamports, 0);
+        assert!(!global.creator_allowlist);
+        assert_eq!(global.mint_suffix(), "");
+        assert_eq!(global.reserved, [0; 227]);
+
+        //written back in place it reads as a current account
+        let mut data = vec![0u8; 8 + Global::INIT_SPACE];
+        global.try_serialize(&mut &mut data[..]).unwrap();
+        let reloaded = Global::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(reloaded.withdraw_authority, global.withdraw_authority);
+        assert_eq!(upgrade_global(&data).unwrap().map(|g| g.version), None);
+    }
+
+    #[test]
+    fn test_upgrade_bonding_curve() {
+        let bonding_curve = upgrade_bonding_curve(BONDING_CURVE_V0).unwrap().unwrap();
+
+        assert_eq!(bonding_curve.version, BondingCurve::CURRENT_VERSION);
+        assert_eq!(bonding_curve.creator, Pubkey::default());
+        assert_eq!(bonding_curve.virtual_sol_reserves, 31_000_000_000);
+        assert_eq!(bonding_curve.virtual_token_reserves, 1_038_387_096_774_194);
+        assert_eq!(bonding_curve.real_sol_reserves, 1_000_000_000);
+        assert_eq!(bonding_curve.real_token_reserves, 758_487_096_774_194);
+        assert_eq!(bonding_curve.token_total_supply, 1_000_000_000_000_000);
+        assert_eq!(bonding_curve.status, CurveStatus::Trading);
+        assert_eq!(bonding_curve.start_time, 0);
+        assert_eq!(bonding_curve.graduation_deadline, None);
+        assert_eq!(bonding_curve.graduation_target, None);
+        assert_eq!(bonding_curve.reserved, [0; 128]);
+
+        //the trailing byte is the baseline `complete` flag
+        let mut complete = BONDING_CURVE_V0.to_vec();
+        *complete.last_mut().unwrap() = 1;
+        let completed = upgrade_bonding_curve(&complete).unwrap().unwrap();
+        assert_eq!(completed.status, CurveStatus::Complete);
+
+        let mut bonding_curve = bonding_curve;
+        bonding_curve.graduation_target = Some(GraduationTarget::SolRaised(85_000_000_000));
+
+        let mut data = vec![0u8; 8 + BondingCurve::INIT_SPACE];
+        bonding_curve.try_serialize(&mut &mut data[..]).unwrap();
+        let reloaded = BondingCurve::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(reloaded.graduation_target, bonding_curve.graduation_target);
+        assert_eq!(upgrade_bonding_curve(&data).unwrap().map(|c| c.version), None);
+    }
+
+    #[test]
+    fn test_upgrade_rejects_other_accounts() {
+        assert!(upgrade_global(BONDING_CURVE_V0).is_err());
+        assert!(upgrade_bonding_curve(GLOBAL_V0).is_err());
+        assert!(upgrade_global(&GLOBAL_V0[..4]).is_err());
+    }
+
+    #[test]
+    fn test_upgrade_rejects_truncated_data() {
+        assert!(upgrade_bonding_curve(&BONDING_CURVE_V0[..40]).is_err());
+    }
+
+    #[test]
+    fn test_upgrade_rejects_unknown_versions() {
+        let bonding_curve = upgrade_bonding_curve(BONDING_CURVE_V0).unwrap().unwrap();
+
+        let mut data = vec![0u8; 8 + BondingCurve::INIT_SPACE];
+        bonding_curve.try_serialize(&mut &mut data[..]).unwrap();
+        data[8] = BondingCurve::CURRENT_VERSION + 1;
+        assert!(upgrade_bonding_curve(&data).is_err());
+
+        //neither the legacy nor the current length
+        assert!(upgrade_bonding_curve(&data[..8 + BondingCurveV0::INIT_SPACE + 1]).is_err());
+    }
+}
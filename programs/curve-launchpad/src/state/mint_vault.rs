@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+pub const MINT_VAULT_CAPACITY: usize = 32;
+
+/// Mints the authority created ahead of time, e.g. ground for a vanity suffix,
+/// for `create` to launch. Any queued mint can be launched, so one that no
+/// longer matches the mint suffix doesn't hold up the rest. Each is an initialized mint with
+/// `DEFAULT_DECIMALS` and the program's mint authority.
+#[account]
+#[derive(InitSpace)]
+pub struct MintVault {
+    #[max_len(MINT_VAULT_CAPACITY)]
+    pub mints: Vec<Pubkey>,
+}
+
+impl MintVault {
+    pub const SEED_PREFIX: &'static [u8; 10] = b"mint-vault";
+
+    /// `false` if the vault is full.
+    pub fn push(&mut self, mint: Pubkey) -> bool {
+        if self.mints.len() >= MINT_VAULT_CAPACITY {
+            return false;
+        }
+
+        self.mints.push(mint);
+        true
+    }
+
+    /// Takes `mint` out of the vault, `false` if it isn't queued.
+    pub fn take(&mut self, mint: &Pubkey) -> bool {
+        match self.mints.iter().position(|queued| queued == mint) {
+            Some(index) => {
+                self.mints.swap_remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mint_vault() {
+        let mut vault = MintVault { mints: vec![] };
+        let mints: Vec<Pubkey> = (0..MINT_VAULT_CAPACITY).map(|_| Pubkey::new_unique()).collect();
+
+        for mint in &mints {
+            assert!(vault.push(*mint));
+        }
+        assert!(!vault.push(Pubkey::new_unique()));
+
+        //any queued mint, but only once
+        assert!(vault.take(&mints[1]));
+        assert!(!vault.take(&mints[1]));
+        assert!(vault.take(&mints[0]));
+        assert!(!vault.take(&Pubkey::new_unique()));
+        assert_eq!(vault.mints.len(), MINT_VAULT_CAPACITY - 2);
+        assert!(mints[2..].iter().all(|mint| vault.mints.contains(mint)));
+
+        assert!(vault.push(Pubkey::new_unique()));
+    }
+}
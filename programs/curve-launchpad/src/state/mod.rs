@@ -1,7 +1,19 @@
 pub mod global;
 pub mod bonding_curve;
 pub mod last_withdraw;
+pub mod curve_status;
+pub mod graduated_curve;
+pub mod legacy;
+pub mod curve_oracle;
+pub mod creator_stats;
+pub mod mint_vault;
 
 pub use global::*;
 pub use bonding_curve::*;
-pub use last_withdraw::*;
\ No newline at end of file
+pub use last_withdraw::*;
+pub use curve_status::*;
+pub use graduated_curve::*;
+pub use legacy::*;
+pub use curve_oracle::*;
+pub use creator_stats::*;
+pub use mint_vault::*;
\ No newline at end of file
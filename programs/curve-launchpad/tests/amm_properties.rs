@@ -0,0 +1,184 @@
+//! Invariants of the curve math over random trades at mainnet-scale reserves.
+
+use curve_launchpad::amm::AMM;
+use proptest::prelude::*;
+
+const INITIAL_VIRTUAL_SOL_RESERVES: u128 = 30_000_000_000;
+const INITIAL_VIRTUAL_TOKEN_RESERVES: u128 = 1_073_000_000_000_000;
+const INITIAL_REAL_TOKEN_RESERVES: u128 = 793_100_000_000_000;
+const TOKEN_TOTAL_SUPPLY: u128 = 1_000_000_000_000_000;
+
+fn mainnet_amm() -> AMM {
+    AMM::new(
+        INITIAL_VIRTUAL_SOL_RESERVES,
+        INITIAL_VIRTUAL_TOKEN_RESERVES,
+        0,
+        INITIAL_REAL_TOKEN_RESERVES,
+    )
+}
+
+#[derive(Clone, Debug)]
+enum Trade {
+    Buy(u64),
+    Sell(u64),
+}
+
+fn trade() -> impl Strategy<Value = Trade> {
+    prop_oneof![
+        (1..=INITIAL_REAL_TOKEN_RESERVES as u64).prop_map(Trade::Buy),
+        any::<u64>().prop_map(Trade::Sell),
+    ]
+}
+
+/// Replays `trades` from launch, selling only tokens bought earlier the way
+/// the program only lets holders sell. Returns the curve and the tokens held.
+fn replay(trades: &[Trade]) -> (AMM, u128) {
+    let mut amm = mainnet_amm();
+    let mut held = 0;
+
+    for trade in trades {
+        match *trade {
+            Trade::Buy(tokens) => {
+                if let Some(result) = amm.apply_buy(tokens as u128) {
+                    held += result.token_amount as u128;
+                }
+            }
+            Trade::Sell(tokens) => {
+                let tokens = tokens as u128 % (held + 1);
+                if tokens > 0 && amm.apply_sell(tokens).is_some() {
+                    held -= tokens;
+                }
+            }
+        }
+    }
+
+    (amm, held)
+}
+
+fn k(amm: &AMM) -> u128 {
+    amm.virtual_sol_reserves * amm.virtual_token_reserves
+}
+
+proptest! {
+    #[test]
+    fn buy_then_sell_never_profits(
+        trades in prop::collection::vec(trade(), 0..20),
+        tokens in 1..=INITIAL_REAL_TOKEN_RESERVES as u64,
+    ) {
+        let (mut amm, _) = replay(&trades);
+
+        if let Some(bought) = amm.apply_buy(tokens as u128) {
+            if bought.token_amount > 0 {
+                let sold = amm.apply_sell(bought.token_amount as u128).unwrap();
+                prop_assert!(sold.sol_amount <= bought.sol_amount);
+            }
+        }
+    }
+
+    #[test]
+    fn k_never_decreases(trades in prop::collection::vec(trade(), 1..40)) {
+        let mut amm = mainnet_amm();
+        let mut held = 0;
+
+        for trade in trades {
+            let before = k(&amm);
+
+            match trade {
+                Trade::Buy(tokens) => {
+                    if let Some(result) = amm.apply_buy(tokens as u128) {
+                        held += result.token_amount as u128;
+                    }
+                }
+                Trade::Sell(tokens) => {
+                    let tokens = tokens as u128 % (held + 1);
+                    if tokens > 0 && amm.apply_sell(tokens).is_some() {
+                        held -= tokens;
+                    }
+                }
+            }
+
+            prop_assert!(k(&amm) >= before, "k fell from {} to {} on {:?}", before, k(&amm), trade);
+        }
+    }
+
+    #[test]
+    fn real_reserves_never_exceed_virtual(trades in prop::collection::vec(trade(), 0..40)) {
+        let (amm, held) = replay(&trades);
+
+        prop_assert!(amm.real_sol_reserves <= amm.virtual_sol_reserves);
+        prop_assert!(amm.real_token_reserves <= amm.virtual_token_reserves);
+        prop_assert_eq!(amm.real_token_reserves + held, INITIAL_REAL_TOKEN_RESERVES);
+    }
+
+    #[test]
+    fn no_panics_on_any_input(
+        trades in prop::collection::vec(trade(), 0..10),
+        amount in any::<u64>(),
+    ) {
+        let (amm, _) = replay(&trades);
+        let amount = amount as u128;
+
+        let _ = amm.get_buy_price(amount);
+        let _ = amm.get_sell_price(amount);
+        let _ = amm.get_tokens_to_raise(amount);
+        let _ = amm.get_market_cap(TOKEN_TOTAL_SUPPLY);
+        let _ = amm.get_tokens_to_market_cap(amount, TOKEN_TOTAL_SUPPLY);
+
+        let (mut buyer, _) = replay(&trades);
+        let _ = buyer.apply_buy(amount);
+
+        let (mut seller, _) = replay(&trades);
+        let _ = seller.apply_sell(amount);
+    }
+
+    #[test]
+    fn selling_everything_back_returns_at_most_what_was_paid(trades in prop::collection::vec(trade(), 0..40)) {
+        let (mut amm, held) = replay(&trades);
+
+        if held > 0 {
+            amm.apply_sell(held).unwrap();
+        }
+
+        //every token is back, so any SOL left over is what rounding kept for the curve
+        prop_assert_eq!(amm.real_token_reserves, INITIAL_REAL_TOKEN_RESERVES);
+        prop_assert_eq!(amm.virtual_token_reserves, INITIAL_VIRTUAL_TOKEN_RESERVES);
+        prop_assert!(amm.virtual_sol_reserves >= INITIAL_VIRTUAL_SOL_RESERVES);
+    }
+
+    #[test]
+    fn splitting_a_buy_never_costs_less(
+        trades in prop::collection::vec(trade(), 0..10),
+        first in 1..INITIAL_REAL_TOKEN_RESERVES as u64 / 2,
+        second in 1..INITIAL_REAL_TOKEN_RESERVES as u64 / 2,
+    ) {
+        let (whole, _) = replay(&trades);
+        let (mut split, _) = replay(&trades);
+
+        let tokens = (first + second) as u128;
+        prop_assume!(tokens <= whole.real_token_reserves);
+
+        let whole_cost = whole.get_buy_price(tokens).unwrap();
+        let split_cost = split.apply_buy(first as u128).unwrap().sol_amount as u128
+            + split.apply_buy(second as u128).unwrap().sol_amount as u128;
+
+        prop_assert!(split_cost >= whole_cost);
+    }
+
+    #[test]
+    fn splitting_a_sell_never_pays_more(
+        trades in prop::collection::vec(trade(), 1..20),
+        first in any::<u64>(),
+    ) {
+        let (mut whole, held) = replay(&trades);
+        let (mut split, _) = replay(&trades);
+        prop_assume!(held > 1);
+
+        let first = 1 + first as u128 % (held - 1);
+
+        let whole_proceeds = whole.apply_sell(held).unwrap().sol_amount;
+        let split_proceeds = split.apply_sell(first).unwrap().sol_amount
+            + split.apply_sell(held - first).unwrap().sol_amount;
+
+        prop_assert!(split_proceeds <= whole_proceeds);
+    }
+}
@@ -0,0 +1,187 @@
+//! Pool creation against `dynamic-amm-mock`, which stands in for the dynamic
+//! AMM at its real address.
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, system_instruction},
+    InstructionData, ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token::get_associated_token_address,
+    token::spl_token,
+};
+use curve_launchpad::{accounts, dynamic_amm};
+use curve_launchpad_client::{amm as client_amm, instructions};
+use dynamic_amm_mock::{
+    pda as amm_pda,
+    state::{Config, LockEscrow, Pool, PoolFees},
+};
+use solana_program_test::BanksClientError;
+use solana_sdk::{account::Account, signature::Keypair, signer::Signer};
+
+use super::TestEnv;
+
+pub const NATIVE_MINT: Pubkey = spl_token::native_mint::ID;
+
+/// 0.25% trade fee, a fifth of it to the protocol.
+pub fn default_pool_fees() -> PoolFees {
+    PoolFees {
+        trade_fee_numerator: 25,
+        trade_fee_denominator: 10_000,
+        protocol_trade_fee_numerator: 20,
+        protocol_trade_fee_denominator: 100,
+    }
+}
+
+/// The client's accounts for creating the WSOL / `mint` pool, with the
+/// token vaults the mock owns in place of the vault program's.
+pub fn create_pool_accounts(payer: &Pubkey, config: &Pubkey, mint: &Pubkey) -> accounts::CreatePool {
+    let accounts = client_amm::create_pool_accounts(payer, config, mint);
+
+    accounts::CreatePool {
+        a_token_vault: amm_pda::token_vault(&accounts.pool, &NATIVE_MINT),
+        b_token_vault: amm_pda::token_vault(&accounts.pool, mint),
+        ..accounts
+    }
+}
+
+/// Swaps `in_amount` of `source_mint` for the pool's other token.
+pub fn swap(
+    user: &Pubkey,
+    pool_address: &Pubkey,
+    pool: &Pool,
+    source_mint: &Pubkey,
+    in_amount: u64,
+    minimum_out_amount: u64,
+) -> Instruction {
+    let (destination_mint, protocol_token_fee) = if *source_mint == pool.token_a_mint {
+        (pool.token_b_mint, pool.protocol_token_a_fee)
+    } else {
+        (pool.token_a_mint, pool.protocol_token_b_fee)
+    };
+
+    amm_instruction(
+        dynamic_amm_mock::accounts::Swap {
+            pool: *pool_address,
+            user_source_token: get_associated_token_address(user, source_mint),
+            user_destination_token: get_associated_token_address(user, &destination_mint),
+            a_vault: pool.a_vault,
+            b_vault: pool.b_vault,
+            a_token_vault: pool.a_token_vault,
+            b_token_vault: pool.b_token_vault,
+            a_vault_lp_mint: pool.a_vault_lp_mint,
+            b_vault_lp_mint: pool.b_vault_lp_mint,
+            a_vault_lp: pool.a_vault_lp,
+            b_vault_lp: pool.b_vault_lp,
+            protocol_token_fee,
+            user: *user,
+            vault_program: amm_pda::VAULT_PROGRAM_ID,
+            token_program: spl_token::ID,
+        },
+        dynamic_amm_mock::instruction::Swap { in_amount, minimum_out_amount },
+    )
+}
+
+/// Claims up to `max_amount` of each token `owner`'s lock escrow has earned.
+pub fn claim_fee(owner: &Pubkey, pool_address: &Pubkey, pool: &Pool, max_amount: u64) -> Instruction {
+    let lock_escrow = amm_pda::lock_escrow(pool_address, owner);
+
+    amm_instruction(
+        dynamic_amm_mock::accounts::ClaimFee {
+            pool: *pool_address,
+            lp_mint: pool.lp_mint,
+            lock_escrow,
+            owner: *owner,
+            source_tokens: get_associated_token_address(owner, &pool.lp_mint),
+            escrow_vault: get_associated_token_address(&lock_escrow, &pool.lp_mint),
+            token_program: spl_token::ID,
+            a_token_vault: pool.a_token_vault,
+            b_token_vault: pool.b_token_vault,
+            a_vault: pool.a_vault,
+            b_vault: pool.b_vault,
+            a_vault_lp: pool.a_vault_lp,
+            b_vault_lp: pool.b_vault_lp,
+            a_vault_lp_mint: pool.a_vault_lp_mint,
+            b_vault_lp_mint: pool.b_vault_lp_mint,
+            user_a_token: get_associated_token_address(owner, &pool.token_a_mint),
+            user_b_token: get_associated_token_address(owner, &pool.token_b_mint),
+            vault_program: amm_pda::VAULT_PROGRAM_ID,
+        },
+        dynamic_amm_mock::instruction::ClaimFee { max_amount },
+    )
+}
+
+fn amm_instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: dynamic_amm::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+impl TestEnv {
+    /// Writes an AMM config straight into the chain, the mock can't create them.
+    pub async fn set_amm_config(&mut self, pool_fees: PoolFees, pool_creator_authority: Pubkey) -> Pubkey {
+        let config = Config {
+            pool_fees,
+            activation_duration: 0,
+            vault_config_key: Pubkey::default(),
+            pool_creator_authority,
+            activation_type: 0,
+            padding: [0; 227],
+        };
+
+        let mut data = Vec::new();
+        config.try_serialize(&mut data).unwrap();
+
+        let address = Pubkey::new_unique();
+        let lamports = self.minimum_balance(data.len()).await;
+        self.context.set_account(
+            &address,
+            &Account {
+                lamports,
+                data,
+                owner: dynamic_amm::ID,
+                executable: false,
+                rent_epoch: 0,
+            }
+            .into(),
+        );
+
+        address
+    }
+
+    /// Moves `lamports` of `owner`'s SOL into its WSOL account.
+    pub async fn wrap_sol(&mut self, owner: &Keypair, lamports: u64) {
+        let address = self.create_token_account(&owner.pubkey(), &NATIVE_MINT).await;
+
+        self.send(
+            &[
+                system_instruction::transfer(&owner.pubkey(), &address, lamports),
+                spl_token::instruction::sync_native(&spl_token::ID, &address).unwrap(),
+            ],
+            &[owner],
+        )
+        .await
+        .unwrap();
+    }
+
+    /// Creates the WSOL / `mint` pool from the withdraw authority's balances.
+    pub async fn create_pool(&mut self, mint: &Pubkey, config: &Pubkey) -> std::result::Result<(), BanksClientError> {
+        let withdraw_authority = self.withdraw_authority.insecure_clone();
+        let accounts = create_pool_accounts(&withdraw_authority.pubkey(), config, mint);
+
+        self.send(&[instructions::create_pool(accounts)], &[&withdraw_authority])
+            .await
+    }
+
+    pub async fn amm_pool(&mut self, address: &Pubkey) -> Pool {
+        let data = self.account_data(address).await;
+        Pool::try_deserialize(&mut &data[..]).unwrap()
+    }
+
+    pub async fn lock_escrow(&mut self, address: &Pubkey) -> LockEscrow {
+        let data = self.account_data(address).await;
+        LockEscrow::try_deserialize(&mut &data[..]).unwrap()
+    }
+}
@@ -0,0 +1,475 @@
+//! In-process test environment for the launchpad, built on solana-program-test.
+//!
+//! The program runs natively so the suite needs no `cargo build-sbf`. Metaplex
+//! runs on the BPF loader from `tests/fixtures/metaplex_metadata.so`, a dump of
+//! the deployed program, embedded so other crates' tests can share this module. The dynamic AMM is the `dynamic-amm-mock` program, run
+//! natively at the AMM's address.
+//! `launchpad-caller`, the example CPI caller, is loaded alongside.
+
+#![allow(dead_code)]
+
+pub mod amm;
+
+use std::sync::Mutex;
+
+use anchor_lang::{
+    event::EVENT_IX_TAG_LE,
+    prelude::*,
+    solana_program::{
+        entrypoint::ProgramResult,
+        instruction::{Instruction, InstructionError},
+        program_pack::Pack,
+        system_instruction,
+    },
+    AnchorDeserialize, Discriminator,
+};
+use anchor_spl::{
+    associated_token::{
+        get_associated_token_address,
+        spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+    },
+    metadata::mpl_token_metadata,
+    token::spl_token,
+};
+use curve_launchpad::{
+    dynamic_amm, instruction,
+    instructions::CurveLaunchpadError,
+    state::{BondingCurve, CurveOracle, Global, GraduationTarget},
+};
+use curve_launchpad_client::{accounts, instructions, pda};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+};
+
+pub const DEFAULT_DECIMALS: u8 = 6;
+pub const DEFAULT_TOKEN_BALANCE: u64 = 1_000_000_000 * 10u64.pow(DEFAULT_DECIMALS as u32);
+pub const DEFAULT_INITIAL_TOKEN_RESERVES: u64 = 793_100_000_000_000;
+pub const DEFAULT_INITIAL_VIRTUAL_SOL_RESERVES: u64 = 30_000_000_000;
+pub const DEFAULT_INITIAL_VIRTUAL_TOKEN_RESERVES: u64 = 1_073_000_000_000_000;
+pub const DEFAULT_FEE_BASIS_POINTS: u64 = 50;
+
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+pub const COLLECTION_NAME: &str = "Curve Launchpad";
+pub const COLLECTION_SYMBOL: &str = "LAUNCH";
+pub const COLLECTION_URI: &str = "https://www.test.com/collection.json";
+
+//events reach the program as self-CPIs, which program-test doesn't record, so the
+//entrypoint wrapper keeps them here; tests share the process, so filter by mint or user
+static EVENTS: Mutex<Vec<Vec<u8>>> = Mutex::new(Vec::new());
+
+//program-test hands builtins accounts with an anonymous lifetime while anchor's entry
+//wants them tied to the slice, leaking the copy satisfies both
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    if data.starts_with(&EVENT_IX_TAG_LE) {
+        EVENTS.lock().unwrap().push(data[EVENT_IX_TAG_LE.len()..].to_vec());
+    }
+
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    curve_launchpad::entry(program_id, accounts, data)
+}
+
+fn process_amm_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    dynamic_amm_mock::entry(program_id, accounts, data)
+}
+
+fn process_caller_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    launchpad_caller::entry(program_id, accounts, data)
+}
+
+/// Every event of type `E` emitted by any test so far.
+pub fn events<E: AnchorDeserialize + Discriminator>() -> Vec<E> {
+    EVENTS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|data| data.starts_with(&E::DISCRIMINATOR))
+        .filter_map(|data| E::try_from_slice(&data[E::DISCRIMINATOR.len()..]).ok())
+        .collect()
+}
+
+/// The launchpad error a failed transaction was rejected with, if any.
+pub fn error_code(result: std::result::Result<(), BanksClientError>) -> Option<u32> {
+    match result.err()?.unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => Some(code),
+        _ => None,
+    }
+}
+
+pub fn assert_error(result: std::result::Result<(), BanksClientError>, expected: CurveLaunchpadError) {
+    assert_eq!(error_code(result), Some(expected.into()), "expected {expected:?}");
+}
+
+pub struct TestEnv {
+    pub context: ProgramTestContext,
+    pub authority: Keypair,
+    pub creator: Keypair,
+    pub fee_recipient: Keypair,
+    pub withdraw_authority: Keypair,
+}
+
+impl TestEnv {
+    /// Starts a fresh chain without touching the program.
+    pub async fn start() -> Self {
+        let mut program_test = ProgramTest::default();
+        program_test.prefer_bpf(false);
+
+        program_test.add_program("curve_launchpad", curve_launchpad::ID, processor!(process_instruction));
+
+        //metaplex only exists as a build of the deployed program, so it runs on the BPF loader
+        let metaplex = include_bytes!("../fixtures/metaplex_metadata.so").to_vec();
+        program_test.add_account(
+            mpl_token_metadata::ID,
+            solana_sdk::account::Account {
+                lamports: Rent::default().minimum_balance(metaplex.len()),
+                data: metaplex,
+                owner: solana_sdk::bpf_loader::id(),
+                executable: true,
+                rent_epoch: 0,
+            },
+        );
+
+        program_test.add_program("dynamic_amm", dynamic_amm::ID, processor!(process_amm_instruction));
+        program_test.add_program("launchpad_caller", launchpad_caller::ID, processor!(process_caller_instruction));
+
+        let mut env = TestEnv {
+            context: program_test.start_with_context().await,
+            authority: Keypair::new(),
+            creator: Keypair::new(),
+            fee_recipient: Keypair::new(),
+            withdraw_authority: Keypair::new(),
+        };
+
+        env.fund(&env.authority.pubkey(), 5 * LAMPORTS_PER_SOL).await;
+        env.fund(&env.creator.pubkey(), 200 * LAMPORTS_PER_SOL).await;
+        env.fund(&env.withdraw_authority.pubkey(), 5 * LAMPORTS_PER_SOL).await;
+
+        env
+    }
+
+    /// Starts a chain with the program initialized and configured with the default params.
+    pub async fn new() -> Self {
+        let mut env = Self::start().await;
+
+        let authority = env.authority.insecure_clone();
+        env.send(&[instructions::initialize(&authority.pubkey(), env.initialize_args())], &[&authority])
+            .await
+            .unwrap();
+
+        let set_params = env.default_params();
+        env.send(&[instructions::set_params(&authority.pubkey(), set_params)], &[&authority])
+            .await
+            .unwrap();
+
+        env
+    }
+
+    pub fn initialize_args(&self) -> instruction::Initialize {
+        instruction::Initialize {
+            collection_name: COLLECTION_NAME.to_string(),
+            collection_symbol: COLLECTION_SYMBOL.to_string(),
+            collection_uri: COLLECTION_URI.to_string(),
+        }
+    }
+
+    pub fn default_params(&self) -> instruction::SetParams {
+        instruction::SetParams {
+            fee_recipient: self.fee_recipient.pubkey(),
+            withdraw_authority: self.withdraw_authority.pubkey(),
+            initial_virtual_token_reserves: DEFAULT_INITIAL_VIRTUAL_TOKEN_RESERVES,
+            initial_virtual_sol_reserves: DEFAULT_INITIAL_VIRTUAL_SOL_RESERVES,
+            initial_real_token_reserves: DEFAULT_INITIAL_TOKEN_RESERVES,
+            inital_token_supply: DEFAULT_TOKEN_BALANCE,
+            fee_basis_points: DEFAULT_FEE_BASIS_POINTS,
+        }
+    }
+
+    /// Signs with `signers` and sends, the first signer pays the fee.
+    pub async fn send(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> std::result::Result<(), BanksClientError> {
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signers[0].pubkey()),
+            signers,
+            self.context.last_blockhash,
+        );
+
+        self.context.banks_client.process_transaction(transaction).await
+    }
+
+    /// Simulates `instruction` paid for by the context payer and decodes the program's return data.
+    pub async fn simulate<T: AnchorDeserialize>(
+        &mut self,
+        instruction: Instruction,
+    ) -> std::result::Result<T, BanksClientError> {
+        let payer = self.context.payer.insecure_clone();
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[&payer],
+            self.context.last_blockhash,
+        );
+
+        let simulation = self.context.banks_client.simulate_transaction(transaction).await?;
+        simulation.result.unwrap()?;
+
+        let return_data = simulation.simulation_details.unwrap().return_data.unwrap();
+        Ok(T::try_from_slice(&return_data.data).unwrap())
+    }
+
+    /// Compute units the transaction takes, simulated. The launchpad runs natively and
+    /// is barely metered, so this is what its CPIs into the BPF programs cost.
+    pub async fn compute_units(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> u64 {
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signers[0].pubkey()),
+            signers,
+            self.context.last_blockhash,
+        );
+
+        let simulation = self.context.banks_client.simulate_transaction(transaction).await.unwrap();
+        simulation.result.unwrap().unwrap();
+
+        simulation.simulation_details.unwrap().units_consumed
+    }
+
+    /// Like `send`, and decodes the return data of the last instruction.
+    pub async fn send_returning<T: AnchorDeserialize>(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> std::result::Result<T, BanksClientError> {
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signers[0].pubkey()),
+            signers,
+            self.context.last_blockhash,
+        );
+
+        let processed = self.context.banks_client.process_transaction_with_metadata(transaction).await?;
+        processed.result?;
+
+        let return_data = processed.metadata.unwrap().return_data.unwrap();
+        Ok(T::try_from_slice(&return_data.data).unwrap())
+    }
+
+    /// Moves to a new blockhash so a transaction identical to an earlier one isn't
+    /// answered with the earlier result.
+    pub async fn refresh_blockhash(&mut self) {
+        self.context.last_blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+    }
+
+    pub async fn fund(&mut self, address: &Pubkey, lamports: u64) {
+        let payer = self.context.payer.insecure_clone();
+        self.send(&[system_instruction::transfer(&payer.pubkey(), address, lamports)], &[&payer])
+            .await
+            .unwrap();
+    }
+
+    pub async fn funded_user(&mut self, lamports: u64) -> Keypair {
+        let user = Keypair::new();
+        self.fund(&user.pubkey(), lamports).await;
+        user
+    }
+
+    pub async fn create_token_account(&mut self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+        let address = get_associated_token_address(owner, mint);
+
+        //resending the same transaction within a blockhash would be rejected as a duplicate
+        if self.account_exists(&address).await {
+            return address;
+        }
+
+        let payer = self.context.payer.insecure_clone();
+        self.send(
+            &[create_associated_token_account_idempotent(&payer.pubkey(), owner, mint, &spl_token::ID)],
+            &[&payer],
+        )
+        .await
+        .unwrap();
+
+        address
+    }
+
+    pub fn create_args(&self) -> instruction::Create {
+        instruction::Create {
+            name: "test".to_string(),
+            symbol: "tst".to_string(),
+            uri: "https://www.test.com".to_string(),
+            graduation_deadline: None,
+            graduation_target: None,
+            start_time: None,
+            verify_creator: false,
+            decimals: None,
+            token_supply: None,
+        }
+    }
+
+    pub async fn try_create_curve(
+        &mut self,
+        mint: &Keypair,
+        args: instruction::Create,
+    ) -> std::result::Result<(), BanksClientError> {
+        let creator = self.creator.insecure_clone();
+        let fee_recipient = self.fee_recipient.pubkey();
+        self.send(
+            &[instructions::create(&mint.pubkey(), &creator.pubkey(), &fee_recipient, args)],
+            &[&creator, mint],
+        )
+        .await
+    }
+
+    /// Creates a curve with the default args and returns its mint.
+    pub async fn create_curve(&mut self) -> Pubkey {
+        let args = self.create_args();
+        self.create_curve_with(args).await
+    }
+
+    pub async fn create_curve_with(&mut self, args: instruction::Create) -> Pubkey {
+        let mint = Keypair::new();
+        self.try_create_curve(&mint, args).await.unwrap();
+        mint.pubkey()
+    }
+
+    pub async fn create_curve_with_target(&mut self, target: GraduationTarget) -> Pubkey {
+        let args = instruction::Create {
+            graduation_target: Some(target),
+            ..self.create_args()
+        };
+        self.create_curve_with(args).await
+    }
+
+    pub async fn buy(
+        &mut self,
+        user: &Keypair,
+        mint: &Pubkey,
+        token_amount: u64,
+        max_sol_cost: u64,
+    ) -> std::result::Result<(), BanksClientError> {
+        self.create_token_account(&user.pubkey(), mint).await;
+
+        let fee_recipient = self.fee_recipient.pubkey();
+        self.send(
+            &[instructions::buy(&user.pubkey(), mint, &fee_recipient, token_amount, max_sol_cost)],
+            &[user],
+        )
+        .await
+    }
+
+    pub async fn sell(
+        &mut self,
+        user: &Keypair,
+        mint: &Pubkey,
+        token_amount: u64,
+        min_sol_output: u64,
+    ) -> std::result::Result<(), BanksClientError> {
+        self.create_token_account(&user.pubkey(), mint).await;
+
+        let fee_recipient = self.fee_recipient.pubkey();
+        self.send(
+            &[instructions::sell(&user.pubkey(), mint, &fee_recipient, token_amount, min_sol_output)],
+            &[user],
+        )
+        .await
+    }
+
+    /// Buys everything left on the curve as the creator, completing it.
+    pub async fn complete_curve(&mut self, mint: &Pubkey) {
+        let bonding_curve = self.bonding_curve(mint).await;
+        let creator = self.creator.insecure_clone();
+
+        self.buy(&creator, mint, bonding_curve.real_token_reserves, u64::MAX)
+            .await
+            .unwrap();
+    }
+
+    pub async fn withdraw(&mut self, mint: &Pubkey) -> std::result::Result<(), BanksClientError> {
+        let withdraw_authority = self.withdraw_authority.insecure_clone();
+        self.send(
+            &[instructions::withdraw(&withdraw_authority.pubkey(), mint)],
+            &[&withdraw_authority],
+        )
+        .await
+    }
+
+    pub async fn global(&mut self) -> Global {
+        let data = self.account_data(&pda::global()).await;
+        accounts::decode_global(&data).unwrap()
+    }
+
+    pub async fn bonding_curve(&mut self, mint: &Pubkey) -> BondingCurve {
+        let data = self.account_data(&pda::bonding_curve(mint)).await;
+        accounts::decode_bonding_curve(&data).unwrap()
+    }
+
+    pub async fn curve_oracle(&mut self, mint: &Pubkey) -> CurveOracle {
+        let data = self.account_data(&pda::curve_oracle(mint)).await;
+        accounts::decode_curve_oracle(&data).unwrap()
+    }
+
+    pub async fn metadata(&mut self, mint: &Pubkey) -> mpl_token_metadata::accounts::Metadata {
+        let data = self.account_data(&pda::metadata(mint)).await;
+        mpl_token_metadata::accounts::Metadata::from_bytes(&data).unwrap()
+    }
+
+    pub async fn mint(&mut self, mint: &Pubkey) -> spl_token::state::Mint {
+        let data = self.account_data(mint).await;
+        spl_token::state::Mint::unpack(&data).unwrap()
+    }
+
+    pub async fn account_data(&mut self, address: &Pubkey) -> Vec<u8> {
+        self.context
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("account {address} does not exist"))
+            .data
+    }
+
+    pub async fn account_exists(&mut self, address: &Pubkey) -> bool {
+        self.context.banks_client.get_account(*address).await.unwrap().is_some()
+    }
+
+    pub async fn balance(&mut self, address: &Pubkey) -> u64 {
+        self.context.banks_client.get_balance(*address).await.unwrap()
+    }
+
+    /// Token balance of `owner`'s associated token account, zero if it doesn't exist.
+    pub async fn token_balance(&mut self, owner: &Pubkey, mint: &Pubkey) -> u64 {
+        let address = get_associated_token_address(owner, mint);
+
+        match self.context.banks_client.get_account(address).await.unwrap() {
+            Some(account) => spl_token::state::Account::unpack(&account.data).unwrap().amount,
+            None => 0,
+        }
+    }
+
+    pub async fn minimum_balance(&mut self, len: usize) -> u64 {
+        self.context.banks_client.get_rent().await.unwrap().minimum_balance(len)
+    }
+
+    pub async fn now(&mut self) -> i64 {
+        self.context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+    }
+
+    /// Moves the cluster clock to `unix_timestamp`.
+    pub async fn set_time(&mut self, unix_timestamp: i64) {
+        let mut clock = self.context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        clock.unix_timestamp = unix_timestamp;
+        self.context.set_sysvar(&clock);
+    }
+}
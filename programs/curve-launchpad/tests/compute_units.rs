@@ -0,0 +1,81 @@
+//! Compute units of the instructions that CPI into Metaplex, measured with the
+//! deployed Metaplex build on the BPF loader. The launchpad itself runs
+//! natively and is barely metered, so these are what its CPIs cost.
+
+mod common;
+
+use common::*;
+use curve_launchpad::instruction;
+use curve_launchpad_client::instructions;
+use solana_sdk::{compute_budget::ComputeBudgetInstruction, signature::Keypair, signer::Signer};
+
+//the budget an instruction gets without a compute budget instruction
+const DEFAULT_COMPUTE_UNIT_LIMIT: u64 = 200_000;
+//what the launchpad's own create takes on BPF, finding its PDAs, initializing
+//the curve, oracle and stats and emitting the event, kept generous
+const CREATE_PROGRAM_UNITS: u64 = 100_000;
+
+#[tokio::test]
+async fn metaplex_cpis_run_on_bpf() {
+    let mut env = TestEnv::new().await;
+    let creator = env.creator.insecure_clone();
+    let fee_recipient = env.fee_recipient.pubkey();
+
+    //creating, collecting and signing the metadata is the most expensive path
+    let mint = Keypair::new();
+    let args = instruction::Create {
+        verify_creator: true,
+        ..env.create_args()
+    };
+    let create = instructions::create(&mint.pubkey(), &creator.pubkey(), &fee_recipient, args);
+    let create_units = env.compute_units(&[create], &[&creator, &mint]).await;
+    //a native stand-in would cost next to nothing
+    assert!(create_units > 50_000, "create took {create_units}");
+    assert!(create_units < DEFAULT_COMPUTE_UNIT_LIMIT, "create took {create_units}");
+
+    let mint = env.create_curve().await;
+    let update = instructions::update_metadata(
+        &creator.pubkey(),
+        &mint,
+        instruction::UpdateMetadata {
+            name: "renamed".to_string(),
+            symbol: "NEW".to_string(),
+            uri: "https://www.test.com/new.json".to_string(),
+        },
+    );
+    let update_units = env.compute_units(&[update], &[&creator]).await;
+    assert!(update_units > 10_000, "update_metadata took {update_units}");
+    assert!(update_units < DEFAULT_COMPUTE_UNIT_LIMIT / 2, "update_metadata took {update_units}");
+
+    env.complete_curve(&mint).await;
+    let withdraw_authority = env.withdraw_authority.insecure_clone();
+    let withdraw = instructions::withdraw(&withdraw_authority.pubkey(), &mint);
+    let withdraw_units = env.compute_units(&[withdraw], &[&withdraw_authority]).await;
+    assert!(withdraw_units > 10_000, "withdraw took {withdraw_units}");
+    assert!(withdraw_units < DEFAULT_COMPUTE_UNIT_LIMIT / 2, "withdraw took {withdraw_units}");
+}
+
+#[tokio::test]
+async fn create_sets_a_compute_unit_limit_that_fits() {
+    let mut env = TestEnv::new().await;
+    let creator = env.creator.insecure_clone();
+    let fee_recipient = env.fee_recipient.pubkey();
+
+    let budget = instructions::set_compute_unit_limit(instructions::CREATE_COMPUTE_UNIT_LIMIT);
+    assert_eq!(budget, ComputeBudgetInstruction::set_compute_unit_limit(instructions::CREATE_COMPUTE_UNIT_LIMIT));
+
+    let mint = Keypair::new();
+    let args = instruction::Create {
+        verify_creator: true,
+        ..env.create_args()
+    };
+    let create = instructions::create(&mint.pubkey(), &creator.pubkey(), &fee_recipient, args);
+    let create_units = env.compute_units(std::slice::from_ref(&create), &[&creator, &mint]).await;
+    assert!(
+        create_units + CREATE_PROGRAM_UNITS <= instructions::CREATE_COMPUTE_UNIT_LIMIT as u64,
+        "create took {create_units}"
+    );
+
+    env.send(&[budget, create], &[&creator, &mint]).await.unwrap();
+    assert_eq!(env.bonding_curve(&mint.pubkey()).await.creator, creator.pubkey());
+}
@@ -0,0 +1,204 @@
+//! `buy` and `sell` called directly and through CPI from `programs/launchpad-caller`,
+//! checking the `TradeResult` they return against what actually moved.
+
+mod common;
+
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction, InstructionData};
+use anchor_spl::associated_token::get_associated_token_address;
+use common::*;
+use curve_launchpad::{
+    instructions::{TradeEvent, TradeResult},
+    state::GraduationTarget,
+};
+use curve_launchpad_client::{instructions, pda, quote};
+use launchpad_caller::CallerError;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+fn caller_accounts(env: &TestEnv, user: &Pubkey, mint: &Pubkey) -> Vec<AccountMeta> {
+    launchpad_caller::accounts::Trade {
+        user: *user,
+        global: pda::global(),
+        fee_recipient: env.fee_recipient.pubkey(),
+        mint: *mint,
+        bonding_curve: pda::bonding_curve(mint),
+        curve_oracle: pda::curve_oracle(mint),
+        bonding_curve_token_account: pda::bonding_curve_token_account(mint),
+        user_token_account: get_associated_token_address(user, mint),
+        system_program: anchor_lang::system_program::ID,
+        token_program: anchor_spl::token::ID,
+        event_authority: pda::event_authority(),
+        curve_launchpad_program: curve_launchpad::ID,
+    }
+    .to_account_metas(None)
+}
+
+fn caller_buy(env: &TestEnv, user: &Pubkey, mint: &Pubkey, token_amount: u64, min_token_amount: u64) -> Instruction {
+    Instruction {
+        program_id: launchpad_caller::ID,
+        accounts: caller_accounts(env, user, mint),
+        data: launchpad_caller::instruction::Buy {
+            token_amount,
+            max_sol_cost: u64::MAX,
+            min_token_amount,
+        }
+        .data(),
+    }
+}
+
+fn caller_sell(env: &TestEnv, user: &Pubkey, mint: &Pubkey, token_amount: u64) -> Instruction {
+    Instruction {
+        program_id: launchpad_caller::ID,
+        accounts: caller_accounts(env, user, mint),
+        data: launchpad_caller::instruction::Sell {
+            token_amount,
+            min_sol_output: 0,
+        }
+        .data(),
+    }
+}
+
+#[tokio::test]
+async fn buy_and_sell_return_the_trade() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let user = env.funded_user(10 * LAMPORTS_PER_SOL).await;
+    let fee_recipient = env.fee_recipient.pubkey();
+    env.create_token_account(&user.pubkey(), &mint).await;
+
+    let token_amount = DEFAULT_TOKEN_BALANCE / 100;
+    let expected = quote::quote_buy(&env.global().await, &env.bonding_curve(&mint).await, token_amount).unwrap();
+
+    let result: TradeResult = env
+        .send_returning(
+            &[instructions::buy(&user.pubkey(), &mint, &fee_recipient, token_amount, u64::MAX)],
+            &[&user],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        result,
+        TradeResult {
+            token_amount: expected.token_amount,
+            sol_amount: expected.sol_amount,
+            fee: expected.fee,
+        }
+    );
+
+    let expected = quote::quote_sell(&env.global().await, &env.bonding_curve(&mint).await, token_amount).unwrap();
+
+    let result: TradeResult = env
+        .send_returning(
+            &[instructions::sell(&user.pubkey(), &mint, &fee_recipient, token_amount, 0)],
+            &[&user],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        result,
+        TradeResult {
+            token_amount,
+            sol_amount: expected.sol_amount,
+            fee: expected.fee,
+        }
+    );
+}
+
+#[tokio::test]
+async fn can_buy_through_cpi() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let user = env.funded_user(10 * LAMPORTS_PER_SOL).await;
+    let fee_recipient = env.fee_recipient.pubkey();
+    env.create_token_account(&user.pubkey(), &mint).await;
+
+    let token_amount = DEFAULT_TOKEN_BALANCE / 100;
+    let user_pre_buy_balance = env.balance(&user.pubkey()).await;
+    let fee_recipient_pre_buy_balance = env.balance(&fee_recipient).await;
+
+    let instruction = caller_buy(&env, &user.pubkey(), &mint, token_amount, token_amount);
+    let result: TradeResult = env.send_returning(&[instruction], &[&user]).await.unwrap();
+
+    assert_eq!(result.token_amount, token_amount);
+    assert_eq!(env.token_balance(&user.pubkey(), &mint).await, result.token_amount);
+    //the user also pays the 5000 lamport signature fee
+    assert_eq!(
+        user_pre_buy_balance - env.balance(&user.pubkey()).await,
+        result.sol_amount + result.fee + 5_000
+    );
+    assert_eq!(env.balance(&fee_recipient).await - fee_recipient_pre_buy_balance, result.fee);
+
+    //the launchpad still emits its event when called through cpi
+    let trade_events: Vec<_> = events::<TradeEvent>()
+        .into_iter()
+        .filter(|event| event.mint == mint)
+        .collect();
+    assert_eq!(trade_events.len(), 1);
+    assert_eq!(trade_events[0].user, user.pubkey());
+    assert_eq!(trade_events[0].sol_amount, result.sol_amount);
+}
+
+#[tokio::test]
+async fn can_sell_through_cpi() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let user = env.funded_user(10 * LAMPORTS_PER_SOL).await;
+    let fee_recipient = env.fee_recipient.pubkey();
+
+    env.buy(&user, &mint, DEFAULT_TOKEN_BALANCE / 100, u64::MAX).await.unwrap();
+
+    let token_amount = 10_000_000;
+    let user_pre_sale_tokens = env.token_balance(&user.pubkey(), &mint).await;
+    let user_pre_sale_balance = env.balance(&user.pubkey()).await;
+    let fee_recipient_pre_sale_balance = env.balance(&fee_recipient).await;
+
+    let instruction = caller_sell(&env, &user.pubkey(), &mint, token_amount);
+    let result: TradeResult = env.send_returning(&[instruction], &[&user]).await.unwrap();
+
+    assert_eq!(result.token_amount, token_amount);
+    assert_eq!(
+        env.token_balance(&user.pubkey(), &mint).await,
+        user_pre_sale_tokens - token_amount
+    );
+    assert_eq!(
+        env.balance(&user.pubkey()).await + 5_000 - user_pre_sale_balance,
+        result.sol_amount - result.fee
+    );
+    assert_eq!(env.balance(&fee_recipient).await - fee_recipient_pre_sale_balance, result.fee);
+}
+
+#[tokio::test]
+async fn caller_rejects_a_partial_fill() {
+    let mut env = TestEnv::new().await;
+    let mint = env
+        .create_curve_with_target(GraduationTarget::SolRaised(2 * LAMPORTS_PER_SOL))
+        .await;
+    let user = env.funded_user(10 * LAMPORTS_PER_SOL).await;
+    env.create_token_account(&user.pubkey(), &mint).await;
+
+    let token_amount = env.bonding_curve(&mint).await.real_token_reserves;
+
+    let instruction = caller_buy(&env, &user.pubkey(), &mint, token_amount, token_amount);
+    let result = env.send(&[instruction], &[&user]).await;
+    assert_eq!(error_code(result), Some(CallerError::PartiallyFilled.into()));
+
+    //accepting the partial fill graduates the curve
+    let instruction = caller_buy(&env, &user.pubkey(), &mint, token_amount, 0);
+    let result: TradeResult = env.send_returning(&[instruction], &[&user]).await.unwrap();
+
+    assert!(result.token_amount < token_amount);
+    assert_eq!(env.token_balance(&user.pubkey(), &mint).await, result.token_amount);
+}
+
+#[tokio::test]
+async fn caller_needs_the_user_to_sign() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let user = Keypair::new();
+    let payer = env.context.payer.insecure_clone();
+
+    let mut instruction = caller_buy(&env, &user.pubkey(), &mint, 100, 0);
+    instruction.accounts[0].is_signer = false;
+
+    let result = env.send(&[instruction], &[&payer]).await;
+    assert!(result.is_err());
+}
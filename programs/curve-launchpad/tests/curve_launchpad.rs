@@ -0,0 +1,963 @@
+//! Port of the `anchor test` suite in `tests/curve-launchpad.ts`. Each scenario
+//! sets up its own chain instead of relying on the order the tests run in.
+
+mod common;
+
+use anchor_lang::{prelude::*, solana_program::program_pack::Pack};
+use anchor_spl::{
+    metadata::mpl_token_metadata::types::{Collection, Creator},
+    token::spl_token::{self, solana_program::program_option::COption},
+};
+use common::*;
+use curve_launchpad::{
+    amm::AMM,
+    instructions::{
+        calculate_fee, CreateEvent, CurveLaunchpadError, GraduatedEvent, MetadataUpdatedEvent, Quote, SetParamsEvent, TradeEvent, Twap,
+    },
+    state::{BondingCurve, CreatorStats, CurveStatus, LastWithdraw},
+};
+use curve_launchpad_client::{accounts, instructions, pda, quote};
+use solana_program_test::BanksClientError;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+fn assert_bonding_curve(amm: &AMM, bonding_curve: &BondingCurve, status: CurveStatus) {
+    assert_eq!(bonding_curve.virtual_token_reserves as u128, amm.virtual_token_reserves);
+    assert_eq!(bonding_curve.virtual_sol_reserves as u128, amm.virtual_sol_reserves);
+    assert_eq!(bonding_curve.real_token_reserves as u128, amm.real_token_reserves);
+    assert_eq!(bonding_curve.real_sol_reserves as u128, amm.real_sol_reserves);
+    assert_eq!(bonding_curve.token_total_supply, DEFAULT_TOKEN_BALANCE);
+    assert_eq!(bonding_curve.status, status);
+}
+
+fn trade_events(mint: &Pubkey) -> Vec<TradeEvent> {
+    events::<TradeEvent>()
+        .into_iter()
+        .filter(|event| event.mint == *mint)
+        .collect()
+}
+
+//the curve's amm as buy and sell will see it
+async fn current_amm(env: &mut TestEnv, mint: &Pubkey) -> AMM {
+    let bonding_curve = env.bonding_curve(mint).await;
+    quote::amm(&bonding_curve)
+}
+
+#[tokio::test]
+async fn is_initialized() {
+    let mut env = TestEnv::start().await;
+    let authority = env.authority.insecure_clone();
+
+    //the collection's metadata is checked like a token's
+    let args = curve_launchpad::instruction::Initialize {
+        collection_uri: String::new(),
+        ..env.initialize_args()
+    };
+    let result = env.send(&[instructions::initialize(&authority.pubkey(), args)], &[&authority]).await;
+    assert_error(result, CurveLaunchpadError::InvalidUri);
+
+    env.send(&[instructions::initialize(&authority.pubkey(), env.initialize_args())], &[&authority])
+        .await
+        .unwrap();
+
+    let global = env.global().await;
+    assert_eq!(global.authority, authority.pubkey());
+    assert!(global.initialized);
+
+    let set_params = env.default_params();
+    env.send(&[instructions::set_params(&authority.pubkey(), set_params)], &[&authority])
+        .await
+        .unwrap();
+
+    let global = env.global().await;
+    assert_eq!(global.fee_recipient, env.fee_recipient.pubkey());
+    assert_eq!(global.withdraw_authority, env.withdraw_authority.pubkey());
+    assert_eq!(global.initial_token_supply, DEFAULT_TOKEN_BALANCE);
+
+    //the launchpad collection, a single NFT held by the program
+    let collection_mint = pda::collection_mint();
+    let collection = env.mint(&collection_mint).await;
+    assert_eq!(collection.supply, 1);
+    assert_eq!(collection.decimals, 0);
+    assert_eq!(collection.mint_authority, COption::Some(pda::master_edition(&collection_mint)));
+    assert_eq!(env.token_balance(&pda::mint_authority(), &collection_mint).await, 1);
+
+    let metadata = env.metadata(&collection_mint).await;
+    assert_eq!(metadata.name.trim_end_matches('\0'), COLLECTION_NAME);
+    assert_eq!(metadata.symbol.trim_end_matches('\0'), COLLECTION_SYMBOL);
+    assert_eq!(metadata.uri.trim_end_matches('\0'), COLLECTION_URI);
+    assert_eq!(metadata.update_authority, pda::mint_authority());
+}
+
+#[tokio::test]
+async fn can_mint_a_token() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+
+    let create_events: Vec<_> = events::<CreateEvent>()
+        .into_iter()
+        .filter(|event| event.mint == mint)
+        .collect();
+    assert_eq!(create_events.len(), 1);
+
+    let create_event = &create_events[0];
+    assert_eq!(create_event.name, "test");
+    assert_eq!(create_event.symbol, "tst");
+    assert_eq!(create_event.uri, "https://www.test.com");
+    assert_eq!(create_event.bonding_curve, pda::bonding_curve(&mint));
+    assert_eq!(create_event.creator, env.creator.pubkey());
+
+    assert_eq!(env.token_balance(&pda::bonding_curve(&mint), &mint).await, DEFAULT_TOKEN_BALANCE);
+
+    let created_mint = env.mint(&mint).await;
+    assert!(created_mint.is_initialized);
+    assert_eq!(created_mint.decimals, DEFAULT_DECIMALS);
+    assert_eq!(created_mint.supply, DEFAULT_TOKEN_BALANCE);
+    //the program still holds the mint authority, nothing can mint through it
+    assert_eq!(created_mint.mint_authority, COption::Some(pda::mint_authority()));
+
+    let metadata = env.metadata(&mint).await;
+    assert_eq!(metadata.name.trim_end_matches('\0'), "test");
+    assert_eq!(metadata.symbol.trim_end_matches('\0'), "tst");
+    assert_eq!(metadata.uri.trim_end_matches('\0'), "https://www.test.com");
+    assert_eq!(
+        metadata.collection,
+        Some(Collection {
+            verified: true,
+            key: pda::collection_mint(),
+        })
+    );
+
+    let bonding_curve = env.bonding_curve(&mint).await;
+    assert_eq!(bonding_curve.virtual_token_reserves, DEFAULT_INITIAL_VIRTUAL_TOKEN_RESERVES);
+    assert_eq!(bonding_curve.virtual_sol_reserves, DEFAULT_INITIAL_VIRTUAL_SOL_RESERVES);
+    assert_eq!(bonding_curve.real_token_reserves, DEFAULT_INITIAL_TOKEN_RESERVES);
+    assert_eq!(bonding_curve.real_sol_reserves, 0);
+    assert_eq!(bonding_curve.token_total_supply, DEFAULT_TOKEN_BALANCE);
+    assert_eq!(bonding_curve.status, CurveStatus::Trading);
+}
+
+#[tokio::test]
+async fn cant_mint_a_token_with_invalid_metadata() {
+    let mut env = TestEnv::new().await;
+
+    let cases = [
+        ("n".repeat(33), "tst", "https://www.test.com", CurveLaunchpadError::InvalidName),
+        ("te\nst".to_string(), "tst", "https://www.test.com", CurveLaunchpadError::InvalidName),
+        ("test".to_string(), "$TST", "https://www.test.com", CurveLaunchpadError::InvalidSymbol),
+        ("test".to_string(), "tst", "https://www.test.com/a b", CurveLaunchpadError::InvalidUri),
+    ];
+
+    for (name, symbol, uri, expected) in cases {
+        let args = curve_launchpad::instruction::Create {
+            name,
+            symbol: symbol.to_string(),
+            uri: uri.to_string(),
+            ..env.create_args()
+        };
+        let result = env.try_create_curve(&Keypair::new(), args).await;
+        assert_error(result, expected);
+    }
+}
+
+#[tokio::test]
+async fn can_mint_a_token_with_a_verified_creator() {
+    let mut env = TestEnv::new().await;
+    let args = curve_launchpad::instruction::Create {
+        verify_creator: true,
+        ..env.create_args()
+    };
+    let mint = env.create_curve_with(args).await;
+
+    let metadata = env.metadata(&mint).await;
+    assert_eq!(
+        metadata.creators,
+        Some(vec![Creator {
+            address: env.creator.pubkey(),
+            verified: true,
+            share: 100,
+        }])
+    );
+
+    //without asking for it there are none
+    let mint = env.create_curve().await;
+    assert_eq!(env.metadata(&mint).await.creators, None);
+}
+
+#[tokio::test]
+async fn creator_can_update_metadata_until_graduation() {
+    let mut env = TestEnv::new().await;
+    let creator = env.creator.insecure_clone();
+    let args = curve_launchpad::instruction::Create {
+        verify_creator: true,
+        ..env.create_args()
+    };
+    let mint = env.create_curve_with(args).await;
+
+    let update = |name: &str| curve_launchpad::instruction::UpdateMetadata {
+        name: name.to_string(),
+        symbol: "NEW".to_string(),
+        uri: "https://www.test.com/new.json".to_string(),
+    };
+
+    env.send(&[instructions::update_metadata(&creator.pubkey(), &mint, update("renamed"))], &[&creator])
+        .await
+        .unwrap();
+
+    let metadata = env.metadata(&mint).await;
+    assert_eq!(metadata.name.trim_end_matches('\0'), "renamed");
+    assert_eq!(metadata.symbol.trim_end_matches('\0'), "NEW");
+    assert_eq!(metadata.uri.trim_end_matches('\0'), "https://www.test.com/new.json");
+    //the verified creator survives the update
+    assert!(metadata.creators.unwrap()[0].verified);
+
+    let updated: Vec<_> = events::<MetadataUpdatedEvent>()
+        .into_iter()
+        .filter(|event| event.mint == mint)
+        .collect();
+    assert_eq!(updated.len(), 1);
+    assert_eq!(updated[0].name, "renamed");
+
+    //only the creator
+    let other = env.funded_user(1_000_000_000).await;
+    let result = env
+        .send(&[instructions::update_metadata(&other.pubkey(), &mint, update("stolen"))], &[&other])
+        .await;
+    assert_error(result, CurveLaunchpadError::InvalidCreator);
+
+    //still validated
+    let result = env
+        .send(&[instructions::update_metadata(&creator.pubkey(), &mint, update(""))], &[&creator])
+        .await;
+    assert_error(result, CurveLaunchpadError::InvalidName);
+
+    //not once the curve has graduated
+    env.complete_curve(&mint).await;
+    let result = env
+        .send(&[instructions::update_metadata(&creator.pubkey(), &mint, update("too late"))], &[&creator])
+        .await;
+    assert_error(result, CurveLaunchpadError::BondingCurveComplete);
+}
+
+#[tokio::test]
+async fn can_buy_a_token() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let creator = env.creator.insecure_clone();
+    let fee_recipient = env.fee_recipient.pubkey();
+
+    let mut amm = current_amm(&mut env, &mint).await;
+
+    let buy_token_amount = DEFAULT_TOKEN_BALANCE / 100;
+    let buy_sol_amount = amm.get_buy_price(buy_token_amount as u128).unwrap() as u64;
+    let fee = calculate_fee(buy_sol_amount, DEFAULT_FEE_BASIS_POINTS);
+    let buy_result = amm.apply_buy(buy_token_amount as u128).unwrap();
+
+    let fee_recipient_pre_buy_balance = env.balance(&fee_recipient).await;
+
+    env.buy(&creator, &mint, buy_token_amount, buy_sol_amount + fee)
+        .await
+        .unwrap();
+
+    assert_eq!(env.balance(&fee_recipient).await - fee_recipient_pre_buy_balance, fee);
+
+    let trade_events = trade_events(&mint);
+    assert_eq!(trade_events.len(), 1);
+    assert_eq!(trade_events[0].token_amount, buy_token_amount);
+    assert!(trade_events[0].is_buy);
+    assert_eq!(trade_events[0].sol_amount, buy_result.sol_amount);
+    assert_eq!(trade_events[0].sol_amount, buy_sol_amount);
+
+    assert_eq!(env.token_balance(&creator.pubkey(), &mint).await, buy_token_amount);
+    assert_eq!(
+        env.token_balance(&pda::bonding_curve(&mint), &mint).await,
+        DEFAULT_TOKEN_BALANCE - buy_token_amount
+    );
+
+    let bonding_curve = env.bonding_curve(&mint).await;
+    assert_bonding_curve(&amm, &bonding_curve, CurveStatus::Trading);
+}
+
+#[tokio::test]
+async fn can_sell_a_token() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let creator = env.creator.insecure_clone();
+    let fee_recipient = env.fee_recipient.pubkey();
+
+    env.buy(&creator, &mint, DEFAULT_TOKEN_BALANCE / 100, u64::MAX)
+        .await
+        .unwrap();
+
+    let mut amm = current_amm(&mut env, &mint).await;
+
+    let token_amount = 10_000_000;
+    let sol_amount = amm.get_sell_price(token_amount as u128).unwrap() as u64;
+    let fee = calculate_fee(sol_amount, DEFAULT_FEE_BASIS_POINTS);
+    let sell_result = amm.apply_sell(token_amount as u128).unwrap();
+
+    let user_pre_sale_balance = env.token_balance(&creator.pubkey(), &mint).await;
+    let curve_pre_sale_balance = env.token_balance(&pda::bonding_curve(&mint), &mint).await;
+    let fee_recipient_pre_sale_balance = env.balance(&fee_recipient).await;
+    let creator_pre_sale_lamports = env.balance(&creator.pubkey()).await;
+
+    env.sell(&creator, &mint, token_amount, sol_amount - fee)
+        .await
+        .unwrap();
+
+    assert_eq!(env.balance(&fee_recipient).await - fee_recipient_pre_sale_balance, fee);
+    //the seller receives the sale less the fee, and pays the 5000 lamport signature fee
+    assert_eq!(
+        env.balance(&creator.pubkey()).await + 5_000 - creator_pre_sale_lamports,
+        sol_amount - fee
+    );
+
+    assert_eq!(
+        env.token_balance(&creator.pubkey(), &mint).await,
+        user_pre_sale_balance - token_amount
+    );
+    assert_eq!(
+        env.token_balance(&pda::bonding_curve(&mint), &mint).await,
+        curve_pre_sale_balance + token_amount
+    );
+
+    let sell_events: Vec<_> = trade_events(&mint).into_iter().filter(|event| !event.is_buy).collect();
+    assert_eq!(sell_events.len(), 1);
+    assert_eq!(sell_events[0].token_amount, token_amount);
+    assert_eq!(sell_events[0].sol_amount, sell_result.sol_amount);
+
+    let bonding_curve = env.bonding_curve(&mint).await;
+    assert_bonding_curve(&amm, &bonding_curve, CurveStatus::Trading);
+}
+
+#[tokio::test]
+async fn can_quote_a_buy() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let creator = env.creator.insecure_clone();
+
+    let token_amount = DEFAULT_TOKEN_BALANCE / 100;
+    let expected = quote::quote_buy(&env.global().await, &env.bonding_curve(&mint).await, token_amount).unwrap();
+
+    let quote: Quote = env.simulate(instructions::quote_buy(&mint, token_amount)).await.unwrap();
+    assert_eq!(quote.token_amount, expected.token_amount);
+    assert_eq!(quote.sol_amount, expected.sol_amount);
+    assert_eq!(quote.fee, expected.fee);
+    //the first buy already pays above the spot price
+    assert!(quote.price_impact_bps > 0);
+
+    //the quote is exactly what the buy does
+    env.buy(&creator, &mint, token_amount, expected.total_cost()).await.unwrap();
+
+    let bonding_curve = env.bonding_curve(&mint).await;
+    assert_eq!(quote.virtual_sol_reserves, bonding_curve.virtual_sol_reserves);
+    assert_eq!(quote.virtual_token_reserves, bonding_curve.virtual_token_reserves);
+    assert_eq!(quote.real_sol_reserves, bonding_curve.real_sol_reserves);
+    assert_eq!(quote.real_token_reserves, bonding_curve.real_token_reserves);
+}
+
+#[tokio::test]
+async fn can_quote_a_sell() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let creator = env.creator.insecure_clone();
+
+    env.buy(&creator, &mint, DEFAULT_TOKEN_BALANCE / 100, u64::MAX)
+        .await
+        .unwrap();
+
+    let token_amount = 10_000_000;
+    let expected = quote::quote_sell(&env.global().await, &env.bonding_curve(&mint).await, token_amount).unwrap();
+
+    let quote: Quote = env.simulate(instructions::quote_sell(&mint, token_amount)).await.unwrap();
+    assert_eq!(quote.token_amount, token_amount);
+    assert_eq!(quote.sol_amount, expected.sol_amount);
+    assert_eq!(quote.fee, expected.fee);
+
+    env.sell(&creator, &mint, token_amount, expected.net_output()).await.unwrap();
+
+    let bonding_curve = env.bonding_curve(&mint).await;
+    assert_eq!(quote.virtual_sol_reserves, bonding_curve.virtual_sol_reserves);
+    assert_eq!(quote.virtual_token_reserves, bonding_curve.virtual_token_reserves);
+    assert_eq!(quote.real_sol_reserves, bonding_curve.real_sol_reserves);
+    assert_eq!(quote.real_token_reserves, bonding_curve.real_token_reserves);
+}
+
+#[tokio::test]
+async fn quote_buy_stops_at_the_curve_token_balance() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let creator = env.creator.insecure_clone();
+
+    //the token account holds less than the curve's reserves say
+    let token_account = pda::bonding_curve_token_account(&mint);
+    let mut account = env.context.banks_client.get_account(token_account).await.unwrap().unwrap();
+    let mut state = spl_token::state::Account::unpack(&account.data).unwrap();
+    state.amount = DEFAULT_TOKEN_BALANCE / 100;
+    spl_token::state::Account::pack(state, &mut account.data).unwrap();
+    env.context.set_account(&token_account, &account.into());
+
+    let token_amount = DEFAULT_TOKEN_BALANCE / 10;
+    let quote: Quote = env.simulate(instructions::quote_buy(&mint, token_amount)).await.unwrap();
+    assert_eq!(quote.token_amount, DEFAULT_TOKEN_BALANCE / 100);
+
+    env.buy(&creator, &mint, token_amount, u64::MAX).await.unwrap();
+    assert_eq!(env.token_balance(&creator.pubkey(), &mint).await, quote.token_amount);
+    assert_eq!(env.bonding_curve(&mint).await.real_sol_reserves, quote.real_sol_reserves);
+}
+
+#[tokio::test]
+async fn cant_quote_before_initialized() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+
+    let mut global = env.global().await;
+    global.initialized = false;
+    let mut account = env.context.banks_client.get_account(pda::global()).await.unwrap().unwrap();
+    let mut data = vec![];
+    global.try_serialize(&mut data).unwrap();
+    account.data[..data.len()].copy_from_slice(&data);
+    env.context.set_account(&pda::global(), &account.into());
+
+    let result = env.simulate::<Quote>(instructions::quote_buy(&mint, 100)).await.map(|_| ());
+    assert_error(result, CurveLaunchpadError::NotInitialized);
+
+    let result = env.simulate::<Quote>(instructions::quote_sell(&mint, 100)).await.map(|_| ());
+    assert_error(result, CurveLaunchpadError::NotInitialized);
+}
+
+#[tokio::test]
+async fn cant_quote_a_complete_curve() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    env.complete_curve(&mint).await;
+
+    let result = env.simulate::<Quote>(instructions::quote_buy(&mint, 100)).await.map(|_| ());
+    assert_error(result, CurveLaunchpadError::BondingCurveComplete);
+
+    let result = env.simulate::<Quote>(instructions::quote_sell(&mint, 100)).await.map(|_| ());
+    assert_error(result, CurveLaunchpadError::BondingCurveComplete);
+
+    let result = env.simulate::<Quote>(instructions::quote_buy(&Pubkey::new_unique(), 100)).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn can_read_a_twap() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let creator = env.creator.insecure_clone();
+
+    let oracle = env.curve_oracle(&mint).await;
+    let created = oracle.last_update;
+    let starting_price = env.bonding_curve(&mint).await.spot_price();
+    assert_eq!(oracle.price, starting_price);
+
+    env.set_time(created + 60).await;
+    env.buy(&creator, &mint, DEFAULT_TOKEN_BALANCE / 100, u64::MAX)
+        .await
+        .unwrap();
+    let price = env.bonding_curve(&mint).await.spot_price();
+    assert!(price > starting_price);
+    assert_eq!(env.curve_oracle(&mint).await.price, price);
+
+    //the starting price for a minute, then the price after the buy for two
+    env.set_time(created + 180).await;
+    let twap: Twap = env.simulate(instructions::get_twap(&mint, 180)).await.unwrap();
+    assert_eq!(twap.window, 180);
+    assert_eq!(twap.spot_price, price);
+    assert_eq!(twap.price, (starting_price * 60 + price * 120) / 180);
+
+    let twap: Twap = env.simulate(instructions::get_twap(&mint, 120)).await.unwrap();
+    assert_eq!(twap.price, price);
+}
+
+#[tokio::test]
+async fn cant_read_a_twap_beyond_the_oracle_history() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let created = env.curve_oracle(&mint).await.last_update;
+    env.set_time(created + 30).await;
+
+    let result = env.simulate::<Twap>(instructions::get_twap(&mint, 60)).await.map(|_| ());
+    assert_error(result, CurveLaunchpadError::OracleWindowTooLong);
+
+    let result = env.simulate::<Twap>(instructions::get_twap(&mint, 0)).await.map(|_| ());
+    assert_error(result, CurveLaunchpadError::InvalidTwapWindow);
+}
+
+#[tokio::test]
+async fn cant_withdraw_as_curve_is_incomplete() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+
+    let result = env.withdraw(&mint).await;
+    assert_error(result, CurveLaunchpadError::BondingCurveNotComplete);
+}
+
+#[tokio::test]
+async fn cant_buy_a_token_not_enough_sol() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let not_enough_sol_user = env.funded_user(21_000_000).await;
+
+    let result = env
+        .buy(&not_enough_sol_user, &mint, 5_000_000_000_000, 5 * LAMPORTS_PER_SOL)
+        .await;
+    assert_error(result, CurveLaunchpadError::InsufficientSOL);
+}
+
+#[tokio::test]
+async fn cant_buy_a_token_exceed_max_sol() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let creator = env.creator.insecure_clone();
+
+    let result = env.buy(&creator, &mint, DEFAULT_TOKEN_BALANCE / 100, 1).await;
+    assert_error(result, CurveLaunchpadError::MaxSOLCostExceeded);
+}
+
+#[tokio::test]
+async fn cant_buy_0_tokens() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let creator = env.creator.insecure_clone();
+
+    let result = env.buy(&creator, &mint, 0, 1).await;
+    assert_error(result, CurveLaunchpadError::MinBuy);
+}
+
+#[tokio::test]
+async fn cant_sell_a_token_not_enough_tokens() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let creator = env.creator.insecure_clone();
+
+    env.buy(&creator, &mint, DEFAULT_TOKEN_BALANCE / 100, u64::MAX)
+        .await
+        .unwrap();
+
+    let result = env.sell(&creator, &mint, DEFAULT_TOKEN_BALANCE, 0).await;
+    assert_error(result, CurveLaunchpadError::InsufficientTokens);
+}
+
+#[tokio::test]
+async fn cant_sell_0_tokens() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let creator = env.creator.insecure_clone();
+
+    let result = env.sell(&creator, &mint, 0, 0).await;
+    assert_error(result, CurveLaunchpadError::MinSell);
+}
+
+#[tokio::test]
+async fn cant_sell_a_token_exceed_min_sol_output() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let creator = env.creator.insecure_clone();
+
+    env.buy(&creator, &mint, DEFAULT_TOKEN_BALANCE / 100, u64::MAX)
+        .await
+        .unwrap();
+
+    let result = env.sell(&creator, &mint, 1, DEFAULT_TOKEN_BALANCE).await;
+    assert_error(result, CurveLaunchpadError::MinSOLOutputExceeded);
+}
+
+#[tokio::test]
+async fn can_complete_the_curve() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let creator = env.creator.insecure_clone();
+
+    let mut amm = current_amm(&mut env, &mint).await;
+    let buy_token_amount = amm.real_token_reserves as u64;
+    let max_sol_amount = amm.get_buy_price(buy_token_amount as u128).unwrap() as u64;
+    let max_sol_amount = max_sol_amount + calculate_fee(max_sol_amount, DEFAULT_FEE_BASIS_POINTS);
+    let buy_result = amm.apply_buy(buy_token_amount as u128).unwrap();
+
+    env.buy(&creator, &mint, buy_token_amount, max_sol_amount)
+        .await
+        .unwrap();
+
+    let trade_events = trade_events(&mint);
+    assert_eq!(trade_events.len(), 1);
+    assert!(trade_events[0].is_buy);
+    assert_eq!(trade_events[0].sol_amount, buy_result.sol_amount);
+
+    assert_eq!(env.token_balance(&creator.pubkey(), &mint).await, buy_token_amount);
+    assert_eq!(
+        env.token_balance(&pda::bonding_curve(&mint), &mint).await + DEFAULT_INITIAL_TOKEN_RESERVES,
+        DEFAULT_TOKEN_BALANCE
+    );
+
+    let bonding_curve = env.bonding_curve(&mint).await;
+    assert_bonding_curve(&amm, &bonding_curve, CurveStatus::Complete);
+}
+
+#[tokio::test]
+async fn cant_buy_a_token_curve_complete() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let creator = env.creator.insecure_clone();
+    env.complete_curve(&mint).await;
+
+    let result = env.buy(&creator, &mint, 100, u64::MAX).await;
+    assert_error(result, CurveLaunchpadError::BondingCurveComplete);
+}
+
+#[tokio::test]
+async fn cant_sell_a_token_curve_complete() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let creator = env.creator.insecure_clone();
+    env.complete_curve(&mint).await;
+
+    let result = env.sell(&creator, &mint, 100, 0).await;
+    assert_error(result, CurveLaunchpadError::BondingCurveComplete);
+}
+
+#[tokio::test]
+async fn cant_withdraw_as_incorrect_authority() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let creator = env.creator.insecure_clone();
+    env.complete_curve(&mint).await;
+
+    let result = env
+        .send(&[instructions::withdraw(&creator.pubkey(), &mint)], &[&creator])
+        .await;
+    assert_error(result, CurveLaunchpadError::InvalidWithdrawAuthority);
+}
+
+#[tokio::test]
+async fn can_withdraw() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    env.complete_curve(&mint).await;
+
+    let withdraw_authority = env.withdraw_authority.pubkey();
+    let bonding_curve_address = pda::bonding_curve(&mint);
+
+    let withdraw_authority_pre_balance = env.balance(&withdraw_authority).await;
+    let bonding_curve_pre_balance = env.balance(&bonding_curve_address).await;
+    let bonding_curve_pre_token_balance = env.token_balance(&bonding_curve_address, &mint).await;
+
+    env.withdraw(&mint).await.unwrap();
+
+    //the curve keeps exactly its rent
+    let min_balance_rent_exempt = env.minimum_balance(8 + BondingCurve::INIT_SPACE).await;
+    assert_eq!(env.balance(&bonding_curve_address).await, min_balance_rent_exempt);
+
+    //the withdraw authority pays for its token account, the last withdraw record and the signature
+    let token_account_rent = env.minimum_balance(165).await;
+    let last_withdraw_rent = env.minimum_balance(8 + LastWithdraw::INIT_SPACE).await;
+    assert_eq!(
+        env.balance(&withdraw_authority).await - withdraw_authority_pre_balance,
+        bonding_curve_pre_balance - min_balance_rent_exempt - token_account_rent - last_withdraw_rent - 5_000
+    );
+
+    assert_eq!(
+        env.token_balance(&withdraw_authority, &mint).await,
+        bonding_curve_pre_token_balance
+    );
+    assert_eq!(env.token_balance(&bonding_curve_address, &mint).await, 0);
+
+    let bonding_curve = env.bonding_curve(&mint).await;
+    assert_eq!(bonding_curve.status, CurveStatus::Migrated);
+
+    //the supply is fixed and the metadata final
+    let mint_account = env.mint(&mint).await;
+    assert_eq!(mint_account.mint_authority, COption::None);
+    assert_eq!(mint_account.freeze_authority, COption::None);
+    assert!(!env.metadata(&mint).await.is_mutable);
+
+    let graduated_events: Vec<_> = events::<GraduatedEvent>()
+        .into_iter()
+        .filter(|event| event.mint == mint)
+        .collect();
+    assert_eq!(
+        graduated_events,
+        vec![GraduatedEvent {
+            mint,
+            bonding_curve: bonding_curve_address,
+            mint_authority_revoked: true,
+            //launched mints never have a freeze authority
+            freeze_authority_revoked: false,
+            metadata_made_immutable: true,
+            timestamp: graduated_events[0].timestamp,
+        }]
+    );
+}
+
+#[tokio::test]
+async fn can_set_params() {
+    let mut env = TestEnv::new().await;
+    let authority = env.authority.insecure_clone();
+    let random_fee_recipient = Keypair::new().pubkey();
+    let random_withdraw_authority = Keypair::new().pubkey();
+
+    let set_params = curve_launchpad::instruction::SetParams {
+        fee_recipient: random_fee_recipient,
+        withdraw_authority: random_withdraw_authority,
+        initial_virtual_token_reserves: 1000,
+        initial_virtual_sol_reserves: 2000,
+        initial_real_token_reserves: 3000,
+        inital_token_supply: 4000,
+        fee_basis_points: 100,
+    };
+
+    env.send(&[instructions::set_params(&authority.pubkey(), set_params)], &[&authority])
+        .await
+        .unwrap();
+
+    let set_params_events: Vec<_> = events::<SetParamsEvent>()
+        .into_iter()
+        .filter(|event| event.fee_recipient == random_fee_recipient)
+        .collect();
+    assert_eq!(set_params_events.len(), 1);
+
+    let set_params_event = &set_params_events[0];
+    assert_eq!(set_params_event.withdraw_authority, random_withdraw_authority);
+    assert_eq!(set_params_event.initial_virtual_token_reserves, 1000);
+    assert_eq!(set_params_event.initial_virtual_sol_reserves, 2000);
+    assert_eq!(set_params_event.initial_real_token_reserves, 3000);
+    assert_eq!(set_params_event.initial_token_supply, 4000);
+    assert_eq!(set_params_event.fee_basis_points, 100);
+
+    let global = env.global().await;
+    assert_eq!(global.fee_recipient, random_fee_recipient);
+    assert_eq!(global.withdraw_authority, random_withdraw_authority);
+    assert_eq!(global.initial_virtual_token_reserves, 1000);
+    assert_eq!(global.initial_virtual_sol_reserves, 2000);
+    assert_eq!(global.initial_real_token_reserves, 3000);
+    assert_eq!(global.initial_token_supply, 4000);
+    assert_eq!(global.fee_basis_points, 100);
+}
+
+#[tokio::test]
+async fn cant_set_params_as_non_authority() {
+    let mut env = TestEnv::new().await;
+    let creator = env.creator.insecure_clone();
+    let set_params = env.default_params();
+
+    let result = env
+        .send(&[instructions::set_params(&creator.pubkey(), set_params)], &[&creator])
+        .await;
+    assert_error(result, CurveLaunchpadError::InvalidAuthority);
+}
+
+async fn set_create_controls(
+    env: &mut TestEnv,
+    creation_fee_lamports: u64,
+    creator_rate_limit: u32,
+    creator_rate_limit_window: i64,
+    creator_allowlist: bool,
+) -> std::result::Result<(), BanksClientError> {
+    let authority = env.authority.insecure_clone();
+    let args = curve_launchpad::instruction::SetCreateControls {
+        creation_fee_lamports,
+        creator_rate_limit,
+        creator_rate_limit_window,
+        creator_allowlist,
+    };
+    env.send(&[instructions::set_create_controls(&authority.pubkey(), args)], &[&authority])
+        .await
+}
+
+#[tokio::test]
+async fn create_pays_the_creation_fee() {
+    let mut env = TestEnv::new().await;
+    let fee = 20_000_000;
+    set_create_controls(&mut env, fee, 0, 0, false).await.unwrap();
+
+    let fee_recipient = env.fee_recipient.pubkey();
+    let creator = env.creator.pubkey();
+    let fee_recipient_before = env.balance(&fee_recipient).await;
+    env.create_curve().await;
+    assert_eq!(env.balance(&fee_recipient).await - fee_recipient_before, fee);
+
+    let creator_stats = accounts::decode_creator_stats(&env.account_data(&pda::creator_stats(&creator)).await).unwrap();
+    assert_eq!(creator_stats.version, CreatorStats::CURRENT_VERSION);
+    assert_eq!(creator_stats.creator, creator);
+    assert_eq!(creator_stats.curves_created, 1);
+
+    //the fee can't be sent anywhere else
+    let creator = env.creator.insecure_clone();
+    let mint = Keypair::new();
+    let args = env.create_args();
+    let result = env
+        .send(
+            &[instructions::create(&mint.pubkey(), &creator.pubkey(), &Keypair::new().pubkey(), args)],
+            &[&creator, &mint],
+        )
+        .await;
+    assert_error(result, CurveLaunchpadError::InvalidFeeRecipient);
+}
+
+#[tokio::test]
+async fn creators_are_rate_limited() {
+    let mut env = TestEnv::new().await;
+    set_create_controls(&mut env, 0, 2, 3_600, false).await.unwrap();
+
+    env.create_curve().await;
+    env.create_curve().await;
+
+    let args = env.create_args();
+    let result = env.try_create_curve(&Keypair::new(), args).await;
+    assert_error(result, CurveLaunchpadError::CreatorRateLimited);
+
+    //the next window
+    let now = env.now().await;
+    env.set_time(now + 3_600).await;
+    env.create_curve().await;
+
+    let creator = env.creator.pubkey();
+    let creator_stats = accounts::decode_creator_stats(&env.account_data(&pda::creator_stats(&creator)).await).unwrap();
+    assert_eq!(creator_stats.curves_created, 3);
+    assert_eq!(creator_stats.window_count, 1);
+}
+
+#[tokio::test]
+async fn only_allowed_creators_can_create() {
+    let mut env = TestEnv::new().await;
+    let authority = env.authority.insecure_clone();
+    let creator = env.creator.pubkey();
+    set_create_controls(&mut env, 0, 0, 0, true).await.unwrap();
+
+    let args = env.create_args();
+    let result = env.try_create_curve(&Keypair::new(), args).await;
+    assert_error(result, CurveLaunchpadError::CreatorNotAllowed);
+
+    env.send(&[instructions::set_creator_allowed(&authority.pubkey(), &creator, true)], &[&authority])
+        .await
+        .unwrap();
+    env.create_curve().await;
+
+    env.send(&[instructions::set_creator_allowed(&authority.pubkey(), &creator, false)], &[&authority])
+        .await
+        .unwrap();
+    let args = env.create_args();
+    let result = env.try_create_curve(&Keypair::new(), args).await;
+    assert_error(result, CurveLaunchpadError::CreatorNotAllowed);
+
+    //only the authority manages the allowlist
+    let other = env.funded_user(1_000_000_000).await;
+    let result = env
+        .send(&[instructions::set_creator_allowed(&other.pubkey(), &other.pubkey(), true)], &[&other])
+        .await;
+    assert_error(result, CurveLaunchpadError::InvalidAuthority);
+}
+
+#[tokio::test]
+async fn cant_set_a_rate_limit_without_a_window() {
+    let mut env = TestEnv::new().await;
+
+    let result = set_create_controls(&mut env, 0, 5, 0, false).await;
+    assert_error(result, CurveLaunchpadError::InvalidRateLimit);
+}
+
+//a fresh keypair whose address ends in `suffix`
+fn vanity_keypair(suffix: &str) -> Keypair {
+    loop {
+        let keypair = Keypair::new();
+        if keypair.pubkey().to_string().ends_with(suffix) {
+            return keypair;
+        }
+    }
+}
+
+#[tokio::test]
+async fn mints_must_end_in_the_suffix() {
+    let mut env = TestEnv::new().await;
+    let authority = env.authority.insecure_clone();
+
+    let result = env
+        .send(&[instructions::set_mint_suffix(&authority.pubkey(), "p0")], &[&authority])
+        .await;
+    assert_error(result, CurveLaunchpadError::InvalidMintSuffix);
+
+    env.send(&[instructions::set_mint_suffix(&authority.pubkey(), "p")], &[&authority])
+        .await
+        .unwrap();
+    assert_eq!(env.global().await.mint_suffix(), "p");
+
+    let args = env.create_args();
+    let result = env.try_create_curve(&vanity_keypair("q"), args).await;
+    assert_error(result, CurveLaunchpadError::RequireVanityMint);
+
+    let mint = vanity_keypair("p");
+    let args = env.create_args();
+    env.try_create_curve(&mint, args).await.unwrap();
+    assert_eq!(env.bonding_curve(&mint.pubkey()).await.status, CurveStatus::Trading);
+
+    //only the authority sets it
+    let other = env.funded_user(1_000_000_000).await;
+    let result = env
+        .send(&[instructions::set_mint_suffix(&other.pubkey(), "")], &[&other])
+        .await;
+    assert_error(result, CurveLaunchpadError::InvalidAuthority);
+}
+
+#[tokio::test]
+async fn create_launches_any_vault_mint() {
+    let mut env = TestEnv::new().await;
+    let authority = env.authority.insecure_clone();
+    let creator = env.creator.insecure_clone();
+    let fee_recipient = env.fee_recipient.pubkey();
+    let mints = [vanity_keypair("q"), vanity_keypair("p")];
+
+    for mint in &mints {
+        env.send(&[instructions::deposit_vault_mint(&authority.pubkey(), &mint.pubkey())], &[&authority, mint])
+            .await
+            .unwrap();
+    }
+
+    let mint_vault = accounts::decode_mint_vault(&env.account_data(&pda::mint_vault()).await).unwrap();
+    assert_eq!(mint_vault.mints, vec![mints[0].pubkey(), mints[1].pubkey()]);
+
+    //the first mint no longer matches, but doesn't hold up the one behind it
+    env.send(&[instructions::set_mint_suffix(&authority.pubkey(), "p")], &[&authority])
+        .await
+        .unwrap();
+
+    //the creator launches without the mint's keypair, but only a queued one
+    let create_from_vault = |mint: &Pubkey, args| instructions::create_from_vault(mint, &creator.pubkey(), &fee_recipient, args);
+    let result = env
+        .send(&[create_from_vault(&mints[0].pubkey(), env.create_args())], &[&creator])
+        .await;
+    assert_error(result, CurveLaunchpadError::RequireVanityMint);
+
+    let result = env
+        .send(&[create_from_vault(&vanity_keypair("p").pubkey(), env.create_args())], &[&creator])
+        .await;
+    assert_error(result, CurveLaunchpadError::InvalidVaultMint);
+
+    //vault mints have the default decimals
+    let args = curve_launchpad::instruction::Create {
+        decimals: Some(9),
+        ..env.create_args()
+    };
+    let result = env.send(&[create_from_vault(&mints[1].pubkey(), args)], &[&creator]).await;
+    assert_error(result, CurveLaunchpadError::InvalidDecimals);
+
+    env.send(&[create_from_vault(&mints[1].pubkey(), env.create_args())], &[&creator])
+        .await
+        .unwrap();
+
+    let mint = mints[1].pubkey();
+    assert_eq!(env.bonding_curve(&mint).await.token_total_supply, DEFAULT_TOKEN_BALANCE);
+    assert_eq!(env.mint(&mint).await.supply, DEFAULT_TOKEN_BALANCE);
+    assert_eq!(env.metadata(&mint).await.name.trim_end_matches('\0'), "test");
+
+    let mint_vault = accounts::decode_mint_vault(&env.account_data(&pda::mint_vault()).await).unwrap();
+    assert_eq!(mint_vault.mints, vec![mints[0].pubkey()]);
+
+    //only the authority deposits
+    let other = env.funded_user(1_000_000_000).await;
+    let mint = Keypair::new();
+    let result = env
+        .send(&[instructions::deposit_vault_mint(&other.pubkey(), &mint.pubkey())], &[&other, &mint])
+        .await;
+    assert_error(result, CurveLaunchpadError::InvalidAuthority);
+}
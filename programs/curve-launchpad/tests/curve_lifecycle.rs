@@ -0,0 +1,503 @@
+//! Scheduling, graduation, refunds, freezing and closing, none of which the
+//! `anchor test` suite covers.
+
+mod common;
+
+use anchor_lang::prelude::*;
+use common::*;
+use curve_launchpad::{
+    instruction,
+    instructions::{CloseCurveEvent, CurveLaunchpadError, RedeemEvent},
+    state::{CurveStatus, GraduatedCurve, GraduationTarget},
+};
+use curve_launchpad_client::{instructions, pda, quote};
+use solana_sdk::{account::AccountSharedData, signature::Keypair, signer::Signer};
+
+#[tokio::test]
+async fn cant_initialize_twice() {
+    let mut env = TestEnv::new().await;
+    let creator = env.creator.insecure_clone();
+
+    let result = env
+        .send(&[instructions::initialize(&creator.pubkey(), env.initialize_args())], &[&creator])
+        .await;
+    assert!(result.is_err());
+
+    let global = env.global().await;
+    assert_eq!(global.authority, env.authority.pubkey());
+}
+
+#[tokio::test]
+async fn cant_buy_with_wrong_fee_recipient() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let creator = env.creator.insecure_clone();
+    env.create_token_account(&creator.pubkey(), &mint).await;
+
+    let wrong_fee_recipient = Keypair::new().pubkey();
+    let result = env
+        .send(
+            &[instructions::buy(&creator.pubkey(), &mint, &wrong_fee_recipient, 1_000_000, u64::MAX)],
+            &[&creator],
+        )
+        .await;
+    assert_error(result, CurveLaunchpadError::InvalidFeeRecipient);
+}
+
+#[tokio::test]
+async fn cant_create_with_deadline_before_start() {
+    let mut env = TestEnv::new().await;
+    let now = env.now().await;
+
+    let args = instruction::Create {
+        start_time: Some(now + 3_600),
+        graduation_deadline: Some(now + 60),
+        ..env.create_args()
+    };
+
+    let result = env.try_create_curve(&Keypair::new(), args).await;
+    assert_error(result, CurveLaunchpadError::InvalidGraduationDeadline);
+}
+
+#[tokio::test]
+async fn cant_create_with_unreachable_target() {
+    let mut env = TestEnv::new().await;
+
+    let args = instruction::Create {
+        graduation_target: Some(GraduationTarget::SolRaised(u64::MAX)),
+        ..env.create_args()
+    };
+
+    let result = env.try_create_curve(&Keypair::new(), args).await;
+    assert_error(result, CurveLaunchpadError::InvalidGraduationTarget);
+}
+
+#[tokio::test]
+async fn can_create_with_custom_decimals_and_supply() {
+    let mut env = TestEnv::new().await;
+    let creator = env.creator.insecure_clone();
+
+    //half the template's whole tokens at 9 decimals
+    let token_supply = 500_000_000 * 10u64.pow(9);
+    let args = instruction::Create {
+        decimals: Some(9),
+        token_supply: Some(token_supply),
+        ..env.create_args()
+    };
+    let mint = env.create_curve_with(args).await;
+
+    let mint_account = env.mint(&mint).await;
+    assert_eq!(mint_account.decimals, 9);
+    assert_eq!(mint_account.supply, token_supply);
+
+    let bonding_curve = env.bonding_curve(&mint).await;
+    assert_eq!(bonding_curve.token_total_supply, token_supply);
+    assert_eq!(bonding_curve.virtual_token_reserves, DEFAULT_INITIAL_VIRTUAL_TOKEN_RESERVES / 2 * 1_000);
+    assert_eq!(bonding_curve.real_token_reserves, DEFAULT_INITIAL_TOKEN_RESERVES / 2 * 1_000);
+    assert_eq!(bonding_curve.virtual_sol_reserves, DEFAULT_INITIAL_VIRTUAL_SOL_RESERVES / 2);
+    assert_eq!(env.token_balance(&pda::bonding_curve(&mint), &mint).await, token_supply);
+
+    //a whole token starts at the template's price
+    let template_mint = env.create_curve().await;
+    let template = env.bonding_curve(&template_mint).await;
+    let whole_token_price = |sol: u64, tokens: u64, decimals: u32| {
+        sol as u128 * 10u128.pow(decimals) / tokens as u128
+    };
+    assert_eq!(
+        whole_token_price(bonding_curve.virtual_sol_reserves, bonding_curve.virtual_token_reserves, 9),
+        whole_token_price(template.virtual_sol_reserves, template.virtual_token_reserves, 6),
+    );
+
+    env.buy(&creator, &mint, 10u64.pow(9), u64::MAX).await.unwrap();
+    assert_eq!(env.token_balance(&creator.pubkey(), &mint).await, 10u64.pow(9));
+}
+
+#[tokio::test]
+async fn cant_create_with_invalid_decimals_or_supply() {
+    let mut env = TestEnv::new().await;
+
+    let args = instruction::Create {
+        decimals: Some(10),
+        ..env.create_args()
+    };
+    let result = env.try_create_curve(&Keypair::new(), args).await;
+    assert_error(result, CurveLaunchpadError::InvalidDecimals);
+
+    let args = instruction::Create {
+        token_supply: Some(0),
+        ..env.create_args()
+    };
+    let result = env.try_create_curve(&Keypair::new(), args).await;
+    assert_error(result, CurveLaunchpadError::InvalidTokenSupply);
+}
+
+#[tokio::test]
+async fn scheduled_curve_opens_at_start_time() {
+    let mut env = TestEnv::new().await;
+    let creator = env.creator.insecure_clone();
+    let start_time = env.now().await + 3_600;
+
+    let args = instruction::Create {
+        start_time: Some(start_time),
+        ..env.create_args()
+    };
+    let mint = env.create_curve_with(args).await;
+    assert_eq!(env.bonding_curve(&mint).await.status, CurveStatus::Scheduled);
+
+    let result = env.buy(&creator, &mint, 1_000_000, u64::MAX).await;
+    assert_error(result, CurveLaunchpadError::BondingCurveNotStarted);
+
+    env.set_time(start_time).await;
+
+    //a different amount, an identical transaction would be rejected as already processed
+    env.buy(&creator, &mint, 2_000_000, u64::MAX).await.unwrap();
+    assert_eq!(env.bonding_curve(&mint).await.status, CurveStatus::Trading);
+}
+
+#[tokio::test]
+async fn buy_graduates_at_sol_target() {
+    let mut env = TestEnv::new().await;
+    let creator = env.creator.insecure_clone();
+    let target = 10 * LAMPORTS_PER_SOL;
+
+    let mint = env.create_curve_with_target(GraduationTarget::SolRaised(target)).await;
+
+    //asking for the whole curve only fills up to the target
+    let global = env.global().await;
+    let bonding_curve = env.bonding_curve(&mint).await;
+    let expected = quote::quote_buy(&global, &bonding_curve, bonding_curve.real_token_reserves).unwrap();
+    assert!(expected.token_amount < bonding_curve.real_token_reserves);
+
+    env.buy(&creator, &mint, bonding_curve.real_token_reserves, u64::MAX)
+        .await
+        .unwrap();
+
+    assert_eq!(env.token_balance(&creator.pubkey(), &mint).await, expected.token_amount);
+
+    let bonding_curve = env.bonding_curve(&mint).await;
+    assert_eq!(bonding_curve.status, CurveStatus::Complete);
+    assert!(bonding_curve.real_sol_reserves >= target);
+}
+
+#[tokio::test]
+async fn missed_deadline_refunds_holders() {
+    let mut env = TestEnv::new().await;
+    let now = env.now().await;
+
+    let args = instruction::Create {
+        graduation_deadline: Some(now + 60),
+        ..env.create_args()
+    };
+    let mint = env.create_curve_with(args).await;
+
+    let user = env.funded_user(10 * LAMPORTS_PER_SOL).await;
+    env.buy(&user, &mint, DEFAULT_TOKEN_BALANCE / 100, u64::MAX)
+        .await
+        .unwrap();
+
+    let result = env
+        .send(&[instructions::redeem(&user.pubkey(), &mint, 1_000)], &[&user])
+        .await;
+    assert_error(result, CurveLaunchpadError::BondingCurveNotRefunding);
+
+    env.set_time(now + 120).await;
+
+    let result = env.buy(&user, &mint, 1_000_000, u64::MAX).await;
+    assert_error(result, CurveLaunchpadError::BondingCurveRefunding);
+
+    //the only holder gets back every lamport paid into the curve
+    let real_sol_reserves = env.bonding_curve(&mint).await.real_sol_reserves;
+    let token_balance = env.token_balance(&user.pubkey(), &mint).await;
+
+    env.send(&[instructions::redeem(&user.pubkey(), &mint, token_balance)], &[&user])
+        .await
+        .unwrap();
+
+    let redeem_events: Vec<_> = events::<RedeemEvent>()
+        .into_iter()
+        .filter(|event| event.mint == mint)
+        .collect();
+    assert_eq!(redeem_events.len(), 1);
+    assert_eq!(redeem_events[0].sol_amount, real_sol_reserves);
+    assert_eq!(redeem_events[0].token_amount, token_balance);
+
+    let bonding_curve = env.bonding_curve(&mint).await;
+    assert_eq!(bonding_curve.status, CurveStatus::Refunding);
+    assert_eq!(bonding_curve.real_sol_reserves, 0);
+    assert_eq!(env.token_balance(&user.pubkey(), &mint).await, 0);
+}
+
+#[tokio::test]
+async fn frozen_curve_past_deadline_refunds_holders() {
+    let mut env = TestEnv::new().await;
+    let now = env.now().await;
+    let authority = env.authority.insecure_clone();
+
+    let args = instruction::Create {
+        graduation_deadline: Some(now + 60),
+        ..env.create_args()
+    };
+    let mint = env.create_curve_with(args).await;
+
+    let user = env.funded_user(10 * LAMPORTS_PER_SOL).await;
+    env.buy(&user, &mint, DEFAULT_TOKEN_BALANCE / 100, u64::MAX)
+        .await
+        .unwrap();
+
+    env.send(&[instructions::set_curve_frozen(&authority.pubkey(), &mint, true)], &[&authority])
+        .await
+        .unwrap();
+
+    env.set_time(now + 120).await;
+
+    let token_balance = env.token_balance(&user.pubkey(), &mint).await;
+    env.send(&[instructions::redeem(&user.pubkey(), &mint, token_balance)], &[&user])
+        .await
+        .unwrap();
+
+    let bonding_curve = env.bonding_curve(&mint).await;
+    assert_eq!(bonding_curve.status, CurveStatus::Refunding);
+    assert_eq!(bonding_curve.real_sol_reserves, 0);
+    assert_eq!(env.token_balance(&user.pubkey(), &mint).await, 0);
+
+    //unfreezing can't bring a refunding curve back to trading
+    let result = env
+        .send(&[instructions::set_curve_frozen(&authority.pubkey(), &mint, false)], &[&authority])
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn frozen_curve_rejects_trades() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let authority = env.authority.insecure_clone();
+    let creator = env.creator.insecure_clone();
+
+    let result = env
+        .send(&[instructions::set_curve_frozen(&creator.pubkey(), &mint, true)], &[&creator])
+        .await;
+    assert_error(result, CurveLaunchpadError::InvalidAuthority);
+
+    env.send(&[instructions::set_curve_frozen(&authority.pubkey(), &mint, true)], &[&authority])
+        .await
+        .unwrap();
+
+    let result = env.buy(&creator, &mint, 1_000_000, u64::MAX).await;
+    assert_error(result, CurveLaunchpadError::BondingCurveFrozen);
+
+    env.send(&[instructions::set_curve_frozen(&authority.pubkey(), &mint, false)], &[&authority])
+        .await
+        .unwrap();
+
+    env.buy(&creator, &mint, 2_000_000, u64::MAX).await.unwrap();
+}
+
+#[tokio::test]
+async fn can_close_curve_after_withdraw() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let creator = env.creator.pubkey();
+    let user = env.funded_user(LAMPORTS_PER_SOL).await;
+    env.complete_curve(&mint).await;
+
+    let result = env
+        .send(&[instructions::close_curve(&user.pubkey(), &creator, &mint)], &[&user])
+        .await;
+    assert_error(result, CurveLaunchpadError::BondingCurveComplete);
+
+    env.withdraw(&mint).await.unwrap();
+
+    let wrong_creator = Keypair::new().pubkey();
+    let result = env
+        .send(&[instructions::close_curve(&user.pubkey(), &wrong_creator, &mint)], &[&user])
+        .await;
+    assert_error(result, CurveLaunchpadError::InvalidCreator);
+
+    let creator_pre_balance = env.balance(&creator).await;
+    let rent = env.balance(&pda::bonding_curve(&mint)).await
+        + env.balance(&pda::curve_oracle(&mint)).await
+        + env.balance(&pda::bonding_curve_token_account(&mint)).await;
+
+    //the same close was rejected above while the curve was still complete
+    env.refresh_blockhash().await;
+    env.send(&[instructions::close_curve(&user.pubkey(), &creator, &mint)], &[&user])
+        .await
+        .unwrap();
+
+    let close_events: Vec<_> = events::<CloseCurveEvent>()
+        .into_iter()
+        .filter(|event| event.mint == mint)
+        .collect();
+    assert_eq!(close_events.len(), 1);
+    //the creator gets back the rent of the curve, its oracle and its token account
+    assert_eq!(close_events[0].lamports, rent);
+    assert_eq!(env.balance(&creator).await - creator_pre_balance, rent);
+
+    assert!(!env.account_exists(&pda::bonding_curve(&mint)).await);
+    assert!(!env.account_exists(&pda::curve_oracle(&mint)).await);
+    assert!(!env.account_exists(&pda::bonding_curve_token_account(&mint)).await);
+
+    let data = env.account_data(&pda::graduated_curve(&mint)).await;
+    let graduated_curve = GraduatedCurve::try_deserialize(&mut &data[..]).unwrap();
+    assert_eq!(graduated_curve.mint, mint);
+    assert_eq!(graduated_curve.creator, creator);
+    assert_eq!(graduated_curve.token_total_supply, DEFAULT_TOKEN_BALANCE);
+}
+
+#[tokio::test]
+async fn migrate_current_accounts_is_a_no_op() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let authority = env.authority.insecure_clone();
+    let before = env.account_data(&pda::bonding_curve(&mint)).await;
+
+    env.send(
+        &[
+            instructions::migrate_global(&authority.pubkey()),
+            instructions::migrate_curve(&authority.pubkey(), &mint, &Pubkey::new_unique()),
+        ],
+        &[&authority],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(env.account_data(&pda::bonding_curve(&mint)).await, before);
+    assert!(env.global().await.initialized);
+}
+
+#[tokio::test]
+async fn migrate_curve_upgrades_baseline_accounts() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let authority = env.authority.insecure_clone();
+    let creator = env.creator.pubkey();
+    let user = env.funded_user(10 * LAMPORTS_PER_SOL).await;
+
+    //as written by the program before accounts were versioned, after a buy of
+    //`tokens` for its 1 SOL of real reserves
+    let legacy = include_bytes!("fixtures/bonding_curve_v0.bin");
+    let tokens = DEFAULT_INITIAL_TOKEN_RESERVES - 758_487_096_774_194;
+    env.buy(&user, &mint, tokens, u64::MAX).await.unwrap();
+
+    let legacy_rent = env.minimum_balance(legacy.len()).await;
+    let mut account = AccountSharedData::new(legacy_rent + LAMPORTS_PER_SOL, legacy.len(), &curve_launchpad::ID);
+    account.set_data_from_slice(legacy);
+    env.context.set_account(&pda::bonding_curve(&mint), &account);
+
+    //only the authority knows who launched it
+    let result = env
+        .send(&[instructions::migrate_curve(&user.pubkey(), &mint, &user.pubkey())], &[&user])
+        .await;
+    assert_error(result, CurveLaunchpadError::InvalidAuthority);
+
+    env.send(&[instructions::migrate_curve(&authority.pubkey(), &mint, &creator)], &[&authority])
+        .await
+        .unwrap();
+
+    let data = env.account_data(&pda::bonding_curve(&mint)).await;
+    assert_eq!(data.len(), 8 + curve_launchpad::state::BondingCurve::INIT_SPACE);
+
+    //the authority paid the extra rent, the reserves are untouched
+    let rent = env.minimum_balance(data.len()).await;
+    assert_eq!(env.balance(&pda::bonding_curve(&mint)).await, rent + LAMPORTS_PER_SOL);
+
+    let bonding_curve = env.bonding_curve(&mint).await;
+    assert_eq!(bonding_curve.status, CurveStatus::Trading);
+    assert_eq!(bonding_curve.creator, creator);
+    assert_eq!(bonding_curve.real_sol_reserves, LAMPORTS_PER_SOL);
+    assert_eq!(bonding_curve.graduation_deadline, None);
+
+    //selling every token back leaves the curve rent exempt
+    env.sell(&user, &mint, tokens, 0).await.unwrap();
+    let bonding_curve = env.bonding_curve(&mint).await;
+    assert_eq!(
+        env.balance(&pda::bonding_curve(&mint)).await,
+        rent + bonding_curve.real_sol_reserves
+    );
+}
+
+#[tokio::test]
+async fn curve_without_oracle_trades_once_it_has_one() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let creator = env.creator.insecure_clone();
+
+    //as created before curves had oracles
+    env.context
+        .set_account(&pda::curve_oracle(&mint), &AccountSharedData::default());
+
+    let result = env.buy(&creator, &mint, 100, u64::MAX).await;
+    assert_eq!(
+        error_code(result),
+        Some(anchor_lang::error::ErrorCode::AccountNotInitialized.into())
+    );
+
+    env.send(&[instructions::init_curve_oracle(&creator.pubkey(), &mint)], &[&creator])
+        .await
+        .unwrap();
+
+    let oracle = env.curve_oracle(&mint).await;
+    assert_eq!(oracle.mint, mint);
+    assert_eq!(oracle.price, env.bonding_curve(&mint).await.spot_price());
+
+    env.refresh_blockhash().await;
+    env.buy(&creator, &mint, 100, u64::MAX).await.unwrap();
+
+    //only once
+    let result = env
+        .send(&[instructions::init_curve_oracle(&creator.pubkey(), &mint)], &[&creator])
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn deployment_without_collection_creates_once_it_has_one() {
+    let mut env = TestEnv::new().await;
+    let authority = env.authority.insecure_clone();
+    let creator = env.creator.insecure_clone();
+
+    //as initialized before there was a collection
+    let collection_mint = pda::collection_mint();
+    for address in [
+        collection_mint,
+        pda::collection_token_account(),
+        pda::metadata(&collection_mint),
+        pda::master_edition(&collection_mint),
+    ] {
+        env.context.set_account(&address, &AccountSharedData::default());
+    }
+
+    let args = env.create_args();
+    let result = env.try_create_curve(&Keypair::new(), args).await;
+    assert_eq!(
+        error_code(result),
+        Some(anchor_lang::error::ErrorCode::AccountNotInitialized.into())
+    );
+
+    let collection_args = |uri: &str| instruction::InitCollection {
+        name: COLLECTION_NAME.to_string(),
+        symbol: COLLECTION_SYMBOL.to_string(),
+        uri: uri.to_string(),
+    };
+
+    //only the authority
+    let result = env
+        .send(&[instructions::init_collection(&creator.pubkey(), collection_args(COLLECTION_URI))], &[&creator])
+        .await;
+    assert_error(result, CurveLaunchpadError::InvalidAuthority);
+
+    let result = env
+        .send(&[instructions::init_collection(&authority.pubkey(), collection_args("no spaces"))], &[&authority])
+        .await;
+    assert_error(result, CurveLaunchpadError::InvalidUri);
+
+    env.send(&[instructions::init_collection(&authority.pubkey(), collection_args(COLLECTION_URI))], &[&authority])
+        .await
+        .unwrap();
+
+    let mint = env.create_curve().await;
+    let collection = env.metadata(&mint).await.collection.unwrap();
+    assert_eq!(collection.key, collection_mint);
+    assert!(collection.verified);
+}
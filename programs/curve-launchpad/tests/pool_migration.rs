@@ -0,0 +1,212 @@
+//! Graduation into the dynamic AMM, run against `dynamic-amm-mock`.
+
+mod common;
+
+use anchor_lang::prelude::*;
+use common::{amm::*, *};
+use curve_launchpad::instructions::CurveLaunchpadError;
+use curve_launchpad_client::instructions;
+use dynamic_amm_mock::{
+    instructions::AmmError,
+    math,
+    pda as amm_pda,
+    state::{Pool, PoolFees},
+};
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+/// Completes a curve, withdraws it and wraps the SOL it raised, leaving the
+/// withdraw authority holding both sides of the pool. Returns how much SOL was wrapped.
+async fn graduate(env: &mut TestEnv, mint: &Pubkey) -> u64 {
+    env.complete_curve(mint).await;
+    let real_sol_reserves = env.bonding_curve(mint).await.real_sol_reserves;
+
+    env.withdraw(mint).await.unwrap();
+
+    let withdraw_authority = env.withdraw_authority.insecure_clone();
+    env.wrap_sol(&withdraw_authority, real_sol_reserves).await;
+
+    real_sol_reserves
+}
+
+#[tokio::test]
+async fn graduated_curve_migrates_to_pool() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let withdraw_authority = env.withdraw_authority.pubkey();
+
+    let sol_amount = graduate(&mut env, &mint).await;
+    let token_amount = env.token_balance(&withdraw_authority, &mint).await;
+
+    let config = env.set_amm_config(default_pool_fees(), withdraw_authority).await;
+    env.create_pool(&mint, &config).await.unwrap();
+
+    let pool_address = amm_pda::pool(&NATIVE_MINT, &mint, &config);
+    let pool = env.amm_pool(&pool_address).await;
+    assert_eq!(pool.token_a_mint, NATIVE_MINT);
+    assert_eq!(pool.token_b_mint, mint);
+    assert_eq!(pool.fees, default_pool_fees());
+    assert_eq!(pool.lp_supply, math::initial_lp_amount(sol_amount, token_amount));
+
+    assert_eq!(token_account_amount(&mut env, &pool.a_token_vault).await, sol_amount);
+    assert_eq!(token_account_amount(&mut env, &pool.b_token_vault).await, token_amount);
+    assert_eq!(env.token_balance(&withdraw_authority, &mint).await, 0);
+
+    //every LP token the first deposit minted is locked
+    let lock_escrow = env.lock_escrow(&amm_pda::lock_escrow(&pool_address, &withdraw_authority)).await;
+    assert_eq!(lock_escrow.owner, withdraw_authority);
+    assert_eq!(lock_escrow.total_locked_amount, pool.lp_supply);
+    assert_eq!(pool.total_locked_lp, pool.lp_supply);
+    assert_eq!(env.token_balance(&withdraw_authority, &pool.lp_mint).await, 0);
+    assert_eq!(token_account_amount(&mut env, &lock_escrow.escrow_vault).await, pool.lp_supply);
+}
+
+#[tokio::test]
+async fn pool_trades_and_pays_locked_fees() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let withdraw_authority = env.withdraw_authority.insecure_clone();
+
+    graduate(&mut env, &mint).await;
+    let config = env.set_amm_config(default_pool_fees(), Pubkey::default()).await;
+    env.create_pool(&mint, &config).await.unwrap();
+
+    let pool_address = amm_pda::pool(&NATIVE_MINT, &mint, &config);
+    let pool = env.amm_pool(&pool_address).await;
+
+    let user = env.funded_user(10 * LAMPORTS_PER_SOL).await;
+    env.wrap_sol(&user, LAMPORTS_PER_SOL).await;
+    env.create_token_account(&user.pubkey(), &mint).await;
+
+    let expected = expected_out(&mut env, &pool, LAMPORTS_PER_SOL, true).await;
+
+    let result = env
+        .send(
+            &[swap(&user.pubkey(), &pool_address, &pool, &NATIVE_MINT, LAMPORTS_PER_SOL, expected + 1)],
+            &[&user],
+        )
+        .await;
+    assert_amm_error(result, AmmError::ExceededSlippage);
+
+    env.send(
+        &[swap(&user.pubkey(), &pool_address, &pool, &NATIVE_MINT, LAMPORTS_PER_SOL, expected)],
+        &[&user],
+    )
+    .await
+    .unwrap();
+    assert_eq!(env.token_balance(&user.pubkey(), &mint).await, expected);
+    assert_eq!(env.token_balance(&user.pubkey(), &NATIVE_MINT).await, 0);
+
+    let (trade_fee, protocol_fee) = math::trade_fees(LAMPORTS_PER_SOL, &pool.fees).unwrap();
+    assert_eq!(token_account_amount(&mut env, &pool.protocol_token_a_fee).await, protocol_fee);
+
+    //half of the tokens go back the other way
+    let expected_sol = expected_out(&mut env, &pool, expected / 2, false).await;
+    env.send(
+        &[swap(&user.pubkey(), &pool_address, &pool, &mint, expected / 2, expected_sol)],
+        &[&user],
+    )
+    .await
+    .unwrap();
+    assert_eq!(env.token_balance(&user.pubkey(), &NATIVE_MINT).await, expected_sol);
+
+    //all LP is locked, so the escrow is owed the whole LP share of the fees
+    let (token_trade_fee, token_protocol_fee) = math::trade_fees(expected / 2, &pool.fees).unwrap();
+    let lp_sol_fee = trade_fee - protocol_fee;
+    let lp_token_fee = token_trade_fee - token_protocol_fee;
+
+    env.create_token_account(&withdraw_authority.pubkey(), &mint).await;
+    let sol_before = env.token_balance(&withdraw_authority.pubkey(), &NATIVE_MINT).await;
+
+    env.send(
+        &[claim_fee(&withdraw_authority.pubkey(), &pool_address, &pool, u64::MAX)],
+        &[&withdraw_authority],
+    )
+    .await
+    .unwrap();
+
+    let claimed_sol = env.token_balance(&withdraw_authority.pubkey(), &NATIVE_MINT).await - sol_before;
+    let claimed_tokens = env.token_balance(&withdraw_authority.pubkey(), &mint).await;
+    assert_claimed(claimed_sol, lp_sol_fee, &pool);
+    assert_claimed(claimed_tokens, lp_token_fee, &pool);
+
+    let lock_escrow = env
+        .lock_escrow(&amm_pda::lock_escrow(&pool_address, &withdraw_authority.pubkey()))
+        .await;
+    assert_eq!(lock_escrow.a_fee, 0);
+    assert_eq!(lock_escrow.b_fee, 0);
+}
+
+#[tokio::test]
+async fn cant_create_pool_before_withdraw() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let withdraw_authority = env.withdraw_authority.insecure_clone();
+    env.complete_curve(&mint).await;
+
+    env.wrap_sol(&withdraw_authority, LAMPORTS_PER_SOL).await;
+    env.create_token_account(&withdraw_authority.pubkey(), &mint).await;
+
+    let config = env.set_amm_config(default_pool_fees(), Pubkey::default()).await;
+    let result = env.create_pool(&mint, &config).await;
+    assert_error(result, CurveLaunchpadError::BondingCurveComplete);
+}
+
+#[tokio::test]
+async fn only_withdraw_authority_can_create_pool() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let creator = env.creator.insecure_clone();
+
+    graduate(&mut env, &mint).await;
+
+    //the creator holds both tokens too, but isn't the withdraw authority
+    env.wrap_sol(&creator, LAMPORTS_PER_SOL).await;
+
+    let config = env.set_amm_config(default_pool_fees(), Pubkey::default()).await;
+    let accounts = create_pool_accounts(&creator.pubkey(), &config, &mint);
+    let result = env
+        .send(&[instructions::create_pool(accounts)], &[&creator])
+        .await;
+    assert_error(result, CurveLaunchpadError::InvalidWithdrawAuthority);
+}
+
+#[tokio::test]
+async fn amm_config_restricts_pool_creator() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+
+    graduate(&mut env, &mint).await;
+
+    let config = env
+        .set_amm_config(PoolFees::default(), Keypair::new().pubkey())
+        .await;
+    let result = env.create_pool(&mint, &config).await;
+    assert_amm_error(result, AmmError::InvalidAdminAccount);
+}
+
+async fn token_account_amount(env: &mut TestEnv, address: &Pubkey) -> u64 {
+    let data = env.account_data(address).await;
+    anchor_spl::token::TokenAccount::try_deserialize(&mut &data[..])
+        .unwrap()
+        .amount
+}
+
+/// What the pool pays out for `in_amount` right now.
+async fn expected_out(env: &mut TestEnv, pool: &Pool, in_amount: u64, a_to_b: bool) -> u64 {
+    let a_reserve = token_account_amount(env, &pool.a_token_vault).await;
+    let b_reserve = token_account_amount(env, &pool.b_token_vault).await;
+    let (in_reserve, out_reserve) = if a_to_b { (a_reserve, b_reserve) } else { (b_reserve, a_reserve) };
+
+    let (trade_fee, _) = math::trade_fees(in_amount, &pool.fees).unwrap();
+    math::swap_out_amount(in_amount - trade_fee, in_reserve, out_reserve).unwrap()
+}
+
+/// Fee per LP is floored, which loses up to one unit per `FEE_PER_LP_PRECISION` LP.
+fn assert_claimed(claimed: u64, owed: u64, pool: &Pool) {
+    let max_rounding = (pool.lp_supply as u128 / Pool::FEE_PER_LP_PRECISION) as u64 + 1;
+    assert!(claimed <= owed && owed - claimed <= max_rounding, "claimed {claimed} of {owed}");
+}
+
+fn assert_amm_error(result: std::result::Result<(), solana_program_test::BanksClientError>, expected: AmmError) {
+    assert_eq!(error_code(result), Some(expected.into()), "expected {expected:?}");
+}
@@ -0,0 +1,58 @@
+//! Where the value goes on a trade: tokens move between the trader and the
+//! curve's vault without being minted or burned, and a sale's fee comes out
+//! of its proceeds.
+
+mod common;
+
+use common::*;
+use curve_launchpad::instructions::calculate_fee;
+use curve_launchpad_client::pda;
+use solana_sdk::signer::Signer;
+
+#[tokio::test]
+async fn buy_transfers_tokens_out_of_the_curve() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let user = env.funded_user(10 * LAMPORTS_PER_SOL).await;
+    let curve = pda::bonding_curve(&mint);
+    let token_amount = DEFAULT_TOKEN_BALANCE / 100;
+
+    let curve_tokens = env.token_balance(&curve, &mint).await;
+
+    env.buy(&user, &mint, token_amount, u64::MAX).await.unwrap();
+
+    assert_eq!(env.token_balance(&user.pubkey(), &mint).await, token_amount);
+    assert_eq!(env.token_balance(&curve, &mint).await, curve_tokens - token_amount);
+    assert_eq!(env.mint(&mint).await.supply, DEFAULT_TOKEN_BALANCE);
+}
+
+#[tokio::test]
+async fn sell_transfers_tokens_back_and_pays_the_fee_from_proceeds() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_curve().await;
+    let user = env.funded_user(10 * LAMPORTS_PER_SOL).await;
+    let fee_recipient = env.fee_recipient.pubkey();
+    let curve = pda::bonding_curve(&mint);
+    let token_amount = DEFAULT_TOKEN_BALANCE / 1_000;
+
+    env.buy(&user, &mint, DEFAULT_TOKEN_BALANCE / 100, u64::MAX)
+        .await
+        .unwrap();
+
+    let curve_tokens = env.token_balance(&curve, &mint).await;
+    let curve_lamports = env.balance(&curve).await;
+    let user_lamports = env.balance(&user.pubkey()).await;
+    let fee_recipient_lamports = env.balance(&fee_recipient).await;
+
+    env.sell(&user, &mint, token_amount, 0).await.unwrap();
+
+    assert_eq!(env.token_balance(&curve, &mint).await, curve_tokens + token_amount);
+    assert_eq!(env.mint(&mint).await.supply, DEFAULT_TOKEN_BALANCE);
+
+    //the curve pays out the sale once, split between the seller and the fee recipient
+    let proceeds = curve_lamports - env.balance(&curve).await;
+    let fee = env.balance(&fee_recipient).await - fee_recipient_lamports;
+    assert_eq!(fee, calculate_fee(proceeds, DEFAULT_FEE_BASIS_POINTS));
+    //the seller also pays the 5000 lamport signature fee
+    assert_eq!(env.balance(&user.pubkey()).await + 5_000 - user_lamports, proceeds - fee);
+}
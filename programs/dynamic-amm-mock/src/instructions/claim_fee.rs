@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::{
+    state::{LockEscrow, Pool},
+    AmmError,
+};
+
+#[derive(Accounts)]
+pub struct ClaimFee<'info> {
+    #[account(
+        mut,
+        has_one = lp_mint @ AmmError::MismatchedLpMint,
+        has_one = a_token_vault @ AmmError::InvalidVaultAccount,
+        has_one = b_token_vault @ AmmError::InvalidVaultAccount,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(mut)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        has_one = pool,
+        has_one = owner @ AmmError::MismatchedOwner,
+        has_one = escrow_vault,
+    )]
+    pub lock_escrow: Box<Account<'info, LockEscrow>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: The real program stages the claimed LP here, the mock pays fees directly
+    #[account(mut)]
+    pub source_tokens: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub escrow_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+
+    #[account(mut)]
+    pub a_token_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub b_token_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Vault program account for token A, checked against the pool
+    #[account(mut, address = pool.a_vault @ AmmError::InvalidVaultAccount)]
+    pub a_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Vault program account for token B, checked against the pool
+    #[account(mut, address = pool.b_vault @ AmmError::InvalidVaultAccount)]
+    pub b_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Pool's position in vault A, checked against the pool
+    #[account(mut, address = pool.a_vault_lp @ AmmError::InvalidVaultLpAccount)]
+    pub a_vault_lp: UncheckedAccount<'info>,
+
+    /// CHECK: Pool's position in vault B, checked against the pool
+    #[account(mut, address = pool.b_vault_lp @ AmmError::InvalidVaultLpAccount)]
+    pub b_vault_lp: UncheckedAccount<'info>,
+
+    /// CHECK: LP mint of vault A, checked against the pool
+    #[account(mut, address = pool.a_vault_lp_mint @ AmmError::InvalidVaultLpAccount)]
+    pub a_vault_lp_mint: UncheckedAccount<'info>,
+
+    /// CHECK: LP mint of vault B, checked against the pool
+    #[account(mut, address = pool.b_vault_lp_mint @ AmmError::InvalidVaultLpAccount)]
+    pub b_vault_lp_mint: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = user_a_token.mint == pool.token_a_mint @ AmmError::MismatchedTokenMint)]
+    pub user_a_token: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = user_b_token.mint == pool.token_b_mint @ AmmError::MismatchedTokenMint)]
+    pub user_b_token: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Vault program, checked against the pool's vaults
+    pub vault_program: UncheckedAccount<'info>,
+}
+
+pub fn claim_fee(ctx: Context<ClaimFee>, max_amount: u64) -> Result<()> {
+    let lock_escrow = &mut ctx.accounts.lock_escrow;
+    lock_escrow
+        .accrue_fees(&ctx.accounts.pool)
+        .ok_or(AmmError::MathOverflow)?;
+
+    let a_amount = lock_escrow.a_fee.min(max_amount);
+    let b_amount = lock_escrow.b_fee.min(max_amount);
+
+    let pool = &ctx.accounts.pool;
+    pool.with_signer(|signer| {
+        for (from, to, amount) in [
+            (&ctx.accounts.a_token_vault, &ctx.accounts.user_a_token, a_amount),
+            (&ctx.accounts.b_token_vault, &ctx.accounts.user_b_token, b_amount),
+        ] {
+            if amount == 0 {
+                continue;
+            }
+
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: from.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: pool.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount,
+            )?;
+        }
+
+        Ok::<_, Error>(())
+    })?;
+
+    lock_escrow.a_fee -= a_amount;
+    lock_escrow.b_fee -= b_amount;
+
+    msg!("claimed {} A and {} B fees from {}", a_amount, b_amount, lock_escrow.key());
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::get_associated_token_address, token::Mint};
+
+use crate::{
+    state::{LockEscrow, Pool},
+    AmmError,
+};
+
+#[derive(Accounts)]
+pub struct CreateLockEscrow<'info> {
+    #[account(has_one = lp_mint @ AmmError::MismatchedLpMint)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + LockEscrow::INIT_SPACE,
+        seeds = [b"lock_escrow", pool.to_account_info().key.as_ref(), owner.key.as_ref()],
+        bump,
+    )]
+    pub lock_escrow: Box<Account<'info, LockEscrow>>,
+
+    /// CHECK: Owner of the escrow, who claims its fees
+    pub owner: UncheckedAccount<'info>,
+
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_lock_escrow(ctx: Context<CreateLockEscrow>) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    let lock_escrow = &mut ctx.accounts.lock_escrow;
+
+    lock_escrow.pool = pool.key();
+    lock_escrow.owner = ctx.accounts.owner.key();
+    lock_escrow.escrow_vault = get_associated_token_address(&lock_escrow.key(), &pool.lp_mint);
+    lock_escrow.bump = ctx.bumps.lock_escrow;
+    lock_escrow.total_locked_amount = 0;
+    lock_escrow.a_fee = 0;
+    lock_escrow.b_fee = 0;
+
+    //fees earned before the escrow existed belong to other LP holders
+    lock_escrow.a_fee_per_lp_checkpoint = pool.a_fee_per_lp;
+    lock_escrow.b_fee_per_lp_checkpoint = pool.b_fee_per_lp;
+
+    Ok(())
+}
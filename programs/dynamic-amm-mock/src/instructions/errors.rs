@@ -0,0 +1,46 @@
+use anchor_lang::error_code;
+
+//same order as the real program so error codes match, only some are used
+#[error_code]
+pub enum AmmError {
+    #[msg("Math operation overflow")]
+    MathOverflow,
+    #[msg("Invalid fee setup")]
+    InvalidFee,
+    #[msg("Invalid invariant d")]
+    InvalidInvariant,
+    #[msg("Fee calculation failure")]
+    FeeCalculationFailure,
+    #[msg("Exceeded slippage tolerance")]
+    ExceededSlippage,
+    #[msg("Invalid curve calculation")]
+    InvalidCalculation,
+    #[msg("Given pool token amount results in zero trading tokens")]
+    ZeroTradingTokens,
+    #[msg("Math conversion overflow")]
+    ConversionError,
+    #[msg("LP mint authority must be 'A' vault lp, without freeze authority, and 0 supply")]
+    FaultyLpMint,
+    #[msg("Token mint mismatched")]
+    MismatchedTokenMint,
+    #[msg("LP mint mismatched")]
+    MismatchedLpMint,
+    #[msg("Invalid lp token owner")]
+    MismatchedOwner,
+    #[msg("Invalid vault account")]
+    InvalidVaultAccount,
+    #[msg("Invalid vault lp account")]
+    InvalidVaultLpAccount,
+    #[msg("Invalid pool lp mint account")]
+    InvalidPoolLpMintAccount,
+    #[msg("Pool disabled")]
+    PoolDisabled,
+    #[msg("Invalid admin account")]
+    InvalidAdminAccount,
+    #[msg("Invalid protocol fee account")]
+    InvalidProtocolFeeAccount,
+    #[msg("Same admin account")]
+    SameAdminAccount,
+    #[msg("Identical user source and destination token account")]
+    IdenticalSourceDestination,
+}
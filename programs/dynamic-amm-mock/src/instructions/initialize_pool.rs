@@ -0,0 +1,243 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, MintTo, Token, TokenAccount, Transfer},
+};
+
+use crate::{
+    math, pda,
+    state::{Config, Pool},
+    AmmError,
+};
+
+#[derive(Accounts)]
+pub struct InitializePermissionlessConstantProductPoolWithConfig<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Pool::INIT_SPACE,
+        seeds = [
+            pda::first_key(token_a_mint.to_account_info().key, token_b_mint.to_account_info().key).as_ref(),
+            pda::second_key(token_a_mint.to_account_info().key, token_b_mint.to_account_info().key).as_ref(),
+            config.to_account_info().key.as_ref(),
+        ],
+        bump,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"lp_mint", pool.to_account_info().key.as_ref()],
+        bump,
+        mint::decimals = token_a_mint.decimals,
+        mint::authority = pool,
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    pub token_a_mint: Box<Account<'info, Mint>>,
+
+    pub token_b_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Vault program account for token A, only its address is checked
+    #[account(mut, address = pda::vault(token_a_mint.to_account_info().key) @ AmmError::InvalidVaultAccount)]
+    pub a_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Vault program account for token B, only its address is checked
+    #[account(mut, address = pda::vault(token_b_mint.to_account_info().key) @ AmmError::InvalidVaultAccount)]
+    pub b_vault: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"token_vault", pool.to_account_info().key.as_ref(), token_a_mint.to_account_info().key.as_ref()],
+        bump,
+        token::mint = token_a_mint,
+        token::authority = pool,
+    )]
+    pub a_token_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"token_vault", pool.to_account_info().key.as_ref(), token_b_mint.to_account_info().key.as_ref()],
+        bump,
+        token::mint = token_b_mint,
+        token::authority = pool,
+    )]
+    pub b_token_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: LP mint of vault A, only its address is checked
+    #[account(mut, address = pda::vault_lp_mint(a_vault.key) @ AmmError::InvalidVaultLpAccount)]
+    pub a_vault_lp_mint: UncheckedAccount<'info>,
+
+    /// CHECK: LP mint of vault B, only its address is checked
+    #[account(mut, address = pda::vault_lp_mint(b_vault.key) @ AmmError::InvalidVaultLpAccount)]
+    pub b_vault_lp_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Pool's position in vault A, only its address is checked
+    #[account(mut, seeds = [a_vault.key.as_ref(), pool.to_account_info().key.as_ref()], bump)]
+    pub a_vault_lp: UncheckedAccount<'info>,
+
+    /// CHECK: Pool's position in vault B, only its address is checked
+    #[account(mut, seeds = [b_vault.key.as_ref(), pool.to_account_info().key.as_ref()], bump)]
+    pub b_vault_lp: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = token_a_mint,
+        token::authority = payer,
+    )]
+    pub payer_token_a: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = token_b_mint,
+        token::authority = payer,
+    )]
+    pub payer_token_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = lp_mint,
+        associated_token::authority = payer,
+    )]
+    pub payer_pool_lp: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"fee", token_a_mint.to_account_info().key.as_ref(), pool.to_account_info().key.as_ref()],
+        bump,
+        token::mint = token_a_mint,
+        token::authority = pool,
+    )]
+    pub protocol_token_a_fee: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"fee", token_b_mint.to_account_info().key.as_ref(), pool.to_account_info().key.as_ref()],
+        bump,
+        token::mint = token_b_mint,
+        token::authority = pool,
+    )]
+    pub protocol_token_b_fee: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: LP mint metadata, the mock doesn't create it but checks its address
+    #[account(mut, address = pda::mint_metadata(lp_mint.to_account_info().key))]
+    pub mint_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex token metadata program
+    #[account(address = pda::METADATA_PROGRAM_ID)]
+    pub metadata_program: UncheckedAccount<'info>,
+
+    /// CHECK: Vault program
+    #[account(address = pda::VAULT_PROGRAM_ID)]
+    pub vault_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_permissionless_constant_product_pool_with_config(
+    ctx: Context<InitializePermissionlessConstantProductPoolWithConfig>,
+    token_a_amount: u64,
+    token_b_amount: u64,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+
+    //an unset creator authority leaves the config open to anyone
+    if config.pool_creator_authority != Pubkey::default() {
+        require_keys_eq!(
+            ctx.accounts.payer.key(),
+            config.pool_creator_authority,
+            AmmError::InvalidAdminAccount
+        );
+    }
+
+    require_keys_neq!(
+        ctx.accounts.token_a_mint.key(),
+        ctx.accounts.token_b_mint.key(),
+        AmmError::MismatchedTokenMint
+    );
+
+    let lp_amount = math::initial_lp_amount(token_a_amount, token_b_amount);
+    require!(lp_amount > 0, AmmError::ZeroTradingTokens);
+
+    for (from, to, amount) in [
+        (&ctx.accounts.payer_token_a, &ctx.accounts.a_token_vault, token_a_amount),
+        (&ctx.accounts.payer_token_b, &ctx.accounts.b_token_vault, token_b_amount),
+    ] {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: from.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: ctx.accounts.payer.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+    }
+
+    let pool = &mut ctx.accounts.pool;
+    pool.lp_mint = ctx.accounts.lp_mint.key();
+    pool.token_a_mint = ctx.accounts.token_a_mint.key();
+    pool.token_b_mint = ctx.accounts.token_b_mint.key();
+    pool.config = config.key();
+    pool.a_vault = ctx.accounts.a_vault.key();
+    pool.b_vault = ctx.accounts.b_vault.key();
+    pool.a_token_vault = ctx.accounts.a_token_vault.key();
+    pool.b_token_vault = ctx.accounts.b_token_vault.key();
+    pool.a_vault_lp = ctx.accounts.a_vault_lp.key();
+    pool.b_vault_lp = ctx.accounts.b_vault_lp.key();
+    pool.a_vault_lp_mint = ctx.accounts.a_vault_lp_mint.key();
+    pool.b_vault_lp_mint = ctx.accounts.b_vault_lp_mint.key();
+    pool.protocol_token_a_fee = ctx.accounts.protocol_token_a_fee.key();
+    pool.protocol_token_b_fee = ctx.accounts.protocol_token_b_fee.key();
+    pool.enabled = true;
+    pool.fees = config.pool_fees;
+    pool.lp_supply = lp_amount;
+    pool.total_locked_lp = 0;
+    pool.a_fee_per_lp = 0;
+    pool.b_fee_per_lp = 0;
+    pool.bump = ctx.bumps.pool;
+
+    pool.with_signer(|signer| {
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    to: ctx.accounts.payer_pool_lp.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer,
+            ),
+            lp_amount,
+        )
+    })?;
+
+    msg!(
+        "initialized pool {} with {} A, {} B and {} LP",
+        pool.key(),
+        token_a_amount,
+        token_b_amount,
+        lp_amount
+    );
+
+    Ok(())
+}
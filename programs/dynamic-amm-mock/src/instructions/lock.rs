@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::{
+    state::{LockEscrow, Pool},
+    AmmError,
+};
+
+#[derive(Accounts)]
+pub struct Lock<'info> {
+    #[account(mut, has_one = lp_mint @ AmmError::MismatchedLpMint)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        has_one = pool,
+        has_one = owner @ AmmError::MismatchedOwner,
+        has_one = escrow_vault,
+    )]
+    pub lock_escrow: Box<Account<'info, LockEscrow>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = source_tokens.mint == lp_mint.key() @ AmmError::MismatchedLpMint,
+        constraint = source_tokens.owner == owner.key() @ AmmError::MismatchedOwner,
+    )]
+    pub source_tokens: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub escrow_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: Vault program account for token A, checked against the pool
+    #[account(address = pool.a_vault @ AmmError::InvalidVaultAccount)]
+    pub a_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Vault program account for token B, checked against the pool
+    #[account(address = pool.b_vault @ AmmError::InvalidVaultAccount)]
+    pub b_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Pool's position in vault A, checked against the pool
+    #[account(address = pool.a_vault_lp @ AmmError::InvalidVaultLpAccount)]
+    pub a_vault_lp: UncheckedAccount<'info>,
+
+    /// CHECK: Pool's position in vault B, checked against the pool
+    #[account(address = pool.b_vault_lp @ AmmError::InvalidVaultLpAccount)]
+    pub b_vault_lp: UncheckedAccount<'info>,
+
+    /// CHECK: LP mint of vault A, checked against the pool
+    #[account(address = pool.a_vault_lp_mint @ AmmError::InvalidVaultLpAccount)]
+    pub a_vault_lp_mint: UncheckedAccount<'info>,
+
+    /// CHECK: LP mint of vault B, checked against the pool
+    #[account(address = pool.b_vault_lp_mint @ AmmError::InvalidVaultLpAccount)]
+    pub b_vault_lp_mint: UncheckedAccount<'info>,
+}
+
+pub fn lock(ctx: Context<Lock>, max_amount: u64) -> Result<()> {
+    require!(max_amount > 0, AmmError::ZeroTradingTokens);
+
+    //settle what the already locked LP earned before the lock grows
+    let lock_escrow = &mut ctx.accounts.lock_escrow;
+    lock_escrow
+        .accrue_fees(&ctx.accounts.pool)
+        .ok_or(AmmError::MathOverflow)?;
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.source_tokens.to_account_info(),
+                to: ctx.accounts.escrow_vault.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        max_amount,
+    )?;
+
+    lock_escrow.total_locked_amount = lock_escrow
+        .total_locked_amount
+        .checked_add(max_amount)
+        .ok_or(AmmError::MathOverflow)?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.total_locked_lp = pool
+        .total_locked_lp
+        .checked_add(max_amount)
+        .ok_or(AmmError::MathOverflow)?;
+
+    msg!("locked {} LP in {}", max_amount, lock_escrow.key());
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+pub mod errors;
+pub mod initialize_pool;
+pub mod create_lock_escrow;
+pub mod lock;
+pub mod claim_fee;
+pub mod swap;
+
+pub use errors::*;
+pub use initialize_pool::*;
+pub use create_lock_escrow::*;
+pub use lock::*;
+pub use claim_fee::*;
+pub use swap::*;
@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::{math, state::Pool, AmmError};
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(
+        mut,
+        has_one = a_token_vault @ AmmError::InvalidVaultAccount,
+        has_one = b_token_vault @ AmmError::InvalidVaultAccount,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(mut)]
+    pub user_source_token: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user_destination_token: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Vault program account for token A, checked against the pool
+    #[account(mut, address = pool.a_vault @ AmmError::InvalidVaultAccount)]
+    pub a_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Vault program account for token B, checked against the pool
+    #[account(mut, address = pool.b_vault @ AmmError::InvalidVaultAccount)]
+    pub b_vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub a_token_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub b_token_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: LP mint of vault A, checked against the pool
+    #[account(mut, address = pool.a_vault_lp_mint @ AmmError::InvalidVaultLpAccount)]
+    pub a_vault_lp_mint: UncheckedAccount<'info>,
+
+    /// CHECK: LP mint of vault B, checked against the pool
+    #[account(mut, address = pool.b_vault_lp_mint @ AmmError::InvalidVaultLpAccount)]
+    pub b_vault_lp_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Pool's position in vault A, checked against the pool
+    #[account(mut, address = pool.a_vault_lp @ AmmError::InvalidVaultLpAccount)]
+    pub a_vault_lp: UncheckedAccount<'info>,
+
+    /// CHECK: Pool's position in vault B, checked against the pool
+    #[account(mut, address = pool.b_vault_lp @ AmmError::InvalidVaultLpAccount)]
+    pub b_vault_lp: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub protocol_token_fee: Box<Account<'info, TokenAccount>>,
+
+    pub user: Signer<'info>,
+
+    /// CHECK: Vault program, checked against the pool's vaults
+    pub vault_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn swap(ctx: Context<Swap>, in_amount: u64, minimum_out_amount: u64) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    require!(pool.enabled, AmmError::PoolDisabled);
+
+    require_keys_neq!(
+        ctx.accounts.user_source_token.key(),
+        ctx.accounts.user_destination_token.key(),
+        AmmError::IdenticalSourceDestination
+    );
+
+    let source_mint = ctx.accounts.user_source_token.mint;
+    let destination_mint = ctx.accounts.user_destination_token.mint;
+
+    let a_to_b = if source_mint == pool.token_a_mint && destination_mint == pool.token_b_mint {
+        true
+    } else if source_mint == pool.token_b_mint && destination_mint == pool.token_a_mint {
+        false
+    } else {
+        return err!(AmmError::MismatchedTokenMint);
+    };
+
+    let (in_vault, out_vault, protocol_fee_account) = if a_to_b {
+        (&ctx.accounts.a_token_vault, &ctx.accounts.b_token_vault, pool.protocol_token_a_fee)
+    } else {
+        (&ctx.accounts.b_token_vault, &ctx.accounts.a_token_vault, pool.protocol_token_b_fee)
+    };
+
+    require_keys_eq!(
+        ctx.accounts.protocol_token_fee.key(),
+        protocol_fee_account,
+        AmmError::InvalidProtocolFeeAccount
+    );
+
+    let (trade_fee, protocol_fee) =
+        math::trade_fees(in_amount, &pool.fees).ok_or(AmmError::FeeCalculationFailure)?;
+
+    let out_amount = math::swap_out_amount(in_amount - trade_fee, in_vault.amount, out_vault.amount)
+        .ok_or(AmmError::MathOverflow)?;
+
+    require!(out_amount > 0, AmmError::ZeroTradingTokens);
+    require!(out_amount >= minimum_out_amount, AmmError::ExceededSlippage);
+
+    for (to, amount) in [
+        (in_vault.to_account_info(), in_amount - protocol_fee),
+        (ctx.accounts.protocol_token_fee.to_account_info(), protocol_fee),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_source_token.to_account_info(),
+                    to,
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+    }
+
+    pool.with_signer(|signer| {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: out_vault.to_account_info(),
+                    to: ctx.accounts.user_destination_token.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer,
+            ),
+            out_amount,
+        )
+    })?;
+
+    //the rest of the trade fee stays in the pool and is owed to LP holders
+    let fee_per_lp = ((trade_fee - protocol_fee) as u128)
+        .checked_mul(Pool::FEE_PER_LP_PRECISION)
+        .ok_or(AmmError::MathOverflow)?
+        / pool.lp_supply as u128;
+
+    let pool = &mut ctx.accounts.pool;
+    if a_to_b {
+        pool.a_fee_per_lp += fee_per_lp;
+    } else {
+        pool.b_fee_per_lp += fee_per_lp;
+    }
+
+    msg!("swapped {} in for {} out", in_amount, out_amount);
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+//! Stand-in for the Meteora dynamic AMM so graduation can run without the
+//! real program. It answers the instructions in `idls/dynamic_amm.json` the
+//! launchpad uses, with the same names, accounts and arguments, and checks
+//! accounts the way the real program does where the mock's simpler layout
+//! allows.
+//!
+//! The vault program layer is collapsed: each pool keeps its reserves in
+//! its own token accounts, and the vault, vault LP and vault LP mint
+//! accounts are recorded on the pool and checked against it but not used.
+
+use anchor_lang::prelude::*;
+
+use instructions::*;
+
+pub mod instructions;
+pub mod math;
+pub mod pda;
+pub mod state;
+
+declare_id!("Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB");
+
+#[program]
+pub mod amm {
+
+    use super::*;
+
+    pub fn initialize_permissionless_constant_product_pool_with_config(
+        ctx: Context<InitializePermissionlessConstantProductPoolWithConfig>,
+        token_a_amount: u64,
+        token_b_amount: u64,
+    ) -> Result<()> {
+        initialize_pool::initialize_permissionless_constant_product_pool_with_config(
+            ctx,
+            token_a_amount,
+            token_b_amount,
+        )
+    }
+
+    pub fn create_lock_escrow(ctx: Context<CreateLockEscrow>) -> Result<()> {
+        create_lock_escrow::create_lock_escrow(ctx)
+    }
+
+    pub fn lock(ctx: Context<Lock>, max_amount: u64) -> Result<()> {
+        lock::lock(ctx, max_amount)
+    }
+
+    pub fn claim_fee(ctx: Context<ClaimFee>, max_amount: u64) -> Result<()> {
+        claim_fee::claim_fee(ctx, max_amount)
+    }
+
+    pub fn swap(ctx: Context<Swap>, in_amount: u64, minimum_out_amount: u64) -> Result<()> {
+        swap::swap(ctx, in_amount, minimum_out_amount)
+    }
+}
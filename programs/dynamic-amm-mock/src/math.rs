@@ -0,0 +1,86 @@
+use crate::state::PoolFees;
+
+/// LP minted for the first deposit, the geometric mean of the two amounts.
+pub fn initial_lp_amount(token_a_amount: u64, token_b_amount: u64) -> u64 {
+    isqrt(token_a_amount as u128 * token_b_amount as u128) as u64
+}
+
+/// The trade fee on `amount` and the protocol's part of it.
+pub fn trade_fees(amount: u64, fees: &PoolFees) -> Option<(u64, u64)> {
+    let trade_fee = fraction(amount, fees.trade_fee_numerator, fees.trade_fee_denominator)?;
+    let protocol_fee = fraction(
+        trade_fee,
+        fees.protocol_trade_fee_numerator,
+        fees.protocol_trade_fee_denominator,
+    )?;
+
+    Some((trade_fee, protocol_fee))
+}
+
+/// Output of a constant product swap of `in_amount`, fees already taken out.
+pub fn swap_out_amount(in_amount: u64, in_reserve: u64, out_reserve: u64) -> Option<u64> {
+    let numerator = (out_reserve as u128).checked_mul(in_amount as u128)?;
+    let denominator = (in_reserve as u128).checked_add(in_amount as u128)?;
+
+    u64::try_from(numerator.checked_div(denominator)?).ok()
+}
+
+fn fraction(amount: u64, numerator: u64, denominator: u64) -> Option<u64> {
+    if denominator == 0 {
+        return Some(0);
+    }
+
+    u64::try_from((amount as u128).checked_mul(numerator as u128)? / denominator as u128).ok()
+}
+
+fn isqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+
+    //newton's method from an overestimate converges down to the floor
+    let mut x = value;
+    let mut y = (x + value / x) / 2;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_initial_lp_amount() {
+        assert_eq!(initial_lp_amount(0, 100), 0);
+        assert_eq!(initial_lp_amount(4, 9), 6);
+        assert_eq!(initial_lp_amount(10, 10), 10);
+        assert_eq!(initial_lp_amount(2, 1), 1);
+        assert_eq!(initial_lp_amount(u64::MAX, u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_trade_fees() {
+        let fees = PoolFees {
+            trade_fee_numerator: 25,
+            trade_fee_denominator: 10_000,
+            protocol_trade_fee_numerator: 20,
+            protocol_trade_fee_denominator: 100,
+        };
+
+        assert_eq!(trade_fees(1_000_000, &fees), Some((2_500, 500)));
+        assert_eq!(trade_fees(1, &fees), Some((0, 0)));
+        assert_eq!(trade_fees(1_000_000, &PoolFees::default()), Some((0, 0)));
+    }
+
+    #[test]
+    fn test_swap_out_amount() {
+        assert_eq!(swap_out_amount(100, 1_000, 1_000), Some(90));
+        assert_eq!(swap_out_amount(0, 1_000, 1_000), Some(0));
+        assert_eq!(swap_out_amount(1_000, 0, 0), Some(0));
+        assert_eq!(swap_out_amount(0, 0, 1_000), None);
+    }
+}
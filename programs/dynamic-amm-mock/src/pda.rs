@@ -0,0 +1,70 @@
+//! Addresses of the accounts the AMM uses, derived the way the real program
+//! derives them except for the token vaults, which the mock owns itself.
+
+use anchor_lang::{prelude::Pubkey, pubkey};
+
+pub const VAULT_PROGRAM_ID: Pubkey = pubkey!("24Uqj9JCLxUeoC3hGfh5W3s9FM9uCHDS2SG3LYwBpyTi");
+pub const VAULT_BASE_KEY: Pubkey = pubkey!("HWzXGcGHy4tcpYfaRDCyLNzXqBTv3E6BttpCH2vJxArv");
+pub const METADATA_PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+pub fn first_key(key1: &Pubkey, key2: &Pubkey) -> Pubkey {
+    *key1.max(key2)
+}
+
+pub fn second_key(key1: &Pubkey, key2: &Pubkey) -> Pubkey {
+    *key1.min(key2)
+}
+
+pub fn pool(token_a_mint: &Pubkey, token_b_mint: &Pubkey, config: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            first_key(token_a_mint, token_b_mint).as_ref(),
+            second_key(token_a_mint, token_b_mint).as_ref(),
+            config.as_ref(),
+        ],
+        &crate::ID,
+    )
+    .0
+}
+
+pub fn lp_mint(pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"lp_mint", pool.as_ref()], &crate::ID).0
+}
+
+pub fn token_vault(pool: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"token_vault", pool.as_ref(), mint.as_ref()], &crate::ID).0
+}
+
+pub fn protocol_fee(mint: &Pubkey, pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"fee", mint.as_ref(), pool.as_ref()], &crate::ID).0
+}
+
+pub fn lock_escrow(pool: &Pubkey, owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"lock_escrow", pool.as_ref(), owner.as_ref()], &crate::ID).0
+}
+
+/// The vault program's shared vault for `mint`.
+pub fn vault(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"vault", mint.as_ref(), VAULT_BASE_KEY.as_ref()],
+        &VAULT_PROGRAM_ID,
+    )
+    .0
+}
+
+pub fn vault_lp_mint(vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"lp_mint", vault.as_ref()], &VAULT_PROGRAM_ID).0
+}
+
+/// The pool's position in `vault`.
+pub fn vault_lp(vault: &Pubkey, pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[vault.as_ref(), pool.as_ref()], &crate::ID).0
+}
+
+pub fn mint_metadata(lp_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", METADATA_PROGRAM_ID.as_ref(), lp_mint.as_ref()],
+        &METADATA_PROGRAM_ID,
+    )
+    .0
+}
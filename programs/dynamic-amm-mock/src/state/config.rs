@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+/// Same layout as the real program's `Config`, so configs dumped from a
+/// cluster load as they are.
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub pool_fees: PoolFees,
+    pub activation_duration: u64,
+    pub vault_config_key: Pubkey,
+    /// Only this key can create pools with the config, anyone if unset.
+    pub pool_creator_authority: Pubkey,
+    pub activation_type: u8,
+    pub padding: [u8; 227],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct PoolFees {
+    /// Kept in the pool, raising the value of its LP.
+    pub trade_fee_numerator: u64,
+    pub trade_fee_denominator: u64,
+    /// Part of the trade fee paid out to the protocol fee account.
+    pub protocol_trade_fee_numerator: u64,
+    pub protocol_trade_fee_denominator: u64,
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Pool;
+
+/// LP locked into a pool for good. The owner keeps claiming the trade fees
+/// the locked LP earns.
+#[account]
+#[derive(InitSpace)]
+pub struct LockEscrow {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub escrow_vault: Pubkey,
+    pub bump: u8,
+    pub total_locked_amount: u64,
+    /// Fees earned but not claimed yet.
+    pub a_fee: u64,
+    pub b_fee: u64,
+    /// The pool's fee per LP as of the last time fees were accrued.
+    pub a_fee_per_lp_checkpoint: u128,
+    pub b_fee_per_lp_checkpoint: u128,
+}
+
+impl LockEscrow {
+    /// Credits the fees the locked LP earned since the last checkpoint.
+    pub fn accrue_fees(&mut self, pool: &Pool) -> Option<()> {
+        let locked = self.total_locked_amount as u128;
+
+        let a_fee = pool
+            .a_fee_per_lp
+            .checked_sub(self.a_fee_per_lp_checkpoint)?
+            .checked_mul(locked)?
+            / Pool::FEE_PER_LP_PRECISION;
+        let b_fee = pool
+            .b_fee_per_lp
+            .checked_sub(self.b_fee_per_lp_checkpoint)?
+            .checked_mul(locked)?
+            / Pool::FEE_PER_LP_PRECISION;
+
+        self.a_fee = self.a_fee.checked_add(u64::try_from(a_fee).ok()?)?;
+        self.b_fee = self.b_fee.checked_add(u64::try_from(b_fee).ok()?)?;
+        self.a_fee_per_lp_checkpoint = pool.a_fee_per_lp;
+        self.b_fee_per_lp_checkpoint = pool.b_fee_per_lp;
+
+        Some(())
+    }
+}
@@ -0,0 +1,7 @@
+pub mod config;
+pub mod pool;
+pub mod lock_escrow;
+
+pub use config::*;
+pub use pool::*;
+pub use lock_escrow::*;
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{pda, state::PoolFees};
+
+/// A constant product pool. Unlike the real program's `Pool` it holds its
+/// reserves directly in `a_token_vault` and `b_token_vault`.
+#[account]
+#[derive(InitSpace)]
+pub struct Pool {
+    pub lp_mint: Pubkey,
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    pub config: Pubkey,
+    pub a_vault: Pubkey,
+    pub b_vault: Pubkey,
+    pub a_token_vault: Pubkey,
+    pub b_token_vault: Pubkey,
+    pub a_vault_lp: Pubkey,
+    pub b_vault_lp: Pubkey,
+    pub a_vault_lp_mint: Pubkey,
+    pub b_vault_lp_mint: Pubkey,
+    pub protocol_token_a_fee: Pubkey,
+    pub protocol_token_b_fee: Pubkey,
+    pub enabled: bool,
+    pub fees: PoolFees,
+    /// LP minted when the pool was seeded, the mock has no other deposits.
+    pub lp_supply: u64,
+    pub total_locked_lp: u64,
+    /// Trade fees left in the pool per LP token, scaled by `FEE_PER_LP_PRECISION`.
+    pub a_fee_per_lp: u128,
+    pub b_fee_per_lp: u128,
+    pub bump: u8,
+}
+
+impl Pool {
+    pub const FEE_PER_LP_PRECISION: u128 = 1_000_000_000_000;
+
+    /// Runs `f` with the seeds the pool signs its token transfers with.
+    pub fn with_signer<R>(&self, f: impl FnOnce(&[&[&[u8]]]) -> R) -> R {
+        let first = pda::first_key(&self.token_a_mint, &self.token_b_mint);
+        let second = pda::second_key(&self.token_a_mint, &self.token_b_mint);
+
+        f(&[&[first.as_ref(), second.as_ref(), self.config.as_ref(), &[self.bump]]])
+    }
+}
@@ -0,0 +1,118 @@
+//! Example of another program trading on the launchpad through CPI. The
+//! launchpad's `buy` and `sell` return a `TradeResult` as return data, which
+//! the `cpi` feature hands back typed, so the caller learns what was executed
+//! without reading balances before and after.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use curve_launchpad::{cpi, instructions::TradeResult, program::CurveLaunchpad};
+
+declare_id!("Q35zTP1YRHEPaWC7a8utNWKTSqgW4HzzpNoDUBmj3vz");
+
+#[program]
+pub mod launchpad_caller {
+
+    use super::*;
+
+    /// Buys up to `token_amount`, failing if the launchpad fills less than `min_token_amount`.
+    pub fn buy(
+        ctx: Context<Trade>,
+        token_amount: u64,
+        max_sol_cost: u64,
+        min_token_amount: u64,
+    ) -> Result<TradeResult> {
+        let accounts = cpi::accounts::Buy {
+            user: ctx.accounts.user.to_account_info(),
+            global: ctx.accounts.global.to_account_info(),
+            fee_recipient: ctx.accounts.fee_recipient.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            bonding_curve: ctx.accounts.bonding_curve.to_account_info(),
+            curve_oracle: ctx.accounts.curve_oracle.to_account_info(),
+            bonding_curve_token_account: ctx.accounts.bonding_curve_token_account.to_account_info(),
+            user_token_account: ctx.accounts.user_token_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            event_authority: ctx.accounts.event_authority.to_account_info(),
+            program: ctx.accounts.curve_launchpad_program.to_account_info(),
+        };
+        let cpi_context = CpiContext::new(ctx.accounts.curve_launchpad_program.to_account_info(), accounts);
+
+        let result = cpi::buy(cpi_context, token_amount, max_sol_cost)?.get();
+
+        //the buy that graduates a curve is only filled up to the graduation target
+        require!(result.token_amount >= min_token_amount, CallerError::PartiallyFilled);
+
+        msg!("bought {} tokens for {} lamports", result.token_amount, result.sol_amount + result.fee);
+
+        Ok(result)
+    }
+
+    pub fn sell(ctx: Context<Trade>, token_amount: u64, min_sol_output: u64) -> Result<TradeResult> {
+        let accounts = cpi::accounts::Sell {
+            user: ctx.accounts.user.to_account_info(),
+            global: ctx.accounts.global.to_account_info(),
+            fee_recipient: ctx.accounts.fee_recipient.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            bonding_curve: ctx.accounts.bonding_curve.to_account_info(),
+            curve_oracle: ctx.accounts.curve_oracle.to_account_info(),
+            bonding_curve_token_account: ctx.accounts.bonding_curve_token_account.to_account_info(),
+            user_token_account: ctx.accounts.user_token_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            event_authority: ctx.accounts.event_authority.to_account_info(),
+            program: ctx.accounts.curve_launchpad_program.to_account_info(),
+        };
+        let cpi_context = CpiContext::new(ctx.accounts.curve_launchpad_program.to_account_info(), accounts);
+
+        let result = cpi::sell(cpi_context, token_amount, min_sol_output)?.get();
+
+        msg!("sold {} tokens for {} lamports", result.token_amount, result.sol_amount - result.fee);
+
+        Ok(result)
+    }
+}
+
+/// The launchpad's `Buy` and `Sell` accounts, which are the same. The launchpad checks them.
+#[derive(Accounts)]
+pub struct Trade<'info> {
+    #[account(mut)]
+    user: Signer<'info>,
+
+    /// CHECK: checked by the launchpad
+    global: UncheckedAccount<'info>,
+
+    /// CHECK: checked by the launchpad
+    #[account(mut)]
+    fee_recipient: UncheckedAccount<'info>,
+
+    mint: Account<'info, Mint>,
+
+    /// CHECK: checked by the launchpad
+    #[account(mut)]
+    bonding_curve: UncheckedAccount<'info>,
+
+    /// CHECK: checked by the launchpad
+    #[account(mut)]
+    curve_oracle: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    bonding_curve_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    user_token_account: Account<'info, TokenAccount>,
+
+    system_program: Program<'info, System>,
+
+    token_program: Program<'info, Token>,
+
+    /// CHECK: the launchpad's event authority, checked by the launchpad
+    event_authority: UncheckedAccount<'info>,
+
+    curve_launchpad_program: Program<'info, CurveLaunchpad>,
+}
+
+#[error_code]
+pub enum CallerError {
+    #[msg("The launchpad filled less than the minimum token amount")]
+    PartiallyFilled,
+}
@@ -0,0 +1,57 @@
+import { expect } from "chai";
+import { AMM } from "../client/amm";
+import vectors from "./vectors/amm.json";
+
+// golden vectors generated from amm.rs by `cargo run -p amm-vectors`
+describe("amm parity", () => {
+  const toAMM = (state: any) =>
+    new AMM(
+      BigInt(state.virtual_sol_reserves),
+      BigInt(state.virtual_token_reserves),
+      BigInt(state.real_sol_reserves),
+      BigInt(state.real_token_reserves)
+    );
+
+  const stateOf = (amm: AMM) => ({
+    virtual_sol_reserves: amm.virtualSolReserves.toString(),
+    virtual_token_reserves: amm.virtualTokenReserves.toString(),
+    real_sol_reserves: amm.realSolReserves.toString(),
+    real_token_reserves: amm.realTokenReserves.toString(),
+  });
+
+  const run = (amm: AMM, action: any) => {
+    const amount = BigInt(action.amount);
+
+    switch (action.type) {
+      case "get_buy_price":
+        return amm.getBuyPrice(amount)?.toString() ?? null;
+      case "get_sell_price":
+        return amm.getSellPrice(amount)?.toString() ?? null;
+      case "apply_buy":
+      case "apply_sell": {
+        const result =
+          action.type === "apply_buy"
+            ? amm.applyBuy(amount)
+            : amm.applySell(amount);
+        return result === null
+          ? null
+          : {
+              token_amount: result.token_amount.toString(),
+              sol_amount: result.sol_amount.toString(),
+            };
+      }
+      default:
+        throw new Error(`unknown action ${action.type}`);
+    }
+  };
+
+  for (const vector of vectors) {
+    it(vector.name, () => {
+      const amm = toAMM(vector.state);
+      const result = run(amm, vector.action);
+
+      expect(result).to.deep.equal(vector.expected.result);
+      expect(stateOf(amm)).to.deep.equal(vector.expected.state);
+    });
+  }
+});
@@ -62,10 +62,7 @@ describe("curve-launchpad", () => {
     let bondingCurveAccount = await program.account.bondingCurve.fetch(
       bondingCurvePDA
     );
-    return ammFromBondingCurve(
-      bondingCurveAccount,
-      DEFUALT_INITIAL_VIRTUAL_TOKEN_RESERVE
-    );
+    return ammFromBondingCurve(bondingCurveAccount);
   };
 
   const assertBondingCurve = (
@@ -93,7 +90,10 @@ describe("curve-launchpad", () => {
       bondingCurveAccount.tokenTotalSupply.toString(),
       DEFAULT_TOKEN_BALANCE.toString()
     );
-    assert.equal(bondingCurveAccount.complete, complete);
+    assert.deepEqual(
+      bondingCurveAccount.status,
+      complete ? { complete: {} } : { trading: {} }
+    );
   };
 
   const simpleBuy = async (
@@ -192,7 +192,11 @@ describe("curve-launchpad", () => {
 
   it("Is initialized!", async () => {
     await program.methods
-      .initialize()
+      .initialize(
+        "Curve Launchpad",
+        "LAUNCH",
+        "https://www.test.com/collection.json"
+      )
       .accounts({
         authority: authority.publicKey,
       })
@@ -234,10 +238,12 @@ describe("curve-launchpad", () => {
     let uri = "https://www.test.com";
 
     const tx = await program.methods
-      .create(name, symbol, uri)
+      .create(name, symbol, uri, null, null, null, false, null, null)
       .accounts({
         mint: mint.publicKey,
         creator: tokenCreator.publicKey,
+        feeRecipient: feeRecipient.publicKey,
+        mintVault: null,
         program: program.programId,
       })
       .transaction();
@@ -321,7 +327,7 @@ describe("curve-launchpad", () => {
       bondingCurveAccount.tokenTotalSupply.toString(),
       DEFAULT_TOKEN_BALANCE.toString()
     );
-    assert.equal(bondingCurveAccount.complete, false);
+    assert.deepEqual(bondingCurveAccount.status, { trading: {} });
   });
 
   it("can buy a token", async () => {
@@ -493,6 +499,7 @@ describe("curve-launchpad", () => {
         .accounts({
           user: withdrawAuthority.publicKey,
           mint: mint.publicKey,
+          program: program.programId,
         })
         .transaction();
 
@@ -707,6 +714,7 @@ describe("curve-launchpad", () => {
         .accounts({
           user: tokenCreator.publicKey,
           mint: mint.publicKey,
+          program: program.programId,
         })
         .transaction();
 
@@ -746,6 +754,7 @@ describe("curve-launchpad", () => {
       .accounts({
         user: withdrawAuthority.publicKey,
         mint: mint.publicKey,
+        program: program.programId,
       })
       .transaction();
 
@@ -757,7 +766,7 @@ describe("curve-launchpad", () => {
     );
 
     let minBalanceRentExempt =
-      await connection.getMinimumBalanceForRentExemption(8 + 41);
+      await connection.getMinimumBalanceForRentExemption(8 + 229);
     let bondingCurvePostSOLBalance = await connection.getBalance(
       bondingCurvePDA
     );
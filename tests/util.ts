@@ -16,9 +16,12 @@ import { getAssociatedTokenAddressSync } from "@solana/spl-token";
 type EventKeys = keyof anchor.IdlEvents<CurveLaunchpad>;
 
 const validEventNames: Array<keyof anchor.IdlEvents<CurveLaunchpad>> = [
+  "closeCurveEvent",
   "completeEvent",
   "createEvent",
+  "redeemEvent",
   "setParamsEvent",
+  "statusChangedEvent",
   "tradeEvent",
 ];
 
@@ -170,16 +173,14 @@ export const fundAccountSOL = async (
 };
 
 export const ammFromBondingCurve = (
-  bondingCurveAccount: anchor.IdlAccounts<CurveLaunchpad>["bondingCurve"] | null,
-  initialVirtualTokenReserves: bigint
+  bondingCurveAccount: anchor.IdlAccounts<CurveLaunchpad>["bondingCurve"] | null
 ) => {
   if(!bondingCurveAccount) throw new Error("Bonding curve account not found");
   return new client.AMM(
     BigInt(bondingCurveAccount.virtualSolReserves.toString()),
     BigInt(bondingCurveAccount.virtualTokenReserves.toString()),
     BigInt(bondingCurveAccount.realSolReserves.toString()),
-    BigInt(bondingCurveAccount.realTokenReserves.toString()),
-    initialVirtualTokenReserves
+    BigInt(bondingCurveAccount.realTokenReserves.toString())
   );
 };
 